@@ -0,0 +1,102 @@
+//! Safetensors files stored as members of a zip archive.
+
+use crate::header::Header;
+use crate::{read_data, trace, Error, LazyReader, Reader, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use zip::{CompressionMethod, ZipArchive};
+
+/// Names of the `.safetensors` members of the zip archive at `path`, in
+/// archive order.
+pub fn list_safetensors_members(path: impl AsRef<Path>) -> Result<Vec<String>> {
+    let zip = ZipArchive::new(BufReader::new(File::open(path)?))?;
+    let mut members = Vec::new();
+    for name in zip.file_names() {
+        let name = name?;
+        if name.ends_with(".safetensors") {
+            members.push(name.into_owned());
+        }
+    }
+    Ok(members)
+}
+
+/// Where an uncompressed member's bytes lie in the archive, or `None` if the
+/// member is compressed.
+fn stored_region(path: &Path, member: &str) -> Result<Option<(u64, u64)>> {
+    let mut zip = ZipArchive::new(BufReader::new(File::open(path)?))?;
+    let file = zip.by_name(member)?;
+    if file.compression() != CompressionMethod::Stored || file.encrypted() {
+        return Ok(None);
+    }
+    Ok(file.data_start().map(|start| (start, file.size())))
+}
+
+impl Reader {
+    /// Loads the safetensors file stored as `member` of the zip archive at
+    /// `path`.
+    ///
+    /// Uncompressed members are read in place with ranged reads, like
+    /// [`Reader::from_file_at`]. Compressed members are decompressed once,
+    /// front to back, with tensors decoded as the stream passes them.
+    pub fn from_zip(path: impl AsRef<Path>, member: &str) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some((offset, len)) = stored_region(path, member)? {
+            return Self::from_file_at(path, offset, Some(len));
+        }
+
+        trace::sequential_read(member);
+        let mut zip = ZipArchive::new(BufReader::new(File::open(path)?))?;
+        let file = zip.by_name(member)?;
+        let size = file.size();
+        read_sequential(file, size)
+    }
+}
+
+impl LazyReader {
+    /// Opens the safetensors file stored as `member` of the zip archive at
+    /// `path` for on-demand loads. Each load reads only that tensor's bytes.
+    ///
+    /// Returns [`Error::CompressedMember`] if the member is compressed, since
+    /// its tensors cannot be reached without decompressing everything before
+    /// them; use [`Reader::from_zip`] instead.
+    pub fn from_zip(path: impl AsRef<Path>, member: &str) -> Result<Self> {
+        let path = path.as_ref();
+        match stored_region(path, member)? {
+            Some((offset, len)) => Self::open_at(path, offset, Some(len)),
+            None => Err(Error::CompressedMember(member.to_string())),
+        }
+    }
+}
+
+/// Reads a safetensors file of `len` bytes from a stream that cannot seek,
+/// decoding tensors in offset order and skipping any gaps between them.
+fn read_sequential(mut reader: impl Read, len: u64) -> Result<Reader> {
+    let (n, header) = Header::read(&mut reader)?;
+    header.validate(len.saturating_sub(8 + n))?;
+
+    let mut position = 0;
+    let mut tensors = HashMap::new();
+    for name in header.names_by_offset() {
+        let info = &header.tensors[name];
+        let (start, end) = info.data_offsets();
+        if start < position {
+            return Err(Error::InvalidHeader(format!(
+                "tensor `{name}` overlaps the tensor before it"
+            )));
+        }
+        io::copy(&mut (&mut reader).take(start - position), &mut io::sink())?;
+        let tensor = read_data(&mut reader, info.dtype(), info.shape().to_vec())?;
+        tensors.insert(name.to_string(), tensor);
+        position = end;
+    }
+
+    Ok(Reader {
+        metadata: header.metadata,
+        tensors,
+        aliases: HashMap::new(),
+        raw_header: Some(header.raw),
+        placeholder: false,
+    })
+}
@@ -0,0 +1,94 @@
+//! Mutable access to tensor data and in-place elementwise arithmetic.
+
+use crate::convert::PAR_MIN_LEN;
+use crate::{Element, Error, Result, Tensor};
+use half::{bf16, f16};
+use rayon::prelude::*;
+
+macro_rules! typed_mut_accessors {
+    ($($name:ident => $ty:ty),* $(,)?) => {
+        $(
+            #[doc = concat!("Borrows the data mutably as `", stringify!($ty), "`.")]
+            pub fn $name(&mut self) -> Result<&mut [$ty]> {
+                self.as_mut_slice()
+            }
+        )*
+    };
+}
+
+impl Tensor {
+    /// Borrows the data mutably as `T`, failing if the tensor's dtype is not
+    /// `T::DTYPE`.
+    pub fn as_mut_slice<T: Element>(&mut self) -> Result<&mut [T]> {
+        let actual = self.dtype();
+        T::slice_mut(self).ok_or(Error::DtypeMismatch {
+            expected: T::DTYPE,
+            actual,
+        })
+    }
+
+    typed_mut_accessors!(
+        as_mut_bool => bool,
+        as_mut_u8 => u8,
+        as_mut_i8 => i8,
+        as_mut_u16 => u16,
+        as_mut_i16 => i16,
+        as_mut_f16 => f16,
+        as_mut_bf16 => bf16,
+        as_mut_u32 => u32,
+        as_mut_i32 => i32,
+        as_mut_f32 => f32,
+        as_mut_u64 => u64,
+        as_mut_i64 => i64,
+        as_mut_f64 => f64,
+    );
+
+    /// Multiplies every element by `factor`.
+    ///
+    /// Each product is computed in f64 and rounded once to the storage type,
+    /// so half-precision tensors do not accumulate intermediate rounding.
+    /// Integer and bool tensors round and saturate as described by
+    /// [`Element::from_f64`].
+    pub fn scale(&mut self, factor: f32) {
+        let factor = factor as f64;
+        with_data!(self, _shape, data => {
+            data.par_iter_mut()
+                .with_min_len(PAR_MIN_LEN)
+                .for_each(|x| *x = Element::from_f64(x.to_f64() * factor));
+        })
+    }
+
+    /// Adds `alpha * other` elementwise, rounding each result once to the
+    /// storage type as in [`Tensor::scale`]. `other` must have the same dtype
+    /// and shape.
+    pub fn add_scaled(&mut self, other: &Tensor, alpha: f32) -> Result<()> {
+        if self.dtype() != other.dtype() {
+            return Err(Error::DtypeMismatch {
+                expected: self.dtype(),
+                actual: other.dtype(),
+            });
+        }
+        if self.shape() != other.shape() {
+            return Err(Error::ShapeMismatch {
+                left: self.shape().to_vec(),
+                right: other.shape().to_vec(),
+            });
+        }
+
+        with_data!(self, _shape, data => add_scaled(data, other, alpha as f64));
+        Ok(())
+    }
+
+    /// Sets every element to `value`, converted as by [`Element::from_f64`].
+    pub fn fill(&mut self, value: f64) {
+        with_data!(self, _shape, data => data.fill(Element::from_f64(value)))
+    }
+}
+
+fn add_scaled<T: Element>(data: &mut [T], other: &Tensor, alpha: f64) {
+    let other = T::slice(other).expect("dtypes were checked");
+    data.par_iter_mut()
+        .zip(other.par_iter())
+        .with_min_len(PAR_MIN_LEN)
+        .for_each(|(x, &y)| *x = T::from_f64(x.to_f64() + alpha * y.to_f64()));
+}
@@ -0,0 +1,107 @@
+use crate::source::Source;
+use safetensors_reader::{Dtype, Result, Selector, StreamWriter, TensorSource};
+use serde_json::json;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Convert floating-point tensors to another dtype, one tensor at a time.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Target dtype for floating-point tensors: f16, bf16, f32 or f64.
+    /// Integer and bool tensors are copied unchanged.
+    #[arg(long, value_parser = parse_float_dtype)]
+    to: Dtype,
+    /// Write floating-point tensors matching this glob as F32 instead.
+    /// May be repeated.
+    #[arg(long = "keep-f32", value_name = "PATTERN")]
+    keep_f32: Vec<String>,
+    /// Print the size change without writing anything.
+    #[arg(long)]
+    dry_run: bool,
+    /// A `.safetensors` file or a `.safetensors.index.json` index.
+    input: PathBuf,
+    #[arg(required_unless_present = "dry_run")]
+    output: Option<PathBuf>,
+}
+
+pub fn run(out: &mut impl Write, args: &Args) -> Result<()> {
+    let source = Source::open(&args.input)?;
+    let keep: Vec<Selector> = args.keep_f32.iter().map(Selector::pattern).collect();
+    let plan: Vec<(&str, Dtype)> = source
+        .names()
+        .into_iter()
+        .map(|name| {
+            let dtype = source.info(name).expect("listed names exist").dtype();
+            let target = match dtype.is_float() {
+                false => dtype,
+                true if keep.iter().any(|selector| selector.matches(name)) => Dtype::F32,
+                true => args.to,
+            };
+            (name, target)
+        })
+        .collect();
+
+    let elements = |name: &str| -> u64 {
+        let info = source.info(name).expect("listed names exist");
+        info.shape().iter().product::<usize>() as u64
+    };
+    let before: u64 = plan
+        .iter()
+        .map(|&(name, _)| source.info(name).unwrap().byte_len())
+        .sum();
+    let after: u64 = plan
+        .iter()
+        .map(|&(name, dtype)| elements(name) * dtype.size() as u64)
+        .sum();
+
+    if args.dry_run {
+        for &(name, target) in &plan {
+            let info = source.info(name).unwrap();
+            writeln!(
+                out,
+                "{name}: {:?} -> {target:?}, {} -> {} bytes",
+                info.dtype(),
+                info.byte_len(),
+                elements(name) * target.size() as u64
+            )?;
+        }
+    } else {
+        let output = args.output.as_ref().expect("required without --dry-run");
+        let mut writer = StreamWriter::create(output)?;
+        if let Some(metadata) = source.metadata().as_object() {
+            for (key, value) in metadata {
+                if let Some(value) = value.as_str() {
+                    writer.insert_metadata(key.as_str(), value);
+                }
+            }
+        }
+        let record = json!({ "to": args.to, "keep_f32": args.keep_f32 });
+        writer.insert_metadata("conversion", record.to_string());
+
+        for &(name, target) in &plan {
+            let tensor = source.load(name)?.to_dtype(target);
+            writer.add_tensor(name, &tensor)?;
+            for (key, value) in source.info(name).unwrap().extra() {
+                writer.insert_tensor_extra(name, key.as_str(), value.clone())?;
+            }
+        }
+        writer.finish()?;
+    }
+
+    let change = after as f64 / before.max(1) as f64 * 100.0 - 100.0;
+    writeln!(
+        out,
+        "{} tensors: {before} -> {after} bytes ({change:+.1}%)",
+        plan.len()
+    )?;
+    Ok(())
+}
+
+fn parse_float_dtype(text: &str) -> std::result::Result<Dtype, String> {
+    let dtype: Dtype = serde_json::from_value(json!(text.to_uppercase()))
+        .map_err(|_| format!("unknown dtype `{text}`"))?;
+    if !dtype.is_float() {
+        return Err(format!("`{text}` is not a floating-point dtype"));
+    }
+    Ok(dtype)
+}
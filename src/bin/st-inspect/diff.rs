@@ -0,0 +1,134 @@
+use crate::source::Source;
+use safetensors_reader::{diff, diff_headers, Diff, HeaderMismatch, Result};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Compare two checkpoints tensor by tensor. Exits with status 1 if they
+/// differ. Tensors whose dtype changed are compared by value and reported,
+/// but only fail with `--strict-dtype`.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Relative tolerance for values.
+    #[arg(long, default_value_t = 1e-5)]
+    rtol: f64,
+    /// Absolute tolerance for values.
+    #[arg(long, default_value_t = 1e-8)]
+    atol: f64,
+    /// Compare names, dtypes and shapes only, without reading tensor data.
+    #[arg(long)]
+    names_only: bool,
+    /// Also fail when a shared tensor's dtype differs.
+    #[arg(long)]
+    strict_dtype: bool,
+    /// Print machine-readable JSON instead of a report.
+    #[arg(long)]
+    json: bool,
+    /// A `.safetensors` file or a `.safetensors.index.json` index.
+    a: PathBuf,
+    /// A `.safetensors` file or a `.safetensors.index.json` index.
+    b: PathBuf,
+}
+
+/// Returns whether the checkpoints match.
+pub fn run(out: &mut impl Write, args: &Args) -> Result<bool> {
+    let (a, b) = (Source::open(&args.a)?, Source::open(&args.b)?);
+    let diff = match args.names_only {
+        true => diff_headers(&a, &b),
+        false => diff(&a, &b, args.rtol, args.atol)?,
+    };
+
+    let passed = match args.strict_dtype {
+        true => diff.passed_strict(),
+        false => diff.passed(),
+    };
+    if args.json {
+        let report = to_json(&diff, passed);
+        writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+    } else {
+        print_report(out, &diff, passed)?;
+    }
+    Ok(passed)
+}
+
+fn to_json(diff: &Diff, passed: bool) -> Value {
+    let mismatches = |mismatches: &[HeaderMismatch]| -> Vec<Value> {
+        mismatches
+            .iter()
+            .map(|mismatch| {
+                json!({
+                    "name": mismatch.name,
+                    "a": {"dtype": mismatch.a.0, "shape": mismatch.a.1},
+                    "b": {"dtype": mismatch.b.0, "shape": mismatch.b.1},
+                })
+            })
+            .collect()
+    };
+    let values: Vec<Value> = diff
+        .values
+        .iter()
+        .map(|value| {
+            json!({
+                "name": value.name,
+                "max_abs_diff": value.max_abs_diff,
+                "passed": value.close.passed,
+                "violations": value.close.violations,
+                "max_violation_index": value.close.max_violation_index,
+            })
+        })
+        .collect();
+    json!({
+        "passed": passed,
+        "only_in_a": diff.only_in_a,
+        "only_in_b": diff.only_in_b,
+        "header_mismatches": mismatches(&diff.header_mismatches),
+        "dtype_changes": mismatches(&diff.dtype_changes),
+        "values": values,
+    })
+}
+
+fn print_report(out: &mut impl Write, diff: &Diff, passed: bool) -> Result<()> {
+    for name in &diff.only_in_a {
+        writeln!(out, "only in a: {name}")?;
+    }
+    for name in &diff.only_in_b {
+        writeln!(out, "only in b: {name}")?;
+    }
+    for mismatch in &diff.header_mismatches {
+        writeln!(
+            out,
+            "mismatch:  {}: {:?} {:?} vs {:?} {:?}",
+            mismatch.name, mismatch.a.0, mismatch.a.1, mismatch.b.0, mismatch.b.1
+        )?;
+    }
+    for change in &diff.dtype_changes {
+        writeln!(
+            out,
+            "dtype:     {}: {:?} vs {:?}",
+            change.name, change.a.0, change.b.0
+        )?;
+    }
+    for value in diff.values.iter().filter(|value| !value.close.passed) {
+        writeln!(
+            out,
+            "differs:   {}: max abs diff {}, {} values outside tolerance",
+            value.name, value.max_abs_diff, value.close.violations
+        )?;
+    }
+
+    let worst = diff
+        .values
+        .iter()
+        .max_by(|a, b| a.max_abs_diff.total_cmp(&b.max_abs_diff));
+    if let Some(worst) = worst {
+        writeln!(
+            out,
+            "{} tensors compared, largest difference {} in {}",
+            diff.values.len(),
+            worst.max_abs_diff,
+            worst.name
+        )?;
+    }
+    writeln!(out, "{}", if passed { "match" } else { "differ" })?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::source::Source;
+use safetensors_reader::{Error, Result, Selector, StreamWriter, TensorSource};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Copy a subset of tensors into a new file without decoding them.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Output `.safetensors` file.
+    #[arg(long)]
+    out: PathBuf,
+    /// Copy tensors matching this glob. May be repeated. Without `--include`
+    /// or `--name`, every tensor is selected.
+    #[arg(long, value_name = "PATTERN")]
+    include: Vec<String>,
+    /// Copy this tensor, which must exist. May be repeated.
+    #[arg(long, value_name = "NAME")]
+    name: Vec<String>,
+    /// Skip tensors matching this glob, even if selected otherwise. May be
+    /// repeated.
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+    /// Remove this prefix from the names of copied tensors that have it,
+    /// unless nothing would remain.
+    #[arg(long, value_name = "PREFIX")]
+    strip_prefix: Option<String>,
+    /// Write an empty file instead of failing when nothing is selected.
+    #[arg(long)]
+    allow_empty: bool,
+    /// A `.safetensors` file or a `.safetensors.index.json` index.
+    input: PathBuf,
+}
+
+pub fn run(out: &mut impl Write, args: &Args) -> Result<()> {
+    let source = Source::open(&args.input)?;
+    let available = source.names();
+
+    let missing: Vec<String> = args
+        .name
+        .iter()
+        .filter(|name| !available.contains(&name.as_str()))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(Error::MissingTensors(missing));
+    }
+
+    let names = Selector::names(&args.name);
+    let include: Vec<Selector> = args.include.iter().map(Selector::pattern).collect();
+    let exclude: Vec<Selector> = args.exclude.iter().map(Selector::pattern).collect();
+    let select_all = args.include.is_empty() && args.name.is_empty();
+    let selected: Vec<&str> = available
+        .into_iter()
+        .filter(|&name| {
+            select_all || names.matches(name) || include.iter().any(|s| s.matches(name))
+        })
+        .filter(|&name| !exclude.iter().any(|s| s.matches(name)))
+        .collect();
+    if selected.is_empty() && !args.allow_empty {
+        let patterns = match select_all {
+            true => "*".to_string(),
+            false => args
+                .include
+                .iter()
+                .chain(&args.name)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        return Err(Error::NoMatch(patterns));
+    }
+
+    let rename = |name: &str| -> String {
+        let prefix = args.strip_prefix.as_deref().unwrap_or_default();
+        let stripped = name.strip_prefix(prefix).filter(|rest| !rest.is_empty());
+        stripped.unwrap_or(name).to_string()
+    };
+
+    let mut writer = StreamWriter::create(&args.out)?;
+    if let Some(metadata) = source.metadata().as_object() {
+        for (key, value) in metadata {
+            let dropped = source.info(key).is_some() && !selected.contains(&key.as_str());
+            if let (Some(value), false) = (value.as_str(), dropped) {
+                writer.insert_metadata(key.as_str(), value);
+            }
+        }
+    }
+
+    let mut bytes = 0;
+    for &name in &selected {
+        let info = source.info(name).expect("listed names exist");
+        let out_name = rename(name);
+        writer.add_from_reader(
+            &out_name,
+            info.dtype(),
+            info.shape(),
+            source.raw_reader(name)?,
+        )?;
+        for (key, value) in info.extra() {
+            writer.insert_tensor_extra(&out_name, key.as_str(), value.clone())?;
+        }
+        bytes += info.byte_len();
+    }
+    writer.finish()?;
+
+    writeln!(out, "{} tensors, {bytes} bytes", selected.len())?;
+    Ok(())
+}
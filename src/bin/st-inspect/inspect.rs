@@ -0,0 +1,162 @@
+use crate::source::Source;
+use safetensors_reader::{Result, TensorSource};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Write;
+
+pub fn run(out: &mut impl Write, source: &Source, as_json: bool) -> Result<()> {
+    let names = source.names();
+    let infos: Vec<_> = names
+        .iter()
+        .map(|&name| source.info(name).expect("listed names exist"))
+        .collect();
+    let params: usize = infos
+        .iter()
+        .map(|info| info.shape().iter().product::<usize>())
+        .sum();
+    let bytes: u64 = infos.iter().map(|info| info.byte_len()).sum();
+    let aliases = source.aliases()?;
+    let alias_of: HashMap<&str, &str> = aliases.iter().copied().collect();
+
+    if as_json {
+        let tensors: Vec<Value> = names
+            .iter()
+            .zip(&infos)
+            .map(|(&name, info)| {
+                let mut entry = json!({
+                    "name": name,
+                    "dtype": info.dtype(),
+                    "shape": info.shape(),
+                    "params": info.shape().iter().product::<usize>(),
+                    "bytes": info.byte_len(),
+                });
+                if let Some(file) = source.shard_file(name) {
+                    entry["file"] = json!(file);
+                }
+                if let Some(target) = alias_of.get(name) {
+                    entry["alias_of"] = json!(target);
+                }
+                entry
+            })
+            .collect();
+        let report = json!({
+            "tensors": tensors,
+            "total_tensors": names.len(),
+            "total_params": params,
+            "total_bytes": bytes,
+            "metadata": source.metadata(),
+        });
+        writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+        return Ok(());
+    }
+
+    let sharded = matches!(source, Source::Sharded(_));
+    let mut table = vec![["name", "dtype", "shape", "params", "bytes", "file"]
+        .map(String::from)
+        .to_vec()];
+    for (&name, info) in names.iter().zip(&infos) {
+        table.push(vec![
+            name.to_string(),
+            format!("{:?}", info.dtype()),
+            format!("{:?}", info.shape()),
+            info.shape().iter().product::<usize>().to_string(),
+            info.byte_len().to_string(),
+            source.shard_file(name).unwrap_or_default(),
+        ]);
+    }
+    if !sharded {
+        table.iter_mut().for_each(|row| row.truncate(5));
+    }
+    print_table(out, &table)?;
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{} tensors, {params} parameters, {bytes} bytes",
+        names.len()
+    )?;
+    if !aliases.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "aliases:")?;
+        for (alias, target) in &aliases {
+            writeln!(out, "  {alias} -> {target}")?;
+        }
+    }
+    if let Some(metadata) = source.metadata().as_object().filter(|map| !map.is_empty()) {
+        writeln!(out)?;
+        writeln!(out, "metadata:")?;
+        for (key, value) in metadata {
+            match value.as_str() {
+                Some(text) => writeln!(out, "  {key}: {text}")?,
+                None => writeln!(out, "  {key}: {value}")?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Loads tensor `name` alone and prints its first `head` values and summary
+/// statistics. The statistics cover finite values, with NaN and infinities
+/// counted separately.
+pub fn run_tensor(
+    out: &mut impl Write,
+    source: &Source,
+    name: &str,
+    head: usize,
+    as_json: bool,
+) -> Result<()> {
+    let tensor = source.load(name)?;
+    let values: Vec<f64> = tensor.iter_f64().take(head).collect();
+    let finite: Vec<f64> = tensor.iter_f64().filter(|x| x.is_finite()).collect();
+    let stats = json!({
+        "min": finite.iter().copied().reduce(f64::min),
+        "max": finite.iter().copied().reduce(f64::max),
+        "mean": (!finite.is_empty()).then(|| finite.iter().sum::<f64>() / finite.len() as f64),
+        "l2_norm": finite.iter().map(|x| x * x).sum::<f64>().sqrt(),
+        "nan": tensor.iter_f64().filter(|x| x.is_nan()).count(),
+        "inf": tensor.iter_f64().filter(|x| x.is_infinite()).count(),
+    });
+
+    if as_json {
+        let report = json!({
+            "name": name,
+            "dtype": tensor.dtype(),
+            "shape": tensor.shape(),
+            "head": values,
+            "stats": stats,
+        });
+        writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+        return Ok(());
+    }
+
+    writeln!(out, "name:  {name}")?;
+    writeln!(out, "dtype: {:?}", tensor.dtype())?;
+    writeln!(out, "shape: {:?}", tensor.shape())?;
+    writeln!(out, "first {} values: {values:?}", values.len())?;
+    for key in ["min", "max", "mean", "l2_norm", "nan", "inf"] {
+        writeln!(out, "{key}: {}", stats[key])?;
+    }
+    Ok(())
+}
+
+/// Prints rows with each column padded to its widest cell. Numeric columns
+/// are right-aligned.
+fn print_table(out: &mut impl Write, table: &[Vec<String>]) -> Result<()> {
+    let columns = table[0].len();
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| table.iter().map(|row| row[c].len()).max().unwrap_or(0))
+        .collect();
+    for row in table {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(c, (cell, &width))| match c {
+                3 | 4 => format!("{cell:>width$}"),
+                _ => format!("{cell:<width$}"),
+            })
+            .collect();
+        writeln!(out, "{}", line.join("  ").trim_end())?;
+    }
+    Ok(())
+}
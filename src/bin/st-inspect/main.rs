@@ -0,0 +1,89 @@
+//! Prints the contents of safetensors files, compares them, extracts subsets
+//! and converts between dtypes.
+
+mod convert;
+mod diff;
+mod extract;
+mod inspect;
+mod source;
+
+use clap::{Parser, Subcommand};
+use safetensors_reader::Error;
+use source::Source;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+/// Exit code for `diff` when the checkpoints differ.
+const EXIT_DIFFERENT: u8 = 1;
+/// Exit code for files that cannot be read or written.
+const EXIT_IO: u8 = 3;
+/// Exit code for files that are read but invalid, and for bad arguments.
+const EXIT_INVALID: u8 = 2;
+
+/// Print the tensors and metadata of a safetensors file or sharded index.
+#[derive(Parser)]
+#[command(
+    name = "st-inspect",
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// A `.safetensors` file or a `.safetensors.index.json` index.
+    #[arg(required = true)]
+    path: Option<PathBuf>,
+    /// Print machine-readable JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+    /// Load only this tensor and print its first values and statistics.
+    #[arg(long, value_name = "NAME")]
+    tensor: Option<String>,
+    /// Number of values to print with `--tensor`.
+    #[arg(long, default_value_t = 10, requires = "tensor")]
+    head: usize,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Convert(convert::Args),
+    Diff(diff::Args),
+    Extract(extract::Args),
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let mut out = BufWriter::new(io::stdout().lock());
+    let result = match &cli.command {
+        Some(Command::Convert(args)) => convert::run(&mut out, args).map(|()| true),
+        Some(Command::Diff(args)) => diff::run(&mut out, args),
+        Some(Command::Extract(args)) => extract::run(&mut out, args).map(|()| true),
+        None => {
+            let path = cli.path.as_ref().expect("required without a subcommand");
+            Source::open(path)
+                .and_then(|source| match &cli.tensor {
+                    Some(name) => inspect::run_tensor(&mut out, &source, name, cli.head, cli.json),
+                    None => inspect::run(&mut out, &source, cli.json),
+                })
+                .map(|()| true)
+        }
+    };
+    let result = result.and_then(|matched| Ok(out.flush().map(|()| matched)?));
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(EXIT_DIFFERENT),
+        // The reader went away, as with `st-inspect ... | head`; nothing is
+        // wrong with the files.
+        Err(Error::Io(err)) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("st-inspect: {err}");
+            ExitCode::from(match err {
+                Error::Io(_) => EXIT_IO,
+                _ => EXIT_INVALID,
+            })
+        }
+    }
+}
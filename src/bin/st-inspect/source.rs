@@ -0,0 +1,95 @@
+use safetensors_reader::{LazyReader, Result, ShardedReader, Tensor, TensorInfo, TensorSource};
+use std::io::Read;
+use std::path::Path;
+
+/// A single safetensors file or a sharded checkpoint given by its index.
+pub enum Source {
+    File(LazyReader),
+    Sharded(ShardedReader),
+}
+
+impl Source {
+    /// Opens `path` as a sharded checkpoint if it is a `.json` index, and as
+    /// a single file otherwise.
+    pub fn open(path: &Path) -> Result<Self> {
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Ok(Self::Sharded(ShardedReader::open(path)?))
+        } else {
+            Ok(Self::File(LazyReader::open(path)?))
+        }
+    }
+
+    /// The `__metadata__` of a single file, or the `metadata` of an index.
+    pub fn metadata(&self) -> &serde_json::Value {
+        match self {
+            Self::File(reader) => reader.metadata(),
+            Self::Sharded(reader) => reader.metadata(),
+        }
+    }
+
+    /// A reader over the raw bytes of tensor `name`, from whichever shard
+    /// holds it.
+    pub fn raw_reader(&self, name: &str) -> Result<Box<dyn Read>> {
+        match self {
+            Self::File(reader) => Ok(Box::new(reader.raw_reader(name)?)),
+            Self::Sharded(reader) => Ok(Box::new(reader.raw_reader(name)?)),
+        }
+    }
+
+    /// Entries that alias the data of another in the same file, as
+    /// `(alias, target)` pairs sorted by alias.
+    pub fn aliases(&self) -> Result<Vec<(&str, &str)>> {
+        match self {
+            Self::File(reader) => reader.aliases(),
+            Self::Sharded(reader) => {
+                // Names come grouped by shard, so each shard appears once.
+                let mut shards: Vec<&LazyReader> = reader
+                    .names()
+                    .into_iter()
+                    .filter_map(|name| reader.shard(name))
+                    .collect();
+                shards.dedup_by_key(|shard| shard.path());
+                let mut aliases = Vec::new();
+                for shard in shards {
+                    aliases.extend(shard.aliases()?);
+                }
+                aliases.sort_unstable();
+                Ok(aliases)
+            }
+        }
+    }
+
+    /// The shard file holding `name`, for sharded checkpoints.
+    pub fn shard_file(&self, name: &str) -> Option<String> {
+        match self {
+            Self::File(_) => None,
+            Self::Sharded(reader) => {
+                let path = reader.shard(name)?.path();
+                Some(path.file_name()?.to_string_lossy().into_owned())
+            }
+        }
+    }
+}
+
+impl TensorSource for Source {
+    fn names(&self) -> Vec<&str> {
+        match self {
+            Self::File(reader) => reader.names(),
+            Self::Sharded(reader) => reader.names(),
+        }
+    }
+
+    fn info(&self, name: &str) -> Option<&TensorInfo> {
+        match self {
+            Self::File(reader) => reader.info(name),
+            Self::Sharded(reader) => reader.info(name),
+        }
+    }
+
+    fn load(&self, name: &str) -> Result<Tensor> {
+        match self {
+            Self::File(reader) => reader.load(name),
+            Self::Sharded(reader) => reader.load(name),
+        }
+    }
+}
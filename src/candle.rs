@@ -0,0 +1,55 @@
+//! Conversion into candle tensors.
+
+use crate::{Error, Reader, Result, Tensor};
+use candle_core::Device;
+use std::collections::HashMap;
+
+impl Tensor {
+    /// Copies the tensor onto `device` with the equivalent candle dtype.
+    ///
+    /// The data is copied straight from this tensor's buffer into candle's
+    /// storage, with no intermediate allocation. BOOL, I8, U16 and U64 have
+    /// no candle dtype and yield [`Error::UnsupportedDtype`].
+    pub fn to_candle(&self, device: &Device) -> Result<candle_core::Tensor> {
+        let shape = self.shape();
+        let tensor = match self {
+            Tensor::U8 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::I16 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::F16 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::Bf16 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::U32 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::I32 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::F32 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::I64 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::F64 { data, .. } => candle_core::Tensor::from_slice(data, shape, device),
+            Tensor::Bool { .. } | Tensor::I8 { .. } | Tensor::U16 { .. } | Tensor::U64 { .. } => {
+                return Err(Error::UnsupportedDtype(format!(
+                    "{:?} (no candle equivalent)",
+                    self.dtype()
+                )))
+            }
+        };
+        Ok(tensor?)
+    }
+}
+
+impl Reader {
+    /// Converts every tensor onto `device`, in the form accepted by
+    /// `candle_nn::VarBuilder::from_tensors`. Aliases share the storage of
+    /// the tensor they alias.
+    pub fn to_candle_varmap(
+        &self,
+        device: &Device,
+    ) -> Result<HashMap<String, candle_core::Tensor>> {
+        let mut varmap = self
+            .tensors
+            .iter()
+            .map(|(name, tensor)| Ok((name.clone(), tensor.to_candle(device)?)))
+            .collect::<Result<HashMap<_, _>>>()?;
+        for (alias, target) in &self.aliases {
+            let tensor = varmap[target].clone();
+            varmap.insert(alias.clone(), tensor);
+        }
+        Ok(varmap)
+    }
+}
@@ -0,0 +1,305 @@
+//! Dtype conversion and the precision it loses.
+
+use crate::convert::PAR_MIN_LEN;
+use crate::{Dtype, Element, Error, Reader, Result, Tensor};
+use half::{bf16, f16};
+use rayon::prelude::*;
+use std::mem;
+use std::num::FpCategory;
+
+/// What converting a tensor to another dtype would do to its values.
+///
+/// Float targets round to nearest. Integer and bool targets round to the
+/// nearest integer and saturate at the bounds of the type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CastReport {
+    pub target: Dtype,
+    pub elements: u64,
+    /// Finite values that become infinite, or that exceed an integer range.
+    pub overflow: u64,
+    /// Non-zero values that become zero.
+    pub underflow: u64,
+    /// Values that become subnormal in a float target.
+    pub subnormal: u64,
+    /// NaN inputs, which integer targets cannot represent.
+    pub nan: u64,
+    /// Number of finite, non-zero values that did not overflow, over which
+    /// the relative error statistics are taken.
+    pub measured: u64,
+    pub max_rel_error: f64,
+    /// Flat index of the value with the largest relative error.
+    pub max_rel_error_index: Option<usize>,
+    sum_rel_error: f64,
+}
+
+impl CastReport {
+    fn new(target: Dtype) -> Self {
+        Self {
+            target,
+            elements: 0,
+            overflow: 0,
+            underflow: 0,
+            subnormal: 0,
+            nan: 0,
+            measured: 0,
+            max_rel_error: 0.0,
+            max_rel_error_index: None,
+            sum_rel_error: 0.0,
+        }
+    }
+
+    /// Mean relative error over the measured values, or 0 if there are none.
+    pub fn mean_rel_error(&self) -> f64 {
+        if self.measured == 0 {
+            0.0
+        } else {
+            self.sum_rel_error / self.measured as f64
+        }
+    }
+
+    /// Whether the conversion is exact for every value.
+    pub fn is_lossless(&self) -> bool {
+        self.overflow == 0
+            && self.underflow == 0
+            && self.max_rel_error == 0.0
+            && (self.nan == 0 || self.target.is_float())
+    }
+
+    fn add(&mut self, index: usize, value: f64) {
+        self.elements += 1;
+        if value.is_nan() {
+            self.nan += 1;
+            return;
+        }
+
+        let (cast, subnormal) = round_trip(value, self.target);
+        self.subnormal += subnormal as u64;
+        if value.is_infinite() || value == 0.0 {
+            return;
+        }
+        if cast.is_infinite() || !self.target.is_float() && cast != value.round() {
+            self.overflow += 1;
+            return;
+        }
+        if cast == 0.0 {
+            self.underflow += 1;
+        }
+
+        let error = ((cast - value) / value).abs();
+        self.measured += 1;
+        self.sum_rel_error += error;
+        if error > self.max_rel_error {
+            self.max_rel_error = error;
+            self.max_rel_error_index = Some(index);
+        }
+    }
+
+    /// Adds the counts of another report for the same target. When `other`
+    /// covers a different tensor its error index is kept only if it becomes
+    /// the maximum.
+    fn merge(&mut self, other: &CastReport) {
+        self.elements += other.elements;
+        self.overflow += other.overflow;
+        self.underflow += other.underflow;
+        self.subnormal += other.subnormal;
+        self.nan += other.nan;
+        self.measured += other.measured;
+        self.sum_rel_error += other.sum_rel_error;
+        let earlier = match (self.max_rel_error_index, other.max_rel_error_index) {
+            (Some(a), Some(b)) => b < a,
+            _ => false,
+        };
+        if other.max_rel_error > self.max_rel_error
+            || other.max_rel_error == self.max_rel_error && earlier
+        {
+            self.max_rel_error = other.max_rel_error;
+            self.max_rel_error_index = other.max_rel_error_index;
+        }
+    }
+}
+
+/// [`CastReport`]s for every tensor of a [`Reader`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CastSummary {
+    /// Totals over all tensors. The error index is not meaningful here.
+    pub total: CastReport,
+    /// Per-tensor reports, worst first: by overflow count, then underflow
+    /// count, then maximum relative error.
+    pub tensors: Vec<(String, CastReport)>,
+}
+
+/// What [`Reader::convert_all`] does with integer and bool tensors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConvertPolicy {
+    /// Leave them in their own dtype.
+    Skip,
+    /// Fail with [`Error::NonFloatTensor`] before converting anything.
+    Error,
+    /// Convert them like float tensors.
+    Convert,
+}
+
+/// The outcome of [`Reader::convert_all`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConvertSummary {
+    pub converted: usize,
+    /// Tensors left as they were, either by policy or because they already
+    /// had the target dtype.
+    pub skipped: usize,
+    /// Total size of all tensors afterwards.
+    pub bytes: u64,
+}
+
+impl Tensor {
+    /// Converts every element to `target` through f64, rounding as described
+    /// by [`Element::from_f64`]. Converting to the tensor's own dtype copies
+    /// it unchanged.
+    pub fn to_dtype(&self, target: Dtype) -> Tensor {
+        if target == self.dtype() {
+            return self.clone();
+        }
+        with_data!(self, shape, data => with_dtype!(target, T => {
+            let converted: Vec<T> = data.iter().map(|&x| T::from_f64(x.to_f64())).collect();
+            Element::into_tensor(converted, shape.clone())
+        }))
+    }
+
+    /// Like [`Tensor::to_dtype`], but consumes the tensor, so that its data
+    /// is freed as soon as the conversion finishes and is returned as it is
+    /// if it already has dtype `target`.
+    pub fn into_dtype(self, target: Dtype) -> Tensor {
+        if target == self.dtype() {
+            return self;
+        }
+        self.to_dtype(target)
+    }
+
+    /// Simulates converting every element to `target` without keeping the
+    /// result.
+    pub fn cast_report(&self, target: Dtype) -> CastReport {
+        self.par_iter_f64()
+            .with_min_len(PAR_MIN_LEN)
+            .enumerate()
+            .fold(
+                || CastReport::new(target),
+                |mut report, (i, value)| {
+                    report.add(i, value);
+                    report
+                },
+            )
+            .reduce(
+                || CastReport::new(target),
+                |mut a, b| {
+                    a.merge(&b);
+                    a
+                },
+            )
+    }
+}
+
+impl Reader {
+    /// Converts every float tensor to `target`, and integer and bool tensors
+    /// according to `policy`, rounding as in [`Tensor::to_dtype`].
+    ///
+    /// Aliases follow the tensor they alias and are not counted separately.
+    /// Tensors are converted in parallel, each taken out of the reader and
+    /// its original freed as soon as it is converted, so memory peaks at the
+    /// originals plus one converted tensor per worker thread.
+    pub fn convert_all(&mut self, target: Dtype, policy: ConvertPolicy) -> Result<ConvertSummary> {
+        if policy == ConvertPolicy::Error {
+            let mut names: Vec<_> = self.tensors.keys().collect();
+            names.sort();
+            if let Some(name) = names
+                .into_iter()
+                .find(|&name| !self.tensors[name].dtype().is_float())
+            {
+                return Err(Error::NonFloatTensor {
+                    name: name.clone(),
+                    dtype: self.tensors[name].dtype(),
+                });
+            }
+        }
+
+        let converted = self
+            .tensors
+            .par_iter_mut()
+            .map(|(_, tensor)| {
+                let dtype = tensor.dtype();
+                if dtype == target || !dtype.is_float() && policy != ConvertPolicy::Convert {
+                    return 0;
+                }
+                // Holds the slot, without allocating, while the original converts.
+                let empty = Tensor::U8 {
+                    data: Vec::new(),
+                    shape: Vec::new(),
+                };
+                let original = mem::replace(tensor, empty);
+                *tensor = original.into_dtype(target);
+                1
+            })
+            .sum();
+
+        Ok(ConvertSummary {
+            converted,
+            skipped: self.tensors.len() - converted,
+            bytes: self
+                .tensors
+                .values()
+                .map(|t| t.as_bytes().len() as u64)
+                .sum(),
+        })
+    }
+
+    /// Simulates converting every tensor to `target`, leaving out aliases,
+    /// whose values are those of the tensor they alias.
+    pub fn cast_report(&self, target: Dtype) -> CastSummary {
+        let mut tensors: Vec<(String, CastReport)> = self
+            .tensors
+            .par_iter()
+            .map(|(name, tensor)| (name.clone(), tensor.cast_report(target)))
+            .collect();
+        tensors.sort_by(|(a_name, a), (b_name, b)| {
+            (b.overflow, b.underflow)
+                .cmp(&(a.overflow, a.underflow))
+                .then(b.max_rel_error.total_cmp(&a.max_rel_error))
+                .then_with(|| a_name.cmp(b_name))
+        });
+
+        let mut total = CastReport::new(target);
+        for (_, report) in &tensors {
+            total.merge(report);
+        }
+        total.max_rel_error_index = None;
+        CastSummary { total, tensors }
+    }
+}
+
+/// `value` converted to `dtype` and back, and whether it is subnormal in
+/// `dtype`.
+pub(crate) fn round_trip(value: f64, dtype: Dtype) -> (f64, bool) {
+    let int = |min: f64, max: f64| (value.round().clamp(min, max), false);
+    match dtype {
+        Dtype::F16 => {
+            let x = f16::from_f64(value);
+            (x.to_f64(), x.classify() == FpCategory::Subnormal)
+        }
+        Dtype::Bf16 => {
+            let x = bf16::from_f64(value);
+            (x.to_f64(), x.classify() == FpCategory::Subnormal)
+        }
+        Dtype::F32 => {
+            let x = value as f32;
+            (x as f64, x.is_subnormal())
+        }
+        Dtype::F64 => (value, value.is_subnormal()),
+        Dtype::Bool => int(0.0, 1.0),
+        Dtype::U8 => int(0.0, u8::MAX as f64),
+        Dtype::I8 => int(i8::MIN as f64, i8::MAX as f64),
+        Dtype::U16 => int(0.0, u16::MAX as f64),
+        Dtype::I16 => int(i16::MIN as f64, i16::MAX as f64),
+        Dtype::U32 => int(0.0, u32::MAX as f64),
+        Dtype::I32 => int(i32::MIN as f64, i32::MAX as f64),
+        Dtype::U64 => int(0.0, u64::MAX as f64),
+        Dtype::I64 => int(i64::MIN as f64, i64::MAX as f64),
+    }
+}
@@ -0,0 +1,69 @@
+//! Approximate comparison of tensors.
+
+use crate::Tensor;
+
+/// Outcome of [`Tensor::allclose`], with enough detail to diagnose a failure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllcloseResult {
+    /// Whether the shapes match and every pair of elements is close.
+    pub passed: bool,
+    pub shape_matches: bool,
+    /// Number of element pairs outside the tolerance.
+    pub violations: usize,
+    /// Largest amount by which `|a - b|` exceeds `atol + rtol * |b|`, or
+    /// infinity for a NaN that is not matched. Zero when nothing violates.
+    pub max_violation: f64,
+    /// Flat row-major index of the largest violation.
+    pub max_violation_index: Option<usize>,
+}
+
+impl Tensor {
+    /// Checks that both tensors have the same shape and that every pair of
+    /// elements satisfies `|a - b| <= atol + rtol * |b|`, with `self` as `a`.
+    /// Elements of any dtypes are compared as f64. NaN is never close to
+    /// anything, including NaN.
+    pub fn allclose(&self, other: &Tensor, rtol: f64, atol: f64) -> AllcloseResult {
+        self.allclose_with(other, rtol, atol, false)
+    }
+
+    /// Like [`Tensor::allclose`], but with `equal_nan` two NaNs count as
+    /// close.
+    pub fn allclose_with(
+        &self,
+        other: &Tensor,
+        rtol: f64,
+        atol: f64,
+        equal_nan: bool,
+    ) -> AllcloseResult {
+        let mut result = AllcloseResult {
+            passed: false,
+            shape_matches: self.shape() == other.shape(),
+            violations: 0,
+            max_violation: 0.0,
+            max_violation_index: None,
+        };
+        if !result.shape_matches {
+            return result;
+        }
+
+        for (i, (a, b)) in self.iter_f64().zip(other.iter_f64()).enumerate() {
+            if a == b || (equal_nan && a.is_nan() && b.is_nan()) {
+                continue;
+            }
+            let violation = match (a - b).abs() - (atol + rtol * b.abs()) {
+                excess if excess <= 0.0 => continue,
+                excess if excess.is_nan() => f64::INFINITY,
+                excess => excess,
+            };
+
+            result.violations += 1;
+            if result.max_violation_index.is_none() || violation > result.max_violation {
+                result.max_violation = violation;
+                result.max_violation_index = Some(i);
+            }
+        }
+
+        result.passed = result.violations == 0;
+        result
+    }
+}
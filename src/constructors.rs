@@ -0,0 +1,69 @@
+//! Building tensors from data in memory.
+
+use crate::{Dtype, Element, Error, Result, Tensor};
+use half::{bf16, f16};
+
+macro_rules! typed_constructors {
+    ($($name:ident => $ty:ty),* $(,)?) => {
+        $(
+            #[doc = concat!("Builds a tensor from `", stringify!($ty), "` elements; see [`Tensor::from_vec`].")]
+            pub fn $name(data: Vec<$ty>, shape: Vec<usize>) -> Result<Tensor> {
+                Tensor::from_vec(data, shape)
+            }
+        )*
+    };
+}
+
+impl Tensor {
+    /// Builds a tensor that takes ownership of `data`, which must hold exactly
+    /// as many elements as `shape` describes.
+    pub fn from_vec<T: Element>(data: Vec<T>, shape: Vec<usize>) -> Result<Tensor> {
+        if element_count(T::DTYPE, &shape)? != data.len() {
+            return Err(Error::ElementCount {
+                shape,
+                len: data.len(),
+            });
+        }
+        Ok(T::into_tensor(data, shape))
+    }
+
+    typed_constructors!(
+        from_vec_bool => bool,
+        from_vec_u8 => u8,
+        from_vec_i8 => i8,
+        from_vec_u16 => u16,
+        from_vec_i16 => i16,
+        from_vec_f16 => f16,
+        from_vec_bf16 => bf16,
+        from_vec_u32 => u32,
+        from_vec_i32 => i32,
+        from_vec_f32 => f32,
+        from_vec_u64 => u64,
+        from_vec_i64 => i64,
+        from_vec_f64 => f64,
+    );
+
+    /// A tensor of zeros (or `false`).
+    pub fn zeros(dtype: Dtype, shape: Vec<usize>) -> Result<Tensor> {
+        Self::full(dtype, shape, 0.0)
+    }
+
+    /// A tensor with every element set to `value`, converted to `dtype` as
+    /// described by [`Element::from_f64`].
+    pub fn full(dtype: Dtype, shape: Vec<usize>, value: f64) -> Result<Tensor> {
+        let len = element_count(dtype, &shape)?;
+        Ok(with_dtype!(dtype, T => {
+            Element::into_tensor(vec![T::from_f64(value); len], shape)
+        }))
+    }
+}
+
+/// The number of elements of `shape`, checking that its byte size for
+/// `dtype` fits in a `u64`.
+fn element_count(dtype: Dtype, shape: &[usize]) -> Result<usize> {
+    dtype.byte_len(shape)?;
+    shape
+        .iter()
+        .try_fold(1usize, |count, &dim| count.checked_mul(dim))
+        .ok_or_else(|| Error::ShapeOverflow(shape.to_vec()))
+}
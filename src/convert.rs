@@ -0,0 +1,287 @@
+//! Reading and transforming elements of any dtype as floating point.
+
+use crate::{Dtype, Element, Error, Result, Tensor};
+use half::{bf16, f16};
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::prelude::*;
+use std::marker::PhantomData;
+use std::slice;
+
+/// Parallel elementwise passes hand each rayon task at least this many
+/// elements, so small tensors are processed on the calling thread.
+pub(crate) const PAR_MIN_LEN: usize = 1 << 14;
+
+impl Tensor {
+    /// Iterates over the elements in row-major order, converting each one to
+    /// f32 as it is yielded.
+    pub fn iter_f32(&self) -> impl ExactSizeIterator<Item = f32> + DoubleEndedIterator + '_ {
+        Values::<f32>::new(self)
+    }
+
+    /// Iterates over the elements in row-major order, converting each one to
+    /// f64 as it is yielded.
+    pub fn iter_f64(&self) -> impl ExactSizeIterator<Item = f64> + DoubleEndedIterator + '_ {
+        Values::<f64>::new(self)
+    }
+
+    /// Parallel version of [`Tensor::iter_f32`].
+    pub fn par_iter_f32(&self) -> impl IndexedParallelIterator<Item = f32> + '_ {
+        ParValues::<f32>::new(self)
+    }
+
+    /// Parallel version of [`Tensor::iter_f64`].
+    pub fn par_iter_f64(&self) -> impl IndexedParallelIterator<Item = f64> + '_ {
+        ParValues::<f64>::new(self)
+    }
+
+    /// Copies every element into a new `Vec<f32>`.
+    pub fn to_f32(&self) -> Vec<f32> {
+        with_data!(self, _shape, data => data.iter().map(|&x| x.to_f32()).collect())
+    }
+
+    /// Copies every element into a new `Vec<f64>`.
+    pub fn to_f64(&self) -> Vec<f64> {
+        with_data!(self, _shape, data => data.iter().map(|&x| x.to_f64()).collect())
+    }
+
+    /// Applies `f` to every element converted to f32 and returns the results
+    /// as an F32 tensor of the same shape. Integer and bool tensors go through
+    /// the same conversion, so values beyond 2^24 are rounded before `f` sees
+    /// them.
+    pub fn map_f32(&self, f: impl Fn(f32) -> f32 + Sync) -> Tensor {
+        with_data!(self, shape, data => {
+            let mapped = data
+                .par_iter()
+                .with_min_len(PAR_MIN_LEN)
+                .map(|&x| f(x.to_f32()))
+                .collect();
+            Tensor::F32 { data: mapped, shape: shape.clone() }
+        })
+    }
+
+    /// Applies `f` to every element of an F32 tensor without reallocating.
+    pub fn map_in_place(&mut self, f: impl Fn(f32) -> f32 + Sync) -> Result<()> {
+        let Tensor::F32 { data, .. } = self else {
+            return Err(Error::DtypeMismatch {
+                expected: Dtype::F32,
+                actual: self.dtype(),
+            });
+        };
+        data.par_iter_mut()
+            .with_min_len(PAR_MIN_LEN)
+            .for_each(|x| *x = f(*x));
+        Ok(())
+    }
+}
+
+/// Evaluates `$body` with `$inner` bound to the contents of `$value`, a
+/// [`Slice`] or [`SliceIter`] named by `$kind`. The body is instantiated once
+/// per element type.
+macro_rules! with_typed {
+    ($kind:ident, $value:expr, $inner:ident => $body:expr) => {
+        match $value {
+            $kind::Bool($inner) => $body,
+            $kind::U8($inner) => $body,
+            $kind::I8($inner) => $body,
+            $kind::U16($inner) => $body,
+            $kind::I16($inner) => $body,
+            $kind::F16($inner) => $body,
+            $kind::Bf16($inner) => $body,
+            $kind::U32($inner) => $body,
+            $kind::I32($inner) => $body,
+            $kind::F32($inner) => $body,
+            $kind::U64($inner) => $body,
+            $kind::I64($inner) => $body,
+            $kind::F64($inner) => $body,
+        }
+    };
+}
+
+/// A tensor's data as a slice of its element type, so the dtype is matched
+/// once rather than per element.
+#[derive(Clone, Copy)]
+enum Slice<'a> {
+    Bool(&'a [bool]),
+    U8(&'a [u8]),
+    I8(&'a [i8]),
+    U16(&'a [u16]),
+    I16(&'a [i16]),
+    F16(&'a [f16]),
+    Bf16(&'a [bf16]),
+    U32(&'a [u32]),
+    I32(&'a [i32]),
+    F32(&'a [f32]),
+    U64(&'a [u64]),
+    I64(&'a [i64]),
+    F64(&'a [f64]),
+}
+
+/// An iterator over a [`Slice`].
+enum SliceIter<'a> {
+    Bool(slice::Iter<'a, bool>),
+    U8(slice::Iter<'a, u8>),
+    I8(slice::Iter<'a, i8>),
+    U16(slice::Iter<'a, u16>),
+    I16(slice::Iter<'a, i16>),
+    F16(slice::Iter<'a, f16>),
+    Bf16(slice::Iter<'a, bf16>),
+    U32(slice::Iter<'a, u32>),
+    I32(slice::Iter<'a, i32>),
+    F32(slice::Iter<'a, f32>),
+    U64(slice::Iter<'a, u64>),
+    I64(slice::Iter<'a, i64>),
+    F64(slice::Iter<'a, f64>),
+}
+
+impl<'a> Slice<'a> {
+    fn of(tensor: &'a Tensor) -> Self {
+        match tensor {
+            Tensor::Bool { data, .. } => Slice::Bool(data),
+            Tensor::U8 { data, .. } => Slice::U8(data),
+            Tensor::I8 { data, .. } => Slice::I8(data),
+            Tensor::U16 { data, .. } => Slice::U16(data),
+            Tensor::I16 { data, .. } => Slice::I16(data),
+            Tensor::F16 { data, .. } => Slice::F16(data),
+            Tensor::Bf16 { data, .. } => Slice::Bf16(data),
+            Tensor::U32 { data, .. } => Slice::U32(data),
+            Tensor::I32 { data, .. } => Slice::I32(data),
+            Tensor::F32 { data, .. } => Slice::F32(data),
+            Tensor::U64 { data, .. } => Slice::U64(data),
+            Tensor::I64 { data, .. } => Slice::I64(data),
+            Tensor::F64 { data, .. } => Slice::F64(data),
+        }
+    }
+
+    fn iter(self) -> SliceIter<'a> {
+        match self {
+            Slice::Bool(data) => SliceIter::Bool(data.iter()),
+            Slice::U8(data) => SliceIter::U8(data.iter()),
+            Slice::I8(data) => SliceIter::I8(data.iter()),
+            Slice::U16(data) => SliceIter::U16(data.iter()),
+            Slice::I16(data) => SliceIter::I16(data.iter()),
+            Slice::F16(data) => SliceIter::F16(data.iter()),
+            Slice::Bf16(data) => SliceIter::Bf16(data.iter()),
+            Slice::U32(data) => SliceIter::U32(data.iter()),
+            Slice::I32(data) => SliceIter::I32(data.iter()),
+            Slice::F32(data) => SliceIter::F32(data.iter()),
+            Slice::U64(data) => SliceIter::U64(data.iter()),
+            Slice::I64(data) => SliceIter::I64(data.iter()),
+            Slice::F64(data) => SliceIter::F64(data.iter()),
+        }
+    }
+}
+
+/// The float types elements can be yielded as.
+trait Float: Copy + Send + Sync + 'static {
+    fn from_element<T: Element>(x: T) -> Self;
+}
+
+impl Float for f32 {
+    fn from_element<T: Element>(x: T) -> Self {
+        x.to_f32()
+    }
+}
+
+impl Float for f64 {
+    fn from_element<T: Element>(x: T) -> Self {
+        x.to_f64()
+    }
+}
+
+/// The iterator behind [`Tensor::iter_f32`] and [`Tensor::iter_f64`].
+///
+/// `next` has to check the element type on every call, but `fold`, and
+/// everything built on it such as `sum`, `for_each` and `collect`, checks it
+/// once and then runs a loop over the typed slice.
+struct Values<'a, U> {
+    iter: SliceIter<'a>,
+    out: PhantomData<fn() -> U>,
+}
+
+impl<'a, U: Float> Values<'a, U> {
+    fn new(tensor: &'a Tensor) -> Self {
+        Self {
+            iter: Slice::of(tensor).iter(),
+            out: PhantomData,
+        }
+    }
+}
+
+impl<U: Float> Iterator for Values<'_, U> {
+    type Item = U;
+
+    fn next(&mut self) -> Option<U> {
+        with_typed!(SliceIter, &mut self.iter, iter => iter.next().map(|&x| U::from_element(x)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        with_typed!(SliceIter, &self.iter, iter => iter.size_hint())
+    }
+
+    fn nth(&mut self, n: usize) -> Option<U> {
+        with_typed!(SliceIter, &mut self.iter, iter => iter.nth(n).map(|&x| U::from_element(x)))
+    }
+
+    fn fold<B, F: FnMut(B, U) -> B>(self, init: B, f: F) -> B {
+        with_typed!(SliceIter, self.iter, iter => iter.map(|&x| U::from_element(x)).fold(init, f))
+    }
+}
+
+impl<U: Float> DoubleEndedIterator for Values<'_, U> {
+    fn next_back(&mut self) -> Option<U> {
+        with_typed!(SliceIter, &mut self.iter, iter => iter.next_back().map(|&x| U::from_element(x)))
+    }
+
+    fn rfold<B, F: FnMut(B, U) -> B>(self, init: B, f: F) -> B {
+        with_typed!(SliceIter, self.iter, iter => iter.map(|&x| U::from_element(x)).rfold(init, f))
+    }
+}
+
+impl<U: Float> ExactSizeIterator for Values<'_, U> {}
+
+/// The parallel iterator behind [`Tensor::par_iter_f32`] and
+/// [`Tensor::par_iter_f64`]. The element type is checked once, when rayon
+/// drives the iterator, and each task then loops over its typed subslice.
+struct ParValues<'a, U> {
+    data: Slice<'a>,
+    out: PhantomData<fn() -> U>,
+}
+
+impl<'a, U: Float> ParValues<'a, U> {
+    fn new(tensor: &'a Tensor) -> Self {
+        Self {
+            data: Slice::of(tensor),
+            out: PhantomData,
+        }
+    }
+}
+
+impl<U: Float> ParallelIterator for ParValues<'_, U> {
+    type Item = U;
+
+    fn drive_unindexed<C: UnindexedConsumer<U>>(self, consumer: C) -> C::Result {
+        with_typed!(Slice, self.data, data => {
+            data.par_iter().map(|&x| U::from_element(x)).drive_unindexed(consumer)
+        })
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+impl<U: Float> IndexedParallelIterator for ParValues<'_, U> {
+    fn len(&self) -> usize {
+        with_typed!(Slice, &self.data, data => data.len())
+    }
+
+    fn drive<C: Consumer<U>>(self, consumer: C) -> C::Result {
+        with_typed!(Slice, self.data, data => data.par_iter().map(|&x| U::from_element(x)).drive(consumer))
+    }
+
+    fn with_producer<CB: ProducerCallback<U>>(self, callback: CB) -> CB::Output {
+        with_typed!(Slice, self.data, data => {
+            data.par_iter().map(|&x| U::from_element(x)).with_producer(callback)
+        })
+    }
+}
@@ -0,0 +1,127 @@
+//! Comparison of two checkpoints tensor by tensor.
+
+use crate::{AllcloseResult, Dtype, Result, TensorSource};
+use std::collections::HashSet;
+
+/// A tensor present on both sides whose dtype or shape differs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderMismatch {
+    pub name: String,
+    pub a: (Dtype, Vec<usize>),
+    pub b: (Dtype, Vec<usize>),
+}
+
+/// The value comparison of a tensor present on both sides with equal shapes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValueDiff {
+    pub name: String,
+    /// Largest `|a - b|`. Infinite where only one side is NaN; two NaNs count
+    /// as equal.
+    pub max_abs_diff: f64,
+    pub close: AllcloseResult,
+}
+
+/// Differences between two checkpoints `a` and `b`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Diff {
+    pub only_in_a: Vec<String>,
+    pub only_in_b: Vec<String>,
+    /// Shared tensors whose shapes differ, whatever their dtypes.
+    pub header_mismatches: Vec<HeaderMismatch>,
+    /// Shared tensors with equal shapes but different dtypes. Their values
+    /// are still compared, so these alone do not fail [`Diff::passed`].
+    pub dtype_changes: Vec<HeaderMismatch>,
+    /// One entry per compared tensor, empty for a header-only diff.
+    pub values: Vec<ValueDiff>,
+}
+
+impl Diff {
+    /// Whether both sides hold the same tensors with the same shapes, and
+    /// every compared tensor is within tolerance. Dtypes may differ.
+    pub fn passed(&self) -> bool {
+        self.only_in_a.is_empty()
+            && self.only_in_b.is_empty()
+            && self.header_mismatches.is_empty()
+            && self.values.iter().all(|value| value.close.passed)
+    }
+
+    /// Like [`Diff::passed`], but a dtype change also fails.
+    pub fn passed_strict(&self) -> bool {
+        self.passed() && self.dtype_changes.is_empty()
+    }
+}
+
+/// Compares the names, dtypes and shapes of two checkpoints without reading
+/// any tensor data.
+pub fn diff_headers(a: &impl TensorSource, b: &impl TensorSource) -> Diff {
+    let a_names = a.names();
+    let b_names = b.names();
+    let a_set: HashSet<&str> = a_names.iter().copied().collect();
+    let b_set: HashSet<&str> = b_names.iter().copied().collect();
+
+    let mut diff = Diff {
+        only_in_a: a_names
+            .iter()
+            .filter(|name| !b_set.contains(*name))
+            .map(|name| name.to_string())
+            .collect(),
+        only_in_b: b_names
+            .iter()
+            .filter(|name| !a_set.contains(*name))
+            .map(|name| name.to_string())
+            .collect(),
+        ..Diff::default()
+    };
+
+    for &name in a_names.iter().filter(|name| b_set.contains(*name)) {
+        let (a_info, b_info) = (a.info(name).unwrap(), b.info(name).unwrap());
+        let mismatch = HeaderMismatch {
+            name: name.to_string(),
+            a: (a_info.dtype(), a_info.shape().to_vec()),
+            b: (b_info.dtype(), b_info.shape().to_vec()),
+        };
+        if a_info.shape() != b_info.shape() {
+            diff.header_mismatches.push(mismatch);
+        } else if a_info.dtype() != b_info.dtype() {
+            diff.dtype_changes.push(mismatch);
+        }
+    }
+    diff
+}
+
+/// Compares two checkpoints like [`diff_headers`], then compares the values
+/// of every shared tensor whose shapes match, even if the dtypes differ.
+/// Tensors are loaded one pair at a time, and pass if they satisfy
+/// [`Tensor::allclose`](crate::Tensor::allclose) with NaN equal to NaN.
+pub fn diff(a: &impl TensorSource, b: &impl TensorSource, rtol: f64, atol: f64) -> Result<Diff> {
+    let mut diff = diff_headers(a, b);
+    let b_names: HashSet<&str> = b.names().into_iter().collect();
+
+    for name in a.names() {
+        let shape_mismatch = diff
+            .header_mismatches
+            .iter()
+            .any(|mismatch| mismatch.name == name);
+        if !b_names.contains(name) || shape_mismatch {
+            continue;
+        }
+
+        let (a_tensor, b_tensor) = (a.load(name)?, b.load(name)?);
+        let max_abs_diff = a_tensor
+            .iter_f64()
+            .zip(b_tensor.iter_f64())
+            .map(|(x, y)| match (x.is_nan(), y.is_nan()) {
+                (true, true) => 0.0,
+                (true, false) | (false, true) => f64::INFINITY,
+                (false, false) if x == y => 0.0,
+                (false, false) => (x - y).abs(),
+            })
+            .fold(0.0, f64::max);
+        diff.values.push(ValueDiff {
+            name: name.to_string(),
+            max_abs_diff,
+            close: a_tensor.allclose_with(&b_tensor, rtol, atol, true),
+        });
+    }
+    Ok(diff)
+}
@@ -0,0 +1,442 @@
+use crate::Dtype;
+use std::fmt;
+use std::io;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    Json(serde_json::Error),
+    #[cfg(feature = "zip")]
+    Zip(zip::result::ZipError),
+    #[cfg(feature = "candle")]
+    Candle(candle_core::Error),
+    #[cfg(feature = "tch")]
+    Tch(tch::TchError),
+    #[cfg(feature = "image")]
+    Image(image::ImageError),
+    /// The header is well-formed JSON but does not describe a valid file.
+    InvalidHeader(String),
+    /// A zip member is compressed, so it cannot be read in place.
+    #[cfg(feature = "zip")]
+    CompressedMember(String),
+    /// The `__metadata__` map does not deserialize into the requested type.
+    /// `path` locates the offending value, such as `run.seed`.
+    Metadata {
+        path: String,
+        error: serde_json::Error,
+    },
+    /// A header entry has a field other than `dtype`, `shape` and
+    /// `data_offsets`, and unknown fields were configured to be rejected.
+    UnknownField {
+        tensor: String,
+        field: String,
+    },
+    /// A sharded checkpoint index is inconsistent with its shards.
+    InvalidIndex(String),
+    /// No tensor with this name exists.
+    TensorNotFound(String),
+    /// Some explicitly requested tensors do not exist.
+    MissingTensors(Vec<String>),
+    /// A name pattern matched no tensors.
+    NoMatch(String),
+    /// A tensor with this name has already been added.
+    DuplicateTensor(String),
+    /// The number of bytes supplied for a tensor does not match its dtype and shape.
+    LengthMismatch {
+        name: String,
+        expected: u64,
+        actual: u64,
+    },
+    /// A byte buffer's length does not match the dtype and shape it should hold.
+    DataLength {
+        dtype: Dtype,
+        shape: Vec<usize>,
+        len: usize,
+    },
+    /// An embedded region extends past the end of its containing file.
+    RegionOutOfBounds {
+        offset: u64,
+        len: u64,
+        file_len: u64,
+    },
+    /// The number of elements supplied does not match the shape.
+    ElementCount {
+        shape: Vec<usize>,
+        len: usize,
+    },
+    /// The dtype exists in the safetensors format but is not supported here.
+    UnsupportedDtype(String),
+    /// A `.npy` stream is malformed or uses an unsupported layout.
+    InvalidNpy(String),
+    /// A nested JSON array could not be converted into a tensor.
+    JsonTensor {
+        path: String,
+        reason: String,
+    },
+    /// A tensor has an integer or bool dtype where only floats are accepted.
+    NonFloatTensor {
+        name: String,
+        dtype: Dtype,
+    },
+    /// The tensor has a different dtype than the operation requires.
+    DtypeMismatch {
+        expected: Dtype,
+        actual: Dtype,
+    },
+    /// The two operands of an elementwise operation have different shapes.
+    ShapeMismatch {
+        left: Vec<usize>,
+        right: Vec<usize>,
+    },
+    /// The tensor has a different number of dimensions than the operation requires.
+    RankMismatch {
+        expected: usize,
+        shape: Vec<usize>,
+    },
+    /// An index has a different number of coordinates than the tensor has dimensions.
+    IndexRank {
+        index: Vec<usize>,
+        shape: Vec<usize>,
+    },
+    /// A coordinate is not smaller than the length of its axis.
+    IndexOutOfBounds {
+        axis: usize,
+        index: usize,
+        shape: Vec<usize>,
+    },
+    /// A row index passed to a gather is not smaller than the number of rows.
+    /// `position` is where it appears in the index list.
+    GatherIndex {
+        position: usize,
+        index: usize,
+        shape: Vec<usize>,
+    },
+    /// A dimension index is not smaller than the tensor's rank.
+    DimOutOfRange {
+        dim: usize,
+        shape: Vec<usize>,
+    },
+    /// A range along a dimension extends past its end.
+    RangeOutOfBounds {
+        dim: usize,
+        start: usize,
+        len: usize,
+        shape: Vec<usize>,
+    },
+    /// Split sizes do not add up to the length of the split dimension.
+    SplitSizes {
+        dim: usize,
+        sizes: Vec<usize>,
+        shape: Vec<usize>,
+    },
+    /// A dimension cannot be divided into the requested number of equal chunks.
+    UnevenChunks {
+        dim: usize,
+        chunks: usize,
+        shape: Vec<usize>,
+    },
+    /// A companion tensor holding quantization parameters does not exist.
+    MissingCompanion {
+        tensor: String,
+        companion: String,
+    },
+    /// Quantization parameters hold neither a single value nor one value per
+    /// index of the quantization axis.
+    QuantParamShape {
+        shape: Vec<usize>,
+        tensor_shape: Vec<usize>,
+        axis: Option<usize>,
+    },
+    /// No tensors were given to concatenate.
+    EmptyConcat,
+    /// A tensor given to concatenate has a different dtype than the first.
+    ConcatDtype {
+        input: usize,
+        expected: Dtype,
+        actual: Dtype,
+    },
+    /// A tensor given to concatenate disagrees with the first in dimension
+    /// `dim`, or in rank when `dim` is `None`.
+    ConcatShape {
+        input: usize,
+        dim: Option<usize>,
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+    /// A max, min or argmax reduced over no elements. `dim` is `None` for a
+    /// reduction over the whole tensor.
+    EmptyReduction {
+        dim: Option<usize>,
+        shape: Vec<usize>,
+    },
+    /// A dimension to be squeezed does not have size 1.
+    NotSqueezable {
+        dim: usize,
+        shape: Vec<usize>,
+    },
+    /// A dimension order is not a permutation of the tensor's dimensions.
+    InvalidPermutation {
+        order: Vec<usize>,
+        shape: Vec<usize>,
+    },
+    /// The requested shape does not hold the same number of elements, or has
+    /// more than one inferred (`-1`) dimension.
+    Reshape {
+        shape: Vec<usize>,
+        new_shape: Vec<isize>,
+    },
+    /// The byte size of a shape does not fit in a `u64`.
+    ShapeOverflow(Vec<usize>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Json(err) => write!(f, "invalid header JSON: {err}"),
+            #[cfg(feature = "zip")]
+            Self::Zip(err) => write!(f, "zip archive error: {err}"),
+            #[cfg(feature = "zip")]
+            Self::CompressedMember(member) => {
+                write!(f, "zip member `{member}` is compressed and cannot be read in place")
+            }
+            #[cfg(feature = "candle")]
+            Self::Candle(err) => write!(f, "candle error: {err}"),
+            #[cfg(feature = "tch")]
+            Self::Tch(err) => write!(f, "tch error: {err}"),
+            #[cfg(feature = "image")]
+            Self::Image(err) => write!(f, "image error: {err}"),
+            Self::InvalidHeader(msg) => write!(f, "invalid header: {msg}"),
+            Self::Metadata { path, error } => write!(f, "metadata at `{path}`: {error}"),
+            Self::UnknownField { tensor, field } => {
+                write!(f, "tensor `{tensor}` has unknown header field `{field}`")
+            }
+            Self::InvalidIndex(msg) => write!(f, "invalid shard index: {msg}"),
+            Self::TensorNotFound(name) => write!(f, "tensor `{name}` not found"),
+            Self::MissingTensors(names) => write!(f, "tensors not found: {}", names.join(", ")),
+            Self::NoMatch(pattern) => write!(f, "pattern `{pattern}` matched no tensors"),
+            Self::DuplicateTensor(name) => write!(f, "duplicate tensor name `{name}`"),
+            Self::LengthMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "tensor `{name}` expects {expected} bytes but {actual} were supplied"
+            ),
+            Self::DataLength { dtype, shape, len } => write!(
+                f,
+                "{len} bytes cannot hold a {dtype:?} tensor of shape {shape:?}"
+            ),
+            Self::RegionOutOfBounds {
+                offset,
+                len,
+                file_len,
+            } => write!(
+                f,
+                "region of {len} bytes at offset {offset} exceeds the {file_len} byte file"
+            ),
+            Self::ElementCount { shape, len } => write!(
+                f,
+                "{len} elements cannot fill a tensor of shape {shape:?}"
+            ),
+            Self::UnsupportedDtype(dtype) => write!(f, "dtype {dtype} is not supported"),
+            Self::InvalidNpy(msg) => write!(f, "invalid .npy data: {msg}"),
+            Self::JsonTensor { path, reason } => {
+                write!(f, "invalid tensor JSON at {path}: {reason}")
+            }
+            Self::NonFloatTensor { name, dtype } => {
+                write!(f, "tensor `{name}` has non-float dtype {dtype:?}")
+            }
+            Self::DtypeMismatch { expected, actual } => {
+                write!(f, "expected a {expected:?} tensor but found {actual:?}")
+            }
+            Self::ShapeMismatch { left, right } => {
+                write!(f, "shapes {left:?} and {right:?} do not match")
+            }
+            Self::RankMismatch { expected, shape } => write!(
+                f,
+                "expected a {expected}-D tensor but found shape {shape:?}"
+            ),
+            Self::IndexRank { index, shape } => write!(
+                f,
+                "index {index:?} has {} coordinates but the tensor has shape {shape:?}",
+                index.len()
+            ),
+            Self::IndexOutOfBounds { axis, index, shape } => write!(
+                f,
+                "index {index} is out of bounds for axis {axis} of shape {shape:?}"
+            ),
+            Self::GatherIndex {
+                position,
+                index,
+                shape,
+            } => write!(
+                f,
+                "row index {index} at position {position} is out of bounds for shape {shape:?}"
+            ),
+            Self::DimOutOfRange { dim, shape } => {
+                write!(f, "dimension {dim} is out of range for shape {shape:?}")
+            }
+            Self::RangeOutOfBounds {
+                dim,
+                start,
+                len,
+                shape,
+            } => write!(
+                f,
+                "range of {len} starting at {start} exceeds dimension {dim} of shape {shape:?}"
+            ),
+            Self::SplitSizes { dim, sizes, shape } => write!(
+                f,
+                "split sizes {sizes:?} do not add up to dimension {dim} of shape {shape:?}"
+            ),
+            Self::UnevenChunks { dim, chunks, shape } => write!(
+                f,
+                "dimension {dim} of shape {shape:?} cannot be split into {chunks} equal chunks"
+            ),
+            Self::MissingCompanion { tensor, companion } => write!(
+                f,
+                "quantization parameter `{companion}` for tensor `{tensor}` not found"
+            ),
+            Self::QuantParamShape {
+                shape,
+                tensor_shape,
+                axis: Some(axis),
+            } => write!(
+                f,
+                "quantization parameters of shape {shape:?} match neither a single value nor axis {axis} of shape {tensor_shape:?}"
+            ),
+            Self::QuantParamShape {
+                shape,
+                tensor_shape,
+                axis: None,
+            } => write!(
+                f,
+                "per-tensor quantization parameters for shape {tensor_shape:?} must hold a single value, found shape {shape:?}"
+            ),
+            Self::EmptyConcat => write!(f, "no tensors to concatenate"),
+            Self::ConcatDtype {
+                input,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "concat input {input} is {actual:?} but the first input is {expected:?}"
+            ),
+            Self::ConcatShape {
+                input,
+                dim: Some(dim),
+                expected,
+                actual,
+            } => write!(
+                f,
+                "concat input {input} has shape {actual:?}, which disagrees with {expected:?} in dimension {dim}"
+            ),
+            Self::ConcatShape {
+                input,
+                dim: None,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "concat input {input} has shape {actual:?}, which differs in rank from {expected:?}"
+            ),
+            Self::EmptyReduction {
+                dim: Some(dim),
+                shape,
+            } => write!(
+                f,
+                "cannot reduce over empty dimension {dim} of shape {shape:?}"
+            ),
+            Self::EmptyReduction { dim: None, shape } => {
+                write!(f, "cannot reduce over shape {shape:?}, which has no elements")
+            }
+            Self::NotSqueezable { dim, shape } => {
+                write!(f, "dimension {dim} of shape {shape:?} does not have size 1")
+            }
+            Self::InvalidPermutation { order, shape } => write!(
+                f,
+                "{order:?} is not a permutation of the dimensions of shape {shape:?}"
+            ),
+            Self::Reshape { shape, new_shape } => {
+                let count: usize = shape.iter().product();
+                let known = new_shape
+                    .iter()
+                    .filter(|&&d| d >= 0)
+                    .fold(1usize, |acc, &d| acc.saturating_mul(d as usize));
+                write!(
+                    f,
+                    "cannot reshape {shape:?} ({count} elements) into {new_shape:?} ({known} elements"
+                )?;
+                match new_shape.iter().filter(|&&d| d < 0).count() {
+                    0 => write!(f, ")"),
+                    1 => write!(f, " plus an inferred dimension)"),
+                    _ => write!(f, "; at most one dimension may be inferred)"),
+                }
+            }
+            Self::ShapeOverflow(shape) => write!(f, "shape {shape:?} is too large"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Json(err) => Some(err),
+            #[cfg(feature = "zip")]
+            Self::Zip(err) => Some(err),
+            #[cfg(feature = "candle")]
+            Self::Candle(err) => Some(err),
+            #[cfg(feature = "tch")]
+            Self::Tch(err) => Some(err),
+            #[cfg(feature = "image")]
+            Self::Image(err) => Some(err),
+            Self::Metadata { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+#[cfg(feature = "zip")]
+impl From<zip::result::ZipError> for Error {
+    fn from(err: zip::result::ZipError) -> Self {
+        Self::Zip(err)
+    }
+}
+
+#[cfg(feature = "candle")]
+impl From<candle_core::Error> for Error {
+    fn from(err: candle_core::Error) -> Self {
+        Self::Candle(err)
+    }
+}
+
+#[cfg(feature = "tch")]
+impl From<tch::TchError> for Error {
+    fn from(err: tch::TchError) -> Self {
+        Self::Tch(err)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(err)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{LazyReader, Result, Selector, StreamWriter};
+use std::path::Path;
+
+/// Copies the tensors chosen by `selector` from `src` into a new file at `dst`.
+///
+/// Tensor bytes are copied verbatim without decoding, and only the selected
+/// byte ranges of `src` are read. String entries of `__metadata__` are carried
+/// over, except entries keyed by the name of a tensor that was not extracted,
+/// and so are any extra per-tensor header fields.
+pub fn extract(src: impl AsRef<Path>, dst: impl AsRef<Path>, selector: &Selector) -> Result<()> {
+    let reader = LazyReader::open(src)?;
+    let available = reader.names();
+    let selected = selector.resolve(&available)?;
+
+    let mut writer = StreamWriter::create(dst)?;
+    if let Some(metadata) = reader.metadata().as_object() {
+        for (key, value) in metadata {
+            let dropped = reader.info(key).is_some() && !selected.contains(&key.as_str());
+            if let (Some(value), false) = (value.as_str(), dropped) {
+                writer.insert_metadata(key.as_str(), value);
+            }
+        }
+    }
+
+    for name in selected {
+        let info = reader.info(name).expect("selected names exist");
+        writer.add_from_reader(name, info.dtype(), info.shape(), reader.raw_reader(name)?)?;
+        for (key, value) in info.extra() {
+            writer.insert_tensor_extra(name, key.as_str(), value.clone())?;
+        }
+    }
+
+    writer.finish()
+}
@@ -0,0 +1,97 @@
+//! A stable identifier for a file computed from its header.
+
+use crate::header::Header;
+use crate::Result;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::fs::File;
+use std::path::Path;
+
+/// Prefix of the canonical form, naming its version.
+const FINGERPRINT_VERSION: &str = "safetensors-header-fingerprint-v1";
+
+/// A SHA-256 hash of the file's header in canonical form, plus the length of
+/// its data section. Only the header is read.
+///
+/// This identifies a file's layout, not its contents: two files with the
+/// same tensors, dtypes, shapes, offsets and metadata but different tensor
+/// bytes have the same fingerprint. It does not depend on the header's key
+/// order, whitespace or trailing padding, and ignores per-tensor fields other
+/// than `dtype`, `shape` and `data_offsets`.
+///
+/// The hashed input is the UTF-8 text
+///
+/// `safetensors-header-fingerprint-v1\n{"data_len":L,"metadata":M,"tensors":T}`
+///
+/// without any whitespace, where `L` is the data section length in bytes,
+/// `M` is `__metadata__` (an empty object if absent or null) with the keys of
+/// every object sorted by their UTF-8 bytes, and `T` is an array of
+/// `[name, dtype, shape, [start, end]]` entries sorted by name, with dtypes
+/// spelled as in the header. Strings are escaped as by `serde_json`. This
+/// definition will not change within the `v1` name, so fingerprints remain
+/// valid as cache keys across releases of this crate.
+pub fn header_fingerprint(path: impl AsRef<Path>) -> Result<[u8; 32]> {
+    let mut file = File::open(path)?;
+    let (n, header) = Header::read(&mut file)?;
+    let data_len = file.metadata()?.len().saturating_sub(8 + n);
+
+    let mut canonical = format!("{FINGERPRINT_VERSION}\n{{\"data_len\":{data_len},\"metadata\":");
+    match &header.metadata {
+        Value::Null => canonical.push_str("{}"),
+        metadata => write_canonical(metadata, &mut canonical),
+    }
+    canonical.push_str(",\"tensors\":[");
+
+    let mut names: Vec<&String> = header.tensors.keys().collect();
+    names.sort();
+    for (i, name) in names.into_iter().enumerate() {
+        let info = &header.tensors[name];
+        let (start, end) = info.data_offsets();
+        if i > 0 {
+            canonical.push(',');
+        }
+        write!(
+            canonical,
+            "[{},{},{},[{start},{end}]]",
+            Value::from(name.as_str()),
+            serde_json::to_value(info.dtype())?,
+            serde_json::to_value(info.shape())?,
+        )
+        .expect("writing to a String cannot fail");
+    }
+    canonical.push_str("]}");
+
+    Ok(Sha256::digest(canonical.as_bytes()).into())
+}
+
+/// Appends `value` as compact JSON with every object's keys sorted.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::from(key.as_str()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
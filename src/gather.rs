@@ -0,0 +1,76 @@
+//! Selecting rows of a 2-D tensor by index, as in an embedding lookup.
+
+use crate::{trace, Element, Error, LazyReader, Result, Tensor};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+impl Tensor {
+    /// Copies rows `indices` of a 2-D `[rows, cols]` tensor, in the order
+    /// given, into a new `[indices.len(), cols]` tensor. Indices may repeat.
+    pub fn gather_rows(&self, indices: &[usize]) -> Result<Tensor> {
+        self.check_rank_2()?;
+        check_indices(indices, self.shape())?;
+        let cols = self.shape()[1];
+        with_data!(self, _shape, data => {
+            let mut gathered = Vec::with_capacity(indices.len() * cols);
+            for &i in indices {
+                gathered.extend_from_slice(&data[i * cols..(i + 1) * cols]);
+            }
+            Ok(Element::into_tensor(gathered, vec![indices.len(), cols]))
+        })
+    }
+}
+
+impl LazyReader {
+    /// Like [`Tensor::gather_rows`], reading only the requested rows of
+    /// tensor `name` from disk. Adjacent rows are fetched with a single read.
+    pub fn gather_rows(&self, name: &str, indices: &[usize]) -> Result<Tensor> {
+        let info = self
+            .info(name)
+            .ok_or_else(|| Error::TensorNotFound(name.to_string()))?;
+        if info.shape().len() != 2 {
+            return Err(Error::RankMismatch {
+                expected: 2,
+                shape: info.shape().to_vec(),
+            });
+        }
+        check_indices(indices, info.shape())?;
+
+        let row_bytes = info.dtype().byte_len(&info.shape()[1..])? as usize;
+        let mut rows: Vec<usize> = indices.to_vec();
+        rows.sort_unstable();
+        rows.dedup();
+
+        // Rows in ascending order, packed into one buffer.
+        let mut fetched = Vec::with_capacity(rows.len() * row_bytes);
+        let mut file = File::open(self.path())?;
+        let data_start = self.data_start() + info.data_offsets().0;
+        let runs = rows.chunk_by(|a, b| a + 1 == *b);
+        trace::gather_reads(name, rows.len(), runs.clone().count());
+        for run in runs {
+            let start = fetched.len();
+            fetched.resize(start + run.len() * row_bytes, 0);
+            file.seek(SeekFrom::Start(data_start + (run[0] * row_bytes) as u64))?;
+            file.read_exact(&mut fetched[start..])?;
+        }
+
+        let mut gathered = Vec::with_capacity(indices.len() * row_bytes);
+        for i in indices {
+            let position = rows.binary_search(i).expect("every index was fetched");
+            gathered.extend_from_slice(&fetched[position * row_bytes..(position + 1) * row_bytes]);
+        }
+        let shape = vec![indices.len(), info.shape()[1]];
+        Tensor::from_bytes(info.dtype(), shape, &gathered)
+    }
+}
+
+fn check_indices(indices: &[usize], shape: &[usize]) -> Result<()> {
+    match indices.iter().position(|&i| i >= shape[0]) {
+        Some(position) => Err(Error::GatherIndex {
+            position,
+            index: indices[position],
+            shape: shape.to_vec(),
+        }),
+        None => Ok(()),
+    }
+}
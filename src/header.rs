@@ -0,0 +1,254 @@
+use crate::{trace, Dtype, Error, Result, Schedule, UnknownFields};
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Headers larger than this are rejected before allocating a buffer for them.
+const MAX_HEADER_LEN: u64 = 100 << 20;
+
+/// Header entry describing where a tensor lives in the data section.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TensorInfo {
+    dtype: Dtype,
+    shape: Vec<usize>,
+    data_offsets: [u64; 2],
+    /// Fields other than the three above, kept as written.
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
+}
+
+/// The per-tensor fields defined by the format, which cannot be used as extra
+/// fields.
+pub(crate) const KNOWN_FIELDS: [&str; 3] = ["dtype", "shape", "data_offsets"];
+
+impl TensorInfo {
+    pub(crate) fn new(dtype: Dtype, shape: Vec<usize>, start: u64, end: u64) -> Self {
+        Self {
+            dtype,
+            shape,
+            data_offsets: [start, end],
+            extra: HashMap::new(),
+        }
+    }
+
+    pub fn dtype(&self) -> Dtype {
+        self.dtype
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Start and end of the tensor's bytes, relative to the data section.
+    pub fn data_offsets(&self) -> (u64, u64) {
+        (self.data_offsets[0], self.data_offsets[1])
+    }
+
+    pub fn byte_len(&self) -> u64 {
+        self.data_offsets[1] - self.data_offsets[0]
+    }
+
+    /// Fields of the header entry beyond `dtype`, `shape` and `data_offsets`,
+    /// such as vendor extensions.
+    pub fn extra(&self) -> &HashMap<String, Value> {
+        &self.extra
+    }
+
+    pub(crate) fn extra_mut(&mut self) -> &mut HashMap<String, Value> {
+        &mut self.extra
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub(crate) struct Header {
+    #[serde(rename = "__metadata__", default)]
+    pub metadata: Value,
+    #[serde(flatten, deserialize_with = "unique_entries")]
+    pub tensors: HashMap<String, TensorInfo>,
+    /// The JSON exactly as stored, including any trailing padding.
+    #[serde(skip)]
+    pub raw: String,
+}
+
+/// Collects the tensor entries of a header, rejecting a name that appears
+/// twice where a plain map would silently keep the last one.
+fn unique_entries<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<HashMap<String, TensorInfo>, D::Error> {
+    struct Entries;
+
+    impl<'de> Visitor<'de> for Entries {
+        type Value = HashMap<String, TensorInfo>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a map of tensor entries")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(
+            self,
+            mut map: A,
+        ) -> std::result::Result<Self::Value, A::Error> {
+            let mut entries = HashMap::new();
+            while let Some(name) = map.next_key::<String>()? {
+                let info = map.next_value()?;
+                if entries.insert(name.clone(), info).is_some() {
+                    return Err(de::Error::custom(format!("duplicate tensor `{name}`")));
+                }
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(Entries)
+}
+
+/// Reads the JSON header of the file at `path` exactly as stored, including
+/// any trailing padding, without parsing it or reading any tensor data.
+pub fn read_raw_header(path: impl AsRef<Path>) -> Result<String> {
+    read_raw(&mut File::open(path)?)
+}
+
+/// Reads the length prefix and the header bytes that follow it.
+fn read_raw<R: Read>(reader: &mut R) -> Result<String> {
+    let mut prefix = [0u8; 8];
+    reader.read_exact(&mut prefix)?;
+    let n = u64::from_le_bytes(prefix);
+    if n > MAX_HEADER_LEN {
+        return Err(Error::InvalidHeader(format!(
+            "header length {n} exceeds the {MAX_HEADER_LEN} byte limit"
+        )));
+    }
+
+    let mut json = vec![0u8; n as usize];
+    reader.read_exact(&mut json)?;
+    String::from_utf8(json)
+        .map_err(|err| Error::InvalidHeader(format!("header is not valid UTF-8: {err}")))
+}
+
+impl Header {
+    /// Reads the length prefix and JSON header, returning the header length
+    /// `N` alongside the parsed header. The data section starts at `8 + N`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "parse_header", skip_all)
+    )]
+    pub fn read<R: Read>(reader: &mut R) -> Result<(u64, Self)> {
+        let raw = read_raw(reader)?;
+        let mut header: Header = serde_json::from_str(&raw)?;
+        header.raw = raw;
+        Ok((header.raw.len() as u64, header))
+    }
+
+    /// Checks every entry against its dtype and shape and against the size of
+    /// the data section.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "validate_header", skip_all, fields(tensors = self.tensors.len()))
+    )]
+    pub fn validate(&self, data_len: u64) -> Result<()> {
+        for (name, info) in &self.tensors {
+            let (start, end) = info.data_offsets();
+            if start > end || end > data_len {
+                return Err(Error::InvalidHeader(format!(
+                    "tensor `{name}` has offsets [{start}, {end}] outside the {data_len} byte data section"
+                )));
+            }
+
+            let expected = info.dtype.byte_len(&info.shape)?;
+            if info.byte_len() != expected {
+                return Err(Error::InvalidHeader(format!(
+                    "tensor `{name}` spans {} bytes but {:?} {:?} needs {expected}",
+                    info.byte_len(),
+                    info.dtype,
+                    info.shape
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies `policy` to the extra fields of every entry.
+    pub fn check_unknown_fields(&self, policy: UnknownFields) -> Result<()> {
+        if policy == UnknownFields::Carry {
+            return Ok(());
+        }
+
+        for name in self.names_by_offset() {
+            let mut fields: Vec<_> = self.tensors[name].extra.keys().collect();
+            fields.sort();
+            for field in fields {
+                if policy == UnknownFields::Error {
+                    return Err(Error::UnknownField {
+                        tensor: name.to_string(),
+                        field: field.clone(),
+                    });
+                }
+                trace::unknown_field(name, field);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Finds entries that declare exactly the same byte range, dtype and
+    /// shape as another, returning `(alias, target)` pairs where `target` is
+    /// the first entry of the range by name. Fails if any other two non-empty
+    /// entries overlap.
+    pub fn aliases(&self) -> Result<Vec<(&str, &str)>> {
+        let mut names: Vec<&str> = self
+            .tensors
+            .iter()
+            .filter(|(_, info)| info.byte_len() > 0)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        names.sort_by_key(|&name| (self.tensors[name].data_offsets, name));
+
+        let mut aliases = Vec::new();
+        // The first entry of the current run of overlapping entries, and the
+        // furthest end seen in it.
+        let mut run: Option<(&str, u64)> = None;
+        for name in names {
+            let info = &self.tensors[name];
+            let (start, end) = info.data_offsets();
+            match run {
+                Some((target, run_end)) if start < run_end => {
+                    let target_info = &self.tensors[target];
+                    if target_info.data_offsets != info.data_offsets
+                        || target_info.dtype != info.dtype
+                        || target_info.shape != info.shape
+                    {
+                        return Err(Error::InvalidHeader(format!(
+                            "tensors `{target}` and `{name}` have overlapping data"
+                        )));
+                    }
+                    aliases.push((name, target));
+                }
+                _ => run = Some((name, end)),
+            }
+        }
+        Ok(aliases)
+    }
+
+    /// Tensor names in the order `schedule` starts reading them.
+    pub fn names_by_schedule(&self, schedule: Schedule) -> Vec<&str> {
+        let mut names = self.names_by_offset();
+        if schedule == Schedule::LargestFirst {
+            names.sort_by_key(|&name| std::cmp::Reverse(self.tensors[name].byte_len()));
+        }
+        names
+    }
+
+    /// Tensor names ordered by their position in the data section. Entries
+    /// that start together are ordered by end, so an empty entry comes before
+    /// the one it shares a start with, and then by name.
+    pub fn names_by_offset(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.tensors.keys().map(String::as_str).collect();
+        names.sort_by_key(|&name| (self.tensors[name].data_offsets, name));
+        names
+    }
+}
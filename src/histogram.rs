@@ -0,0 +1,223 @@
+//! Value histograms and percentile estimates.
+
+use crate::convert::PAR_MIN_LEN;
+use crate::{LazyReader, Reader, Result, Tensor};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Counts of values in equal-width bins over a closed range.
+///
+/// NaN, infinite values and finite values outside the range are counted
+/// separately and do not take part in [`Histogram::percentile`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub range: (f64, f64),
+    pub counts: Vec<u64>,
+    pub nan: u64,
+    pub pos_inf: u64,
+    pub neg_inf: u64,
+    pub below: u64,
+    pub above: u64,
+}
+
+impl Histogram {
+    /// An empty histogram of `bins` bins over `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn new(bins: usize, range: (f64, f64)) -> Self {
+        assert!(bins > 0, "a histogram needs at least one bin");
+        Self {
+            range,
+            counts: vec![0; bins],
+            nan: 0,
+            pos_inf: 0,
+            neg_inf: 0,
+            below: 0,
+            above: 0,
+        }
+    }
+
+    pub fn add(&mut self, value: f64) {
+        let (lo, hi) = self.range;
+        match value {
+            v if v.is_nan() => self.nan += 1,
+            f64::INFINITY => self.pos_inf += 1,
+            f64::NEG_INFINITY => self.neg_inf += 1,
+            v if v < lo => self.below += 1,
+            v if v > hi => self.above += 1,
+            v => {
+                let bins = self.counts.len();
+                let bin = if hi > lo {
+                    (((v - lo) / (hi - lo) * bins as f64) as usize).min(bins - 1)
+                } else {
+                    0
+                };
+                self.counts[bin] += 1;
+            }
+        }
+    }
+
+    /// Adds every element of `tensor`, in parallel for large tensors.
+    pub fn add_tensor(&mut self, tensor: &Tensor) {
+        let (bins, range) = (self.counts.len(), self.range);
+        let partial = tensor
+            .par_iter_f64()
+            .with_min_len(PAR_MIN_LEN)
+            .fold(
+                || Histogram::new(bins, range),
+                |mut histogram, value| {
+                    histogram.add(value);
+                    histogram
+                },
+            )
+            .reduce(
+                || Histogram::new(bins, range),
+                |mut a, b| {
+                    a.merge(&b);
+                    a
+                },
+            );
+        self.merge(&partial);
+    }
+
+    /// Adds the counts of a histogram with the same bins and range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the histograms have different bins or ranges.
+    pub fn merge(&mut self, other: &Histogram) {
+        assert!(
+            self.counts.len() == other.counts.len() && self.range == other.range,
+            "merged histograms must share bins and range"
+        );
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        self.nan += other.nan;
+        self.pos_inf += other.pos_inf;
+        self.neg_inf += other.neg_inf;
+        self.below += other.below;
+        self.above += other.above;
+    }
+
+    /// Number of values that fell into a bin.
+    pub fn binned(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Lower and upper edge of bin `i`.
+    pub fn bin_edges(&self, i: usize) -> (f64, f64) {
+        let (lo, hi) = self.range;
+        let width = (hi - lo) / self.counts.len() as f64;
+        (lo + width * i as f64, lo + width * (i + 1) as f64)
+    }
+
+    /// Estimates the `p`-th percentile (0 to 100) of the binned values,
+    /// assuming values are spread evenly within each bin. Returns `None` if
+    /// no value fell into a bin.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        let total = self.binned();
+        if total == 0 {
+            return None;
+        }
+
+        let target = p.clamp(0.0, 100.0) / 100.0 * total as f64;
+        let mut before = 0.0;
+        for (i, &count) in self.counts.iter().enumerate() {
+            let count = count as f64;
+            if count > 0.0 && before + count >= target {
+                let (lo, hi) = self.bin_edges(i);
+                return Some(lo + (hi - lo) * ((target - before) / count).max(0.0));
+            }
+            before += count;
+        }
+        Some(self.range.1)
+    }
+}
+
+impl Tensor {
+    /// Histogram of the elements over `range`, or over the range of the
+    /// finite elements when `range` is `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn histogram(&self, bins: usize, range: Option<(f64, f64)>) -> Histogram {
+        let range = range.unwrap_or_else(|| finite_range(self).unwrap_or((0.0, 0.0)));
+        let mut histogram = Histogram::new(bins, range);
+        histogram.add_tensor(self);
+        histogram
+    }
+}
+
+impl Reader {
+    /// Auto-ranged histograms of every tensor, keyed by name. Aliases get a
+    /// copy of the histogram of the tensor they alias.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn histograms(&self, bins: usize) -> HashMap<String, Histogram> {
+        let mut histograms: HashMap<String, Histogram> = self
+            .tensors
+            .par_iter()
+            .map(|(name, tensor)| (name.clone(), tensor.histogram(bins, None)))
+            .collect();
+        for (alias, target) in &self.aliases {
+            let histogram = histograms[target].clone();
+            histograms.insert(alias.clone(), histogram);
+        }
+        histograms
+    }
+}
+
+impl LazyReader {
+    /// Like [`Tensor::histogram`], but streams the tensor from the file in
+    /// chunks instead of loading it. Without a `range` the tensor is read
+    /// twice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn histogram(
+        &self,
+        name: &str,
+        bins: usize,
+        range: Option<(f64, f64)>,
+    ) -> Result<Histogram> {
+        const CHUNK_ELEMENTS: usize = 1 << 20;
+
+        let range = match range {
+            Some(range) => range,
+            None => {
+                let mut range = None;
+                for chunk in self.chunks(name, CHUNK_ELEMENTS)? {
+                    if let Some((lo, hi)) = finite_range(&chunk?) {
+                        range = Some(
+                            range.map_or((lo, hi), |(a, b): (f64, f64)| (a.min(lo), b.max(hi))),
+                        );
+                    }
+                }
+                range.unwrap_or((0.0, 0.0))
+            }
+        };
+
+        let mut histogram = Histogram::new(bins, range);
+        for chunk in self.chunks(name, CHUNK_ELEMENTS)? {
+            histogram.add_tensor(&chunk?);
+        }
+        Ok(histogram)
+    }
+}
+
+/// Smallest and largest finite element, if any.
+fn finite_range(tensor: &Tensor) -> Option<(f64, f64)> {
+    tensor
+        .par_iter_f64()
+        .with_min_len(PAR_MIN_LEN)
+        .filter(|x| x.is_finite())
+        .map(|x| (x, x))
+        .reduce_with(|(a, b), (c, d)| (a.min(c), b.max(d)))
+}
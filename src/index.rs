@@ -0,0 +1,100 @@
+//! Element access by multi-dimensional index.
+
+use crate::{Error, Result, Tensor};
+use half::{bf16, f16};
+
+/// A single element of any dtype.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scalar {
+    Bool(bool),
+    U8(u8),
+    I8(i8),
+    U16(u16),
+    I16(i16),
+    F16(f16),
+    Bf16(bf16),
+    U32(u32),
+    I32(i32),
+    F32(f32),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+}
+
+macro_rules! impl_from_scalar {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Scalar {
+                fn from(value: $ty) -> Self {
+                    Scalar::$variant(value)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_scalar!(
+    bool => Bool,
+    u8 => U8,
+    i8 => I8,
+    u16 => U16,
+    i16 => I16,
+    f16 => F16,
+    bf16 => Bf16,
+    u32 => U32,
+    i32 => I32,
+    f32 => F32,
+    u64 => U64,
+    i64 => I64,
+    f64 => F64,
+);
+
+impl Tensor {
+    /// Row-major (C-contiguous) strides, in elements.
+    pub fn strides(&self) -> Vec<usize> {
+        strides(self.shape())
+    }
+
+    /// The element at `index`, or `None` if the index has the wrong number of
+    /// coordinates or any coordinate is out of bounds. A rank-0 tensor is
+    /// indexed with `&[]`.
+    pub fn get(&self, index: &[usize]) -> Option<Scalar> {
+        self.try_get(index).ok()
+    }
+
+    /// Like [`Tensor::get`], but reports why the index is invalid.
+    pub fn try_get(&self, index: &[usize]) -> Result<Scalar> {
+        let flat = flat_index(self.shape(), index)?;
+        Ok(with_data!(self, _shape, data => data[flat].into()))
+    }
+}
+
+pub(crate) fn strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for i in (1..shape.len()).rev() {
+        strides[i - 1] = strides[i] * shape[i];
+    }
+    strides
+}
+
+fn flat_index(shape: &[usize], index: &[usize]) -> Result<usize> {
+    if index.len() != shape.len() {
+        return Err(Error::IndexRank {
+            index: index.to_vec(),
+            shape: shape.to_vec(),
+        });
+    }
+
+    let mut flat = 0;
+    for (axis, (&i, &len)) in index.iter().zip(shape).enumerate() {
+        if i >= len {
+            return Err(Error::IndexOutOfBounds {
+                axis,
+                index: i,
+                shape: shape.to_vec(),
+            });
+        }
+        flat = flat * len + i;
+    }
+    Ok(flat)
+}
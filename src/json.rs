@@ -0,0 +1,144 @@
+//! Building tensors from nested JSON arrays.
+
+use crate::{Dtype, Element, Error, Result, Tensor};
+use half::{bf16, f16};
+use serde_json::Value;
+
+/// Converts a JSON scalar into an element, or explains why it can't.
+trait FromJson: Sized {
+    fn from_json(value: &Value) -> std::result::Result<Self, String>;
+}
+
+impl FromJson for bool {
+    fn from_json(value: &Value) -> std::result::Result<Self, String> {
+        value
+            .as_bool()
+            .ok_or_else(|| format!("expected a bool, found {value}"))
+    }
+}
+
+macro_rules! impl_from_json_int {
+    ($($ty:ty),*) => {
+        $(
+            impl FromJson for $ty {
+                fn from_json(value: &Value) -> std::result::Result<Self, String> {
+                    let converted = match value {
+                        Value::Number(n) if n.is_i64() => n.as_i64().and_then(|x| x.try_into().ok()),
+                        Value::Number(n) if n.is_u64() => n.as_u64().and_then(|x| x.try_into().ok()),
+                        _ => return Err(format!("expected an integer, found {value}")),
+                    };
+                    converted.ok_or_else(|| format!("{value} is out of range for {}", stringify!($ty)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_json_int!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+macro_rules! impl_from_json_float {
+    ($($ty:ty => $convert:expr),*) => {
+        $(
+            impl FromJson for $ty {
+                fn from_json(value: &Value) -> std::result::Result<Self, String> {
+                    value
+                        .as_f64()
+                        .map($convert)
+                        .ok_or_else(|| format!("expected a number, found {value}"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_json_float!(
+    f16 => f16::from_f64,
+    bf16 => bf16::from_f64,
+    f32 => |x| x as f32,
+    f64 => |x| x
+);
+
+impl Tensor {
+    /// Builds a `dtype` tensor from arbitrarily nested JSON arrays, such as
+    /// `[[1.0, 2.0], [3.0, 4.0]]`. The shape is inferred from the nesting and
+    /// a bare scalar gives a rank-0 tensor.
+    ///
+    /// Ragged arrays, non-numeric values and integers outside the range of
+    /// `dtype` are rejected with [`Error::JsonTensor`] naming the offending
+    /// element, e.g. `$[1][0]`.
+    pub fn from_json(dtype: Dtype, value: &Value) -> Result<Tensor> {
+        match dtype {
+            Dtype::Bool => collect::<bool>(value),
+            Dtype::U8 => collect::<u8>(value),
+            Dtype::I8 => collect::<i8>(value),
+            Dtype::U16 => collect::<u16>(value),
+            Dtype::I16 => collect::<i16>(value),
+            Dtype::F16 => collect::<f16>(value),
+            Dtype::Bf16 => collect::<bf16>(value),
+            Dtype::U32 => collect::<u32>(value),
+            Dtype::I32 => collect::<i32>(value),
+            Dtype::F32 => collect::<f32>(value),
+            Dtype::U64 => collect::<u64>(value),
+            Dtype::I64 => collect::<i64>(value),
+            Dtype::F64 => collect::<f64>(value),
+        }
+    }
+}
+
+fn collect<T: FromJson + Element>(value: &Value) -> Result<Tensor> {
+    // The shape is read off the first element at each depth; every other
+    // element is then checked against it.
+    let mut shape = Vec::new();
+    let mut first = value;
+    while let Value::Array(items) = first {
+        shape.push(items.len());
+        match items.first() {
+            Some(item) => first = item,
+            None => break,
+        }
+    }
+
+    let mut data = Vec::with_capacity(shape.iter().product());
+    flatten(value, &shape, &mut "$".to_string(), &mut data)?;
+    Ok(T::into_tensor(data, shape))
+}
+
+fn flatten<T: FromJson>(
+    value: &Value,
+    shape: &[usize],
+    path: &mut String,
+    out: &mut Vec<T>,
+) -> Result<()> {
+    let error = |path: &str, reason: String| Error::JsonTensor {
+        path: path.to_string(),
+        reason,
+    };
+
+    match (value, shape.split_first()) {
+        (Value::Array(items), Some((&len, rest))) if items.len() == len => {
+            for (i, item) in items.iter().enumerate() {
+                let mark = path.len();
+                path.push_str(&format!("[{i}]"));
+                flatten(item, rest, path, out)?;
+                path.truncate(mark);
+            }
+            Ok(())
+        }
+        (Value::Array(items), Some((&len, _))) => Err(error(
+            path,
+            format!(
+                "ragged array: expected {len} elements, found {}",
+                items.len()
+            ),
+        )),
+        (Value::Array(_), None) => Err(error(path, "ragged array: expected a scalar".to_string())),
+        (_, Some((&len, _))) => Err(error(
+            path,
+            format!("ragged array: expected an array of {len} elements, found {value}"),
+        )),
+        (_, None) => {
+            out.push(T::from_json(value).map_err(|reason| error(path, reason))?);
+            Ok(())
+        }
+    }
+}
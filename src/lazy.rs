@@ -0,0 +1,158 @@
+use crate::header::{Header, TensorInfo};
+use crate::{read_data, read_tensor, region_end, trace, Error, ReaderOptions, Result, Tensor};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Reads only the header up front and loads tensors on demand.
+///
+/// The reader holds no open file and no mutable state: every read opens its
+/// own handle and seeks it independently. It is `Send + Sync`, so one reader
+/// can be shared behind an `Arc` and loaded from many threads at once
+/// without the reads serializing on each other.
+pub struct LazyReader {
+    path: PathBuf,
+    data_start: u64,
+    header: Header,
+}
+
+// Sharing across threads is part of the API; keep it from regressing.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<LazyReader>();
+};
+
+impl LazyReader {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with(path, &ReaderOptions::default())
+    }
+
+    pub fn open_with(path: impl AsRef<Path>, options: &ReaderOptions) -> Result<Self> {
+        Self::open_at_with(path, 0, None, options)
+    }
+
+    /// Like [`Reader::from_file_at`](crate::Reader::from_file_at), for a
+    /// payload embedded in a larger file.
+    pub fn open_at(path: impl AsRef<Path>, offset: u64, len: Option<u64>) -> Result<Self> {
+        Self::open_at_with(path, offset, len, &ReaderOptions::default())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "LazyReader::open", skip_all, fields(path = %path.as_ref().display(), offset))
+    )]
+    pub fn open_at_with(
+        path: impl AsRef<Path>,
+        offset: u64,
+        len: Option<u64>,
+        options: &ReaderOptions,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = File::open(&path)?;
+        let end = region_end(&file, offset, len)?;
+
+        file.seek(SeekFrom::Start(offset))?;
+        let (n, header) = Header::read(&mut (&mut file).take(end - offset))?;
+        let data_start = offset + 8 + n;
+        header.validate(end.saturating_sub(data_start))?;
+        header.check_unknown_fields(options.unknown_fields)?;
+
+        Ok(Self {
+            path,
+            data_start,
+            header,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn metadata(&self) -> &serde_json::Value {
+        &self.header.metadata
+    }
+
+    /// Length in bytes of the JSON header; tensor data begins at `8 + N`.
+    pub fn header_len(&self) -> u64 {
+        self.header.raw.len() as u64
+    }
+
+    /// The JSON header exactly as stored in the file, including any trailing
+    /// padding.
+    pub fn raw_header(&self) -> &str {
+        &self.header.raw
+    }
+
+    /// Position in the file of the first byte of the data section.
+    pub(crate) fn data_start(&self) -> u64 {
+        self.data_start
+    }
+
+    /// Tensor names ordered by their position in the file.
+    pub fn names(&self) -> Vec<&str> {
+        self.header.names_by_offset()
+    }
+
+    pub fn info(&self, name: &str) -> Option<&TensorInfo> {
+        self.header.tensors.get(name)
+    }
+
+    /// Entries that declare exactly the same byte range, dtype and shape as
+    /// another, as `(alias, target)` pairs sorted by alias, where `target` is
+    /// the first entry of the range by name. These are what
+    /// [`ReaderOptions::allow_aliases`] accepts. Fails with
+    /// [`Error::InvalidHeader`] if any other two entries overlap.
+    pub fn aliases(&self) -> Result<Vec<(&str, &str)>> {
+        let mut aliases = self.header.aliases()?;
+        aliases.sort_unstable();
+        Ok(aliases)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Tensor> {
+        let info = self.get_info(name)?;
+        trace::read_tensor(&trace::current(), name, info.byte_len(), || {
+            let mut file = File::open(&self.path)?;
+            Ok(read_tensor(&mut file, self.data_start, info)?)
+        })
+    }
+
+    /// Returns a reader over the tensor's raw bytes, without decoding them.
+    pub fn raw_reader(&self, name: &str) -> Result<impl Read> {
+        let info = self.get_info(name)?;
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.data_start + info.data_offsets().0))?;
+        Ok(file.take(info.byte_len()))
+    }
+
+    /// Decodes the tensor in row-major order as 1-D tensors of at most
+    /// `elements` elements each, so tensors larger than memory can be scanned.
+    pub fn chunks(
+        &self,
+        name: &str,
+        elements: usize,
+    ) -> Result<impl Iterator<Item = Result<Tensor>>> {
+        let info = self.get_info(name)?;
+        let dtype = info.dtype();
+        let mut remaining = info.shape().iter().product::<usize>();
+        let mut reader = self.raw_reader(name)?;
+        let elements = elements.max(1);
+
+        Ok(std::iter::from_fn(move || {
+            if remaining == 0 {
+                return None;
+            }
+            let len = remaining.min(elements);
+            remaining -= len;
+            let chunk = read_data(&mut reader, dtype, vec![len]);
+            if chunk.is_err() {
+                remaining = 0;
+            }
+            Some(chunk.map_err(Error::from))
+        }))
+    }
+
+    fn get_info(&self, name: &str) -> Result<&TensorInfo> {
+        self.info(name)
+            .ok_or_else(|| Error::TensorNotFound(name.to_string()))
+    }
+}
@@ -1,159 +1,805 @@
-use bytemuck;
+/// Evaluates `$body` with `$shape` and `$data` bound to the fields of whichever
+/// variant `$tensor` is. The body is instantiated once per element type.
+macro_rules! with_data {
+    ($tensor:expr, $shape:pat, $data:ident => $body:expr) => {
+        match $tensor {
+            Tensor::Bool {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::U8 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::I8 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::U16 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::I16 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::F16 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::Bf16 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::U32 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::I32 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::F32 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::U64 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::I64 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+            Tensor::F64 {
+                data: $data,
+                shape: $shape,
+            } => $body,
+        }
+    };
+}
+
+/// Evaluates `$body` with the type alias `$ty` set to the element type of
+/// `$dtype`. The body is instantiated once per element type.
+macro_rules! with_dtype {
+    ($dtype:expr, $ty:ident => $body:expr) => {
+        match $dtype {
+            Dtype::Bool => {
+                type $ty = bool;
+                $body
+            }
+            Dtype::U8 => {
+                type $ty = u8;
+                $body
+            }
+            Dtype::I8 => {
+                type $ty = i8;
+                $body
+            }
+            Dtype::U16 => {
+                type $ty = u16;
+                $body
+            }
+            Dtype::I16 => {
+                type $ty = i16;
+                $body
+            }
+            Dtype::F16 => {
+                type $ty = half::f16;
+                $body
+            }
+            Dtype::Bf16 => {
+                type $ty = half::bf16;
+                $body
+            }
+            Dtype::U32 => {
+                type $ty = u32;
+                $body
+            }
+            Dtype::I32 => {
+                type $ty = i32;
+                $body
+            }
+            Dtype::F32 => {
+                type $ty = f32;
+                $body
+            }
+            Dtype::U64 => {
+                type $ty = u64;
+                $body
+            }
+            Dtype::I64 => {
+                type $ty = i64;
+                $body
+            }
+            Dtype::F64 => {
+                type $ty = f64;
+                $body
+            }
+        }
+    };
+}
+
+#[cfg(feature = "zip")]
+mod archive;
+mod arith;
+#[cfg(feature = "candle")]
+mod candle;
+mod cast;
+mod compare;
+mod constructors;
+mod convert;
+mod diff;
+mod error;
+mod extract;
+mod fingerprint;
+mod gather;
+mod header;
+mod histogram;
+mod index;
+mod json;
+mod lazy;
+#[cfg(feature = "nalgebra")]
+mod linalg;
+mod meta;
+mod metadata;
+mod norm;
+mod npy;
+mod ops;
+mod options;
+mod pgm;
+mod plan;
+mod provenance;
+#[cfg(feature = "python")]
+mod python;
+mod quant;
+mod raw;
+mod reduce;
+mod rows;
+mod select;
+mod sharded;
+mod shared;
+mod source;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "tch")]
+mod torch;
+mod trace;
+#[cfg(feature = "safetensors")]
+mod upstream;
+mod writer;
+
+#[cfg(feature = "zip")]
+pub use archive::list_safetensors_members;
+pub use cast::{CastReport, CastSummary, ConvertPolicy, ConvertSummary};
+pub use compare::AllcloseResult;
+pub use diff::{diff, diff_headers, Diff, HeaderMismatch, ValueDiff};
+pub use error::{Error, Result};
+pub use extract::extract;
+pub use fingerprint::header_fingerprint;
+pub use header::{read_raw_header, TensorInfo};
+pub use histogram::Histogram;
+pub use index::Scalar;
+pub use lazy::LazyReader;
+pub use meta::{read_meta, MetaTensor};
+pub use norm::NormKind;
+pub use npy::Bf16Policy;
+pub use options::{NonMatrix, ReaderOptions, Schedule, TensorPredicate, UnknownFields};
+pub use pgm::{Normalize, NAN_PIXEL};
+pub use plan::{LoadPlan, PlannedTensor};
+pub use provenance::{Framework, Provenance};
+#[cfg(feature = "python")]
+pub use python::safetensors_reader as python_module;
+pub use quant::QuantScheme;
+pub use raw::RawData;
+pub use rows::RowView;
+pub use select::Selector;
+pub use sharded::ShardedReader;
+pub use shared::SharedTensor;
+pub use source::TensorSource;
+#[cfg(feature = "tch")]
+pub use torch::NonContiguous;
+pub use writer::StreamWriter;
+
+use header::Header;
+
 use byteorder::{LittleEndian, ReadBytesExt};
 use half::{bf16, f16};
 use rayon::prelude::*;
-use serde::Deserialize;
-use serde_json;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::error::Error;
 use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-#[derive(Deserialize, Debug)]
-struct MetadataValue {
-    dtype: String,
-    shape: Vec<usize>,
-    data_offsets: Vec<usize>,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Dtype {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    F16,
+    Bf16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
 }
 
-#[derive(Deserialize, Debug)]
-struct Metadata {
-    #[serde(rename = "__metadata__")]
-    metadata: serde_json::Value,
-    #[serde(flatten)]
-    items: HashMap<String, MetadataValue>,
+impl Dtype {
+    /// Size of a single element in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            Self::Bool | Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 | Self::F16 | Self::Bf16 => 2,
+            Self::U32 | Self::I32 | Self::F32 => 4,
+            Self::U64 | Self::I64 | Self::F64 => 8,
+        }
+    }
+
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::F16 | Self::Bf16 | Self::F32 | Self::F64)
+    }
+
+    /// Number of bytes needed to store a tensor of this dtype with `shape`.
+    pub(crate) fn byte_len(self, shape: &[usize]) -> Result<u64> {
+        shape
+            .iter()
+            .try_fold(self.size() as u64, |acc, &dim| acc.checked_mul(dim as u64))
+            .ok_or_else(|| Error::ShapeOverflow(shape.to_vec()))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum Tensor {
+    Bool { data: Vec<bool>, shape: Vec<usize> },
     U8 { data: Vec<u8>, shape: Vec<usize> },
+    I8 { data: Vec<i8>, shape: Vec<usize> },
+    U16 { data: Vec<u16>, shape: Vec<usize> },
+    I16 { data: Vec<i16>, shape: Vec<usize> },
     F16 { data: Vec<f16>, shape: Vec<usize> },
     Bf16 { data: Vec<bf16>, shape: Vec<usize> },
+    U32 { data: Vec<u32>, shape: Vec<usize> },
+    I32 { data: Vec<i32>, shape: Vec<usize> },
     F32 { data: Vec<f32>, shape: Vec<usize> },
+    U64 { data: Vec<u64>, shape: Vec<usize> },
+    I64 { data: Vec<i64>, shape: Vec<usize> },
+    F64 { data: Vec<f64>, shape: Vec<usize> },
 }
 
 impl Tensor {
     pub fn shape(&self) -> &[usize] {
+        with_data!(self, shape, _data => shape)
+    }
+
+    /// Number of elements, the product of the shape.
+    pub fn numel(&self) -> usize {
+        self.shape().iter().product()
+    }
+
+    pub fn dtype(&self) -> Dtype {
         match self {
-            Self::U8 { shape, .. } => shape,
-            Self::F16 { shape, .. } => shape,
-            Self::Bf16 { shape, .. } => shape,
-            Self::F32 { shape, .. } => shape,
+            Self::Bool { .. } => Dtype::Bool,
+            Self::U8 { .. } => Dtype::U8,
+            Self::I8 { .. } => Dtype::I8,
+            Self::U16 { .. } => Dtype::U16,
+            Self::I16 { .. } => Dtype::I16,
+            Self::F16 { .. } => Dtype::F16,
+            Self::Bf16 { .. } => Dtype::Bf16,
+            Self::U32 { .. } => Dtype::U32,
+            Self::I32 { .. } => Dtype::I32,
+            Self::F32 { .. } => Dtype::F32,
+            Self::U64 { .. } => Dtype::U64,
+            Self::I64 { .. } => Dtype::I64,
+            Self::F64 { .. } => Dtype::F64,
         }
     }
+
+    /// Borrows the data as `T`, failing if the tensor's dtype is not `T::DTYPE`.
+    pub fn as_slice<T: Element>(&self) -> Result<&[T]> {
+        T::slice(self).ok_or(Error::DtypeMismatch {
+            expected: T::DTYPE,
+            actual: self.dtype(),
+        })
+    }
+
+    /// Builds a tensor by copying little-endian `bytes`, which need not be
+    /// aligned for the element type.
+    pub fn from_bytes(dtype: Dtype, shape: Vec<usize>, bytes: &[u8]) -> Result<Tensor> {
+        if dtype.byte_len(&shape)? != bytes.len() as u64 {
+            return Err(Error::DataLength {
+                dtype,
+                shape,
+                len: bytes.len(),
+            });
+        }
+
+        Ok(read_data(&mut &bytes[..], dtype, shape)?)
+    }
+
+    /// The raw little-endian bytes backing this tensor.
+    pub fn as_bytes(&self) -> &[u8] {
+        with_data!(self, _shape, data => bytemuck::cast_slice(data))
+    }
+}
+
+/// A Rust element type with a matching safetensors dtype.
+pub trait Element: Copy + Send + Sync + 'static {
+    const DTYPE: Dtype;
+
+    /// Borrows the tensor's data if its dtype is `Self::DTYPE`.
+    fn slice(tensor: &Tensor) -> Option<&[Self]>;
+
+    /// Borrows the tensor's data mutably if its dtype is `Self::DTYPE`.
+    fn slice_mut(tensor: &mut Tensor) -> Option<&mut [Self]>;
+
+    fn into_tensor(data: Vec<Self>, shape: Vec<usize>) -> Tensor;
+
+    /// The value as an f64, which represents every element type except the
+    /// largest 64-bit integers exactly.
+    fn to_f64(self) -> f64;
+
+    /// The value as an f32, rounded to nearest.
+    fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Converts an f64 to the nearest value of this type. Integer types round
+    /// to the nearest integer and saturate at their bounds, with NaN becoming
+    /// zero; bool behaves like an integer type with bounds 0 and 1.
+    fn from_f64(value: f64) -> Self;
 }
 
+macro_rules! impl_element {
+    ($($ty:ty => $variant:ident, |$x:ident| $to_f64:expr, |$v:ident| $from_f64:expr);* $(;)?) => {
+        $(
+            impl Element for $ty {
+                const DTYPE: Dtype = Dtype::$variant;
+
+                fn to_f64(self) -> f64 {
+                    let $x = self;
+                    $to_f64
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    let $v = value;
+                    $from_f64
+                }
+
+                fn slice(tensor: &Tensor) -> Option<&[Self]> {
+                    match tensor {
+                        Tensor::$variant { data, .. } => Some(data),
+                        _ => None,
+                    }
+                }
+
+                fn slice_mut(tensor: &mut Tensor) -> Option<&mut [Self]> {
+                    match tensor {
+                        Tensor::$variant { data, .. } => Some(data),
+                        _ => None,
+                    }
+                }
+
+                fn into_tensor(data: Vec<Self>, shape: Vec<usize>) -> Tensor {
+                    Tensor::$variant { data, shape }
+                }
+            }
+        )*
+    };
+}
+
+impl_element!(
+    bool => Bool, |x| x as u8 as f64, |v| v.round() as u8 != 0;
+    u8 => U8, |x| x as f64, |v| v.round() as u8;
+    i8 => I8, |x| x as f64, |v| v.round() as i8;
+    u16 => U16, |x| x as f64, |v| v.round() as u16;
+    i16 => I16, |x| x as f64, |v| v.round() as i16;
+    f16 => F16, |x| x.to_f64(), |v| f16::from_f64(v);
+    bf16 => Bf16, |x| x.to_f64(), |v| bf16::from_f64(v);
+    u32 => U32, |x| x as f64, |v| v.round() as u32;
+    i32 => I32, |x| x as f64, |v| v.round() as i32;
+    f32 => F32, |x| x as f64, |v| v as f32;
+    u64 => U64, |x| x as f64, |v| v.round() as u64;
+    i64 => I64, |x| x as f64, |v| v.round() as i64;
+    f64 => F64, |x| x, |v| v;
+);
+
 pub struct Reader {
     pub metadata: serde_json::Value,
     pub tensors: HashMap<String, Tensor>,
+    /// Names that share the data of another tensor, mapped to the name it is
+    /// stored under in `tensors`. Only filled when
+    /// [`ReaderOptions::allow_aliases`] is set; see [`Reader::tensor`].
+    pub aliases: HashMap<String, String>,
+    raw_header: Option<String>,
+    placeholder: bool,
 }
 
 impl Reader {
-    pub fn from_file(path: &'static str) -> Result<Self, Box<dyn Error>> {
-        let mut file = File::open(path)?;
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_file_with(path, &ReaderOptions::default())
+    }
+
+    pub fn from_file_with(path: impl AsRef<Path>, options: &ReaderOptions) -> Result<Self> {
+        Self::from_file_at_with(path, 0, None, options)
+    }
+
+    /// Reads a safetensors payload embedded in a larger file, starting at byte
+    /// `offset` and spanning `len` bytes, or the rest of the file if `len` is
+    /// `None`. Offsets in the header are relative to the embedded payload,
+    /// and tensors must lie within it.
+    pub fn from_file_at(path: impl AsRef<Path>, offset: u64, len: Option<u64>) -> Result<Self> {
+        Self::from_file_at_with(path, offset, len, &ReaderOptions::default())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Reader::from_file", skip_all, fields(path = %path.as_ref().display(), offset))
+    )]
+    pub fn from_file_at_with(
+        path: impl AsRef<Path>,
+        offset: u64,
+        len: Option<u64>,
+        options: &ReaderOptions,
+    ) -> Result<Self> {
+        let Loaded {
+            header,
+            tensors,
+            aliases,
+        } = read_file(path.as_ref(), offset, len, options)?;
+
+        Ok(Reader {
+            metadata: header.metadata,
+            tensors,
+            aliases: aliases.into_iter().collect(),
+            raw_header: Some(header.raw),
+            placeholder: false,
+        })
+    }
 
-        // Read the first 8 bytes to get N.
-        let n_bytes = read_bytes(&mut file, 8)?;
-        let n = u64::from_le_bytes(n_bytes.try_into().unwrap());
+    /// Loads every tensor of the file at `path` into shared storage, as
+    /// [`Reader::into_shared_tensors`] does. With
+    /// [`ReaderOptions::allow_aliases`], names that alias the same data share
+    /// a single copy of it.
+    pub fn from_file_shared(
+        path: impl AsRef<Path>,
+        options: &ReaderOptions,
+    ) -> Result<HashMap<String, SharedTensor>> {
+        Ok(Self::from_file_with(path, options)?.into_shared_tensors())
+    }
 
-        // Read the next N bytes (the JSON data).
-        let json_bytes = read_bytes(&mut file, n as usize)?;
-        // Read JSON data.
-        let json_data: Metadata = serde_json::from_slice(&json_bytes).expect("Invalid JSON");
+    /// Parses a complete safetensors file held in memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with(bytes, &ReaderOptions::default())
+    }
 
-        let mut ordered_keys: Vec<_> = json_data.items.keys().collect();
-        ordered_keys.sort_by_key(|&key| json_data.items[key].data_offsets[0]);
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "Reader::from_bytes", skip_all, fields(len = bytes.len()))
+    )]
+    pub fn from_bytes_with(bytes: &[u8], options: &ReaderOptions) -> Result<Self> {
+        let (n, header) = Header::read(&mut &bytes[..])?;
+        let data = &bytes[8 + n as usize..];
+        header.validate(data.len() as u64)?;
+        header.check_unknown_fields(options.unknown_fields)?;
 
-        let tensors: HashMap<String, Tensor> = ordered_keys
+        let aliases = options.aliases(&header)?;
+        let names: Vec<&str> = header
+            .tensors
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !aliases.iter().any(|(alias, _)| alias == name))
+            .collect();
+        let parent = trace::current();
+        let tensors = names
             .into_par_iter()
-            .map(|key| {
-                let mut f = File::open(path).unwrap();
-                let value = &json_data.items[key];
-                let (start, end) = (value.data_offsets[0], value.data_offsets[1]);
-
-                match value.dtype.as_str() {
-                    "U8" => {
-                        let size = end - start + 1;
-                        let data = read_bytes(&mut f, size).unwrap();
-
-                        (
-                            key.to_string(),
-                            Tensor::U8 {
-                                data,
-                                shape: value.shape.clone(),
-                            },
-                        )
-                    }
-                    "F16" => {
-                        assert_eq!((end - start) % 2, 0, "Invalid alignment.");
-                        let size = (end - start) / 2;
-                        f.seek(SeekFrom::Start(start as u64)).unwrap();
-                        let data: Vec<f16> =
-                            bytemuck::allocation::cast_vec(read_bytes_u16(&mut f, size).unwrap());
-
-                        (
-                            key.to_string(),
-                            Tensor::F16 {
-                                data,
-                                shape: value.shape.clone(),
-                            },
-                        )
-                    }
-                    "BF16" => {
-                        assert_eq!((end - start) % 2, 0, "Invalid alignment.");
-                        let size = (end - start) / 2;
-                        f.seek(SeekFrom::Start(start as u64)).unwrap();
-                        let data: Vec<bf16> =
-                            bytemuck::allocation::cast_vec(read_bytes_u16(&mut f, size).unwrap());
-
-                        (
-                            key.to_string(),
-                            Tensor::Bf16 {
-                                data,
-                                shape: value.shape.clone(),
-                            },
-                        )
-                    }
-                    "F32" => {
-                        assert_eq!((end - start) % 2, 0, "Invalid alignment.");
-                        let size = (end - start) / 4;
-                        f.seek(SeekFrom::Start(start as u64)).unwrap();
-                        let data = read_bytes_f32(&mut f, size).unwrap();
-
-                        (
-                            key.to_string(),
-                            Tensor::F32 {
-                                data,
-                                shape: value.shape.clone(),
-                            },
-                        )
-                    }
-                    _ => panic!("The dtype {} is currently unsupported.", value.dtype),
-                }
+            .map(|name| {
+                let info = &header.tensors[name];
+                trace::read_tensor(&parent, name, info.byte_len(), || {
+                    let (start, end) = info.data_offsets();
+                    let bytes = &data[start as usize..end as usize];
+                    let tensor = match options.transposes(name, info.shape())? {
+                        true => {
+                            trace::transposed_read(name);
+                            read_data_transposed(&mut &bytes[..], info.dtype(), info.shape())?
+                        }
+                        false => Tensor::from_bytes(info.dtype(), info.shape().to_vec(), bytes)?,
+                    };
+                    Ok((name.to_string(), tensor))
+                })
             })
+            .collect::<Result<HashMap<_, _>>>()?;
+        let aliases = aliases
+            .into_iter()
+            .map(|(alias, target)| (alias.to_string(), target.to_string()))
             .collect();
 
         Ok(Reader {
-            metadata: json_data.metadata,
+            metadata: header.metadata,
             tensors,
+            aliases,
+            raw_header: Some(header.raw),
+            placeholder: false,
         })
     }
+
+    /// Tensor `name`, or the tensor whose data it shares if it is one of
+    /// [`Reader::aliases`].
+    pub fn tensor(&self, name: &str) -> Option<&Tensor> {
+        let name = self.aliases.get(name).map_or(name, String::as_str);
+        self.tensors.get(name)
+    }
+
+    /// Length in bytes of the JSON header, the `N` of the length prefix. Tensor
+    /// data begins at byte `8 + N` of the file. `None` if the reader was not
+    /// loaded from a safetensors file.
+    pub fn header_len(&self) -> Option<u64> {
+        self.raw_header.as_ref().map(|raw| raw.len() as u64)
+    }
+
+    /// The JSON header exactly as stored in the file, including any trailing
+    /// padding. `None` if the reader was not loaded from a safetensors file.
+    pub fn raw_header(&self) -> Option<&str> {
+        self.raw_header.as_deref()
+    }
+
+    /// Like [`Reader::raw_header`], as bytes.
+    pub fn raw_header_bytes(&self) -> Option<&[u8]> {
+        self.raw_header().map(str::as_bytes)
+    }
+
+    /// Drops the retained raw header to free its memory.
+    pub fn discard_raw_header(&mut self) {
+        self.raw_header = None;
+    }
+}
+
+/// The end of the region of `file` that starts at `offset` and spans `len`
+/// bytes, or the rest of the file.
+pub(crate) fn region_end(file: &File, offset: u64, len: Option<u64>) -> Result<u64> {
+    let file_len = file.metadata()?.len();
+    let len = len.unwrap_or(file_len.saturating_sub(offset));
+    match offset.checked_add(len) {
+        Some(end) if end <= file_len => Ok(end),
+        _ => Err(Error::RegionOutOfBounds {
+            offset,
+            len,
+            file_len,
+        }),
+    }
+}
+
+/// A file's header and tensors as read by [`read_file`].
+struct Loaded {
+    header: Header,
+    /// Every tensor except aliases.
+    tensors: HashMap<String, Tensor>,
+    /// `(alias, target)` pairs, where the data of `alias` is that of `target`.
+    aliases: Vec<(String, String)>,
+}
+
+/// Reads the header and every tensor except aliases of the file region
+/// starting at `offset`.
+fn read_file(
+    path: &Path,
+    offset: u64,
+    len: Option<u64>,
+    options: &ReaderOptions,
+) -> Result<Loaded> {
+    let mut file = File::open(path)?;
+    let end = region_end(&file, offset, len)?;
+
+    file.seek(SeekFrom::Start(offset))?;
+    let (n, header) = Header::read(&mut (&mut file).take(end - offset))?;
+    let data_start = offset + 8 + n;
+    header.validate(end.saturating_sub(data_start))?;
+    header.check_unknown_fields(options.unknown_fields)?;
+
+    let aliases = options.aliases(&header)?;
+    let names: Vec<&str> = header
+        .names_by_schedule(options.schedule)
+        .into_iter()
+        .filter(|name| !aliases.iter().any(|(alias, _)| alias == name))
+        .collect();
+
+    // Workers take the next name from a shared queue rather than splitting
+    // the list up front, so reads start in exactly the scheduled order.
+    let next = AtomicUsize::new(0);
+    let parent = trace::current();
+    let tensors = (0..rayon::current_num_threads())
+        .into_par_iter()
+        .flat_map_iter(|_| {
+            std::iter::from_fn(|| names.get(next.fetch_add(1, Ordering::Relaxed))).map(|&name| {
+                let info = &header.tensors[name];
+                trace::read_tensor(&parent, name, info.byte_len(), || {
+                    let transpose = options.transposes(name, info.shape())?;
+                    let mut f = File::open(path)?;
+                    let tensor = match transpose {
+                        true => {
+                            trace::transposed_read(name);
+                            f.seek(SeekFrom::Start(data_start + info.data_offsets().0))?;
+                            read_data_transposed(&mut f, info.dtype(), info.shape())?
+                        }
+                        false => read_tensor(&mut f, data_start, info)?,
+                    };
+                    Ok((name.to_string(), tensor))
+                })
+            })
+        })
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    let aliases = aliases
+        .into_iter()
+        .map(|(alias, target)| (alias.to_string(), target.to_string()))
+        .collect();
+    Ok(Loaded {
+        header,
+        tensors,
+        aliases,
+    })
+}
+
+/// Bytes of row-major input decoded at a time by [`read_data_transposed`].
+const TRANSPOSE_BLOCK: usize = 1 << 16;
+
+/// Rows decoded per read by [`read_data_transposed`].
+pub(crate) fn transpose_block_rows(dtype: Dtype, shape: &[usize]) -> usize {
+    let row_bytes = (shape[1] * dtype.size()).max(1);
+    (TRANSPOSE_BLOCK / row_bytes).clamp(1, shape[0].max(1))
+}
+
+/// Reads the tensor described by `info` from a file whose data section
+/// begins at `data_start`.
+pub(crate) fn read_tensor(
+    file: &mut File,
+    data_start: u64,
+    info: &TensorInfo,
+) -> io::Result<Tensor> {
+    let (start, _) = info.data_offsets();
+    file.seek(SeekFrom::Start(data_start + start))?;
+    read_data(file, info.dtype(), info.shape().to_vec())
+}
+
+/// Reads the little-endian elements of a `dtype` tensor with `shape`.
+pub(crate) fn read_data<R: Read>(
+    reader: &mut R,
+    dtype: Dtype,
+    shape: Vec<usize>,
+) -> io::Result<Tensor> {
+    let size = shape.iter().product();
+
+    Ok(match dtype {
+        Dtype::Bool => Tensor::Bool {
+            data: read_bytes_bool(reader, size)?,
+            shape,
+        },
+        Dtype::U8 => Tensor::U8 {
+            data: read_bytes(reader, size)?,
+            shape,
+        },
+        Dtype::I8 => Tensor::I8 {
+            data: bytemuck::allocation::cast_vec(read_bytes(reader, size)?),
+            shape,
+        },
+        Dtype::U16 => Tensor::U16 {
+            data: read_bytes_u16(reader, size)?,
+            shape,
+        },
+        Dtype::I16 => Tensor::I16 {
+            data: bytemuck::allocation::cast_vec(read_bytes_u16(reader, size)?),
+            shape,
+        },
+        Dtype::F16 => Tensor::F16 {
+            data: bytemuck::allocation::cast_vec(read_bytes_u16(reader, size)?),
+            shape,
+        },
+        Dtype::Bf16 => Tensor::Bf16 {
+            data: bytemuck::allocation::cast_vec(read_bytes_u16(reader, size)?),
+            shape,
+        },
+        Dtype::U32 => Tensor::U32 {
+            data: read_bytes_u32(reader, size)?,
+            shape,
+        },
+        Dtype::I32 => Tensor::I32 {
+            data: bytemuck::allocation::cast_vec(read_bytes_u32(reader, size)?),
+            shape,
+        },
+        Dtype::F32 => Tensor::F32 {
+            data: read_bytes_f32(reader, size)?,
+            shape,
+        },
+        Dtype::U64 => Tensor::U64 {
+            data: read_bytes_u64(reader, size)?,
+            shape,
+        },
+        Dtype::I64 => Tensor::I64 {
+            data: bytemuck::allocation::cast_vec(read_bytes_u64(reader, size)?),
+            shape,
+        },
+        Dtype::F64 => Tensor::F64 {
+            data: read_bytes_f64(reader, size)?,
+            shape,
+        },
+    })
 }
 
-fn read_bytes(file: &mut File, size: usize) -> io::Result<Vec<u8>> {
+/// Reads a 2-D `dtype` tensor of `shape` stored row-major and returns it
+/// transposed, scattering each block of rows into place as it is read.
+pub(crate) fn read_data_transposed<R: Read>(
+    reader: &mut R,
+    dtype: Dtype,
+    shape: &[usize],
+) -> io::Result<Tensor> {
+    let (rows, cols) = (shape[0], shape[1]);
+    let block_rows = transpose_block_rows(dtype, shape);
+
+    with_dtype!(dtype, T => {
+        let mut transposed = vec![T::from_f64(0.0); rows * cols];
+        let mut row = 0;
+        while row < rows {
+            let len = block_rows.min(rows - row);
+            let block = read_data(reader, dtype, vec![len, cols])?;
+            let block = T::slice(&block).expect("read with the same dtype");
+            for (r, values) in block.chunks_exact(cols.max(1)).enumerate() {
+                for (c, &value) in values.iter().enumerate() {
+                    transposed[c * rows + row + r] = value;
+                }
+            }
+            row += len;
+        }
+        Ok(Element::into_tensor(transposed, vec![cols, rows]))
+    })
+}
+
+fn read_bytes<R: Read>(reader: &mut R, size: usize) -> io::Result<Vec<u8>> {
     let mut buffer = vec![0u8; size];
-    file.read_exact(&mut buffer)?;
+    reader.read_exact(&mut buffer)?;
     Ok(buffer)
 }
 
-fn read_bytes_u16(file: &mut File, size: usize) -> io::Result<Vec<u16>> {
+fn read_bytes_bool<R: Read>(reader: &mut R, size: usize) -> io::Result<Vec<bool>> {
+    let buffer = read_bytes(reader, size)?;
+    if buffer.iter().any(|&b| b > 1) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "bool tensor contains a byte other than 0 or 1",
+        ));
+    }
+    Ok(buffer.into_iter().map(|b| b == 1).collect())
+}
+
+fn read_bytes_u16<R: Read>(reader: &mut R, size: usize) -> io::Result<Vec<u16>> {
     let mut buffer = vec![0u16; size];
-    file.read_u16_into::<LittleEndian>(&mut buffer)?;
+    reader.read_u16_into::<LittleEndian>(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_bytes_u32<R: Read>(reader: &mut R, size: usize) -> io::Result<Vec<u32>> {
+    let mut buffer = vec![0u32; size];
+    reader.read_u32_into::<LittleEndian>(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_bytes_f32<R: Read>(reader: &mut R, size: usize) -> io::Result<Vec<f32>> {
+    let mut buffer = vec![0.0; size];
+    reader.read_f32_into::<LittleEndian>(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_bytes_u64<R: Read>(reader: &mut R, size: usize) -> io::Result<Vec<u64>> {
+    let mut buffer = vec![0u64; size];
+    reader.read_u64_into::<LittleEndian>(&mut buffer)?;
     Ok(buffer)
 }
 
-fn read_bytes_f32(file: &mut File, size: usize) -> io::Result<Vec<f32>> {
+fn read_bytes_f64<R: Read>(reader: &mut R, size: usize) -> io::Result<Vec<f64>> {
     let mut buffer = vec![0.0; size];
-    file.read_f32_into::<LittleEndian>(&mut buffer)?;
+    reader.read_f64_into::<LittleEndian>(&mut buffer)?;
     Ok(buffer)
 }
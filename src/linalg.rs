@@ -0,0 +1,40 @@
+//! Conversions between 1-D/2-D tensors and nalgebra's dynamic vectors and matrices.
+//!
+//! Safetensors stores matrices row-major while nalgebra stores them
+//! column-major, so every conversion reorders the elements.
+
+use crate::{Element, Error, Result, Tensor};
+use nalgebra::{DMatrix, DVector, Scalar};
+
+impl Tensor {
+    pub fn to_dmatrix<T: Element + Scalar>(&self) -> Result<DMatrix<T>> {
+        let &[rows, cols] = self.shape() else {
+            return Err(Error::RankMismatch {
+                expected: 2,
+                shape: self.shape().to_vec(),
+            });
+        };
+        Ok(DMatrix::from_row_slice(rows, cols, self.as_slice::<T>()?))
+    }
+
+    pub fn to_dvector<T: Element + Scalar>(&self) -> Result<DVector<T>> {
+        if self.shape().len() != 1 {
+            return Err(Error::RankMismatch {
+                expected: 1,
+                shape: self.shape().to_vec(),
+            });
+        }
+        Ok(DVector::from_column_slice(self.as_slice::<T>()?))
+    }
+
+    pub fn from_dmatrix<T: Element + Scalar>(matrix: &DMatrix<T>) -> Tensor {
+        // The column-major storage of the transpose is the row-major
+        // storage of the original.
+        let data = matrix.transpose().as_slice().to_vec();
+        T::into_tensor(data, vec![matrix.nrows(), matrix.ncols()])
+    }
+
+    pub fn from_dvector<T: Element + Scalar>(vector: &DVector<T>) -> Tensor {
+        T::into_tensor(vector.as_slice().to_vec(), vec![vector.len()])
+    }
+}
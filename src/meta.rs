@@ -0,0 +1,117 @@
+//! Placeholder tensors built from a header, without reading any data.
+
+use crate::header::Header;
+use crate::{Dtype, LazyReader, Reader, Result, Tensor};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+
+/// A tensor's dtype and shape with no data behind it. Nothing is allocated
+/// for the elements, and there is no way to reach them: use
+/// [`MetaTensor::zeros`] to get a real tensor of the same layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MetaTensor {
+    dtype: Dtype,
+    shape: Vec<usize>,
+}
+
+impl MetaTensor {
+    pub fn new(dtype: Dtype, shape: Vec<usize>) -> Self {
+        Self { dtype, shape }
+    }
+
+    pub fn dtype(&self) -> Dtype {
+        self.dtype
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn len(&self) -> usize {
+        self.shape.iter().product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Bytes the tensor's data would take in a safetensors file.
+    pub fn byte_len(&self) -> Result<u64> {
+        self.dtype.byte_len(&self.shape)
+    }
+
+    /// Allocates a zero-filled tensor of this dtype and shape.
+    pub fn zeros(&self) -> Result<Tensor> {
+        Tensor::zeros(self.dtype, self.shape.clone())
+    }
+}
+
+/// Reads only the header at `path` and describes every tensor in it.
+///
+/// The data section is never read, and a file cut short after its header is
+/// accepted. Entries are still checked for a byte span that matches their
+/// dtype and shape.
+pub fn read_meta(path: impl AsRef<Path>) -> Result<HashMap<String, MetaTensor>> {
+    let (_, header) = Header::read(&mut File::open(path)?)?;
+    header.validate(u64::MAX)?;
+    Ok(header
+        .tensors
+        .into_iter()
+        .map(|(name, info)| (name, MetaTensor::new(info.dtype(), info.shape().to_vec())))
+        .collect())
+}
+
+impl LazyReader {
+    /// Every tensor of the file as a [`MetaTensor`], without reading data.
+    pub fn to_meta(&self) -> HashMap<String, MetaTensor> {
+        self.names()
+            .into_iter()
+            .map(|name| {
+                let info = self.info(name).expect("listed names have entries");
+                (
+                    name.to_string(),
+                    MetaTensor::new(info.dtype(), info.shape().to_vec()),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Reader {
+    /// Builds a reader from the header at `path` alone, with every tensor
+    /// zero-filled at its stored dtype and shape. The data section is never
+    /// read, as for [`read_meta`], but each tensor is fully allocated; use
+    /// [`read_meta`] to allocate nothing.
+    ///
+    /// [`Reader::is_placeholder`] is true for the result so code handed it
+    /// can tell the zeros are not the file's contents.
+    pub fn from_file_meta(path: impl AsRef<Path>) -> Result<Self> {
+        let (_, header) = Header::read(&mut File::open(path)?)?;
+        header.validate(u64::MAX)?;
+        let tensors = header
+            .tensors
+            .iter()
+            .map(|(name, info)| {
+                Ok((
+                    name.clone(),
+                    Tensor::zeros(info.dtype(), info.shape().to_vec())?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Reader {
+            metadata: header.metadata,
+            tensors,
+            aliases: HashMap::new(),
+            raw_header: Some(header.raw),
+            placeholder: true,
+        })
+    }
+
+    /// Whether the tensors are placeholders from [`Reader::from_file_meta`]
+    /// rather than data read from a file.
+    pub fn is_placeholder(&self) -> bool {
+        self.placeholder
+    }
+}
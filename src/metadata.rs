@@ -0,0 +1,61 @@
+//! Typed access to the `__metadata__` map.
+
+use crate::{Error, LazyReader, Reader, Result};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+impl Reader {
+    /// Deserializes the `__metadata__` map into `T`. A file without metadata
+    /// deserializes as an empty map.
+    pub fn metadata_as<T: DeserializeOwned>(&self) -> Result<T> {
+        deserialize(&self.metadata, false)
+    }
+
+    /// Like [`Reader::metadata_as`], but string values holding a JSON object
+    /// or array are parsed first, so they can deserialize into nested
+    /// structs and collections.
+    pub fn metadata_as_nested<T: DeserializeOwned>(&self) -> Result<T> {
+        deserialize(&self.metadata, true)
+    }
+}
+
+impl LazyReader {
+    /// See [`Reader::metadata_as`].
+    pub fn metadata_as<T: DeserializeOwned>(&self) -> Result<T> {
+        deserialize(self.metadata(), false)
+    }
+
+    /// See [`Reader::metadata_as_nested`].
+    pub fn metadata_as_nested<T: DeserializeOwned>(&self) -> Result<T> {
+        deserialize(self.metadata(), true)
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(metadata: &Value, nested: bool) -> Result<T> {
+    let mut value = match metadata {
+        Value::Null => Value::Object(Default::default()),
+        value => value.clone(),
+    };
+    if let (true, Value::Object(map)) = (nested, &mut value) {
+        for entry in map.values_mut() {
+            if let Some(parsed) = entry.as_str().and_then(parse_embedded) {
+                *entry = parsed;
+            }
+        }
+    }
+
+    serde_path_to_error::deserialize(value).map_err(|err| Error::Metadata {
+        path: err.path().to_string(),
+        error: err.into_inner(),
+    })
+}
+
+/// Parses `text` if it holds a JSON object or array. Other JSON, such as a
+/// number, stays a string so it still fits `String` fields.
+fn parse_embedded(text: &str) -> Option<Value> {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+        return None;
+    }
+    serde_json::from_str(text).ok()
+}
@@ -0,0 +1,98 @@
+//! Norms of whole tensors and of the rows of matrices.
+
+use crate::convert::PAR_MIN_LEN;
+use crate::{Element, Error, Reader, Result, Tensor};
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Which norm to compute. All norms accumulate in f64 and propagate NaN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NormKind {
+    /// Sum of absolute values.
+    L1,
+    /// Square root of the sum of squares.
+    L2,
+    /// Largest absolute value.
+    MaxAbs,
+}
+
+impl NormKind {
+    /// The contribution of a single element.
+    fn term(self, x: f64) -> f64 {
+        match self {
+            NormKind::L2 => x * x,
+            NormKind::L1 | NormKind::MaxAbs => x.abs(),
+        }
+    }
+
+    fn combine(self, a: f64, b: f64) -> f64 {
+        match self {
+            NormKind::L1 | NormKind::L2 => a + b,
+            NormKind::MaxAbs if a.is_nan() || b.is_nan() => f64::NAN,
+            NormKind::MaxAbs => a.max(b),
+        }
+    }
+
+    fn finish(self, acc: f64) -> f64 {
+        match self {
+            NormKind::L2 => acc.sqrt(),
+            NormKind::L1 | NormKind::MaxAbs => acc,
+        }
+    }
+
+    fn of<T: Element>(self, values: &[T]) -> f64 {
+        self.finish(
+            values
+                .iter()
+                .fold(0.0, |acc, &x| self.combine(acc, self.term(x.to_f64()))),
+        )
+    }
+}
+
+impl Tensor {
+    /// The norm of all elements taken as one vector. An empty tensor has norm
+    /// 0.
+    pub fn norm(&self, kind: NormKind) -> f64 {
+        let acc = self
+            .par_iter_f64()
+            .with_min_len(PAR_MIN_LEN)
+            .map(|x| kind.term(x))
+            .reduce(|| 0.0, |a, b| kind.combine(a, b));
+        kind.finish(acc)
+    }
+
+    /// The norm of each row of a 2-D tensor.
+    pub fn row_norms(&self, kind: NormKind) -> Result<Vec<f64>> {
+        let &[_, cols] = self.shape() else {
+            return Err(Error::RankMismatch {
+                expected: 2,
+                shape: self.shape().to_vec(),
+            });
+        };
+        Ok(with_data!(self, _shape, data => {
+            if cols == 0 {
+                vec![0.0; self.shape()[0]]
+            } else {
+                data.par_chunks(cols)
+                    .with_min_len(PAR_MIN_LEN.div_ceil(cols))
+                    .map(|row| kind.of(row))
+                    .collect()
+            }
+        }))
+    }
+}
+
+impl Reader {
+    /// The norm of every tensor, keyed by name, including aliases.
+    pub fn norms(&self, kind: NormKind) -> HashMap<String, f64> {
+        let mut norms: HashMap<String, f64> = self
+            .tensors
+            .par_iter()
+            .map(|(name, tensor)| (name.clone(), tensor.norm(kind)))
+            .collect();
+        for (alias, target) in &self.aliases {
+            norms.insert(alias.clone(), norms[target]);
+        }
+        norms
+    }
+}
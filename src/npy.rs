@@ -0,0 +1,400 @@
+//! Conversion to and from NumPy's `.npy` format and, with the `npz` feature,
+//! `.npz` archives.
+
+use crate::{read_data, Dtype, Error, Result, Tensor};
+use half::bf16;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+/// The `descr` NumPy uses for each dtype. Single-byte types have no byte order.
+const DESCRS: &[(Dtype, &str)] = &[
+    (Dtype::Bool, "|b1"),
+    (Dtype::U8, "|u1"),
+    (Dtype::I8, "|i1"),
+    (Dtype::U16, "<u2"),
+    (Dtype::I16, "<i2"),
+    (Dtype::F16, "<f2"),
+    (Dtype::U32, "<u4"),
+    (Dtype::I32, "<i4"),
+    (Dtype::F32, "<f4"),
+    (Dtype::U64, "<u8"),
+    (Dtype::I64, "<i8"),
+    (Dtype::F64, "<f8"),
+];
+
+/// NumPy's own writer pads the preamble and header to this alignment.
+const HEADER_ALIGN: usize = 64;
+
+/// bf16 elements converted per write when upcasting.
+const UPCAST_CHUNK: usize = 1 << 16;
+
+/// What to do with BF16 tensors, which have no NumPy dtype.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Bf16Policy {
+    /// Fail with [`Error::UnsupportedDtype`].
+    #[default]
+    Error,
+    /// Write the values as `<f4`. Every bf16 value is exactly representable
+    /// as f32, but the file is twice the size.
+    UpcastF32,
+}
+
+impl Tensor {
+    pub fn write_npy(&self, path: impl AsRef<Path>, bf16: Bf16Policy) -> Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        self.write_npy_to(&mut out, bf16)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Writes the tensor in `.npy` format to `out`.
+    ///
+    /// Format version 1.0 is used unless the header is too long for its
+    /// 16-bit length field, in which case version 2.0 is used.
+    pub fn write_npy_to<W: Write>(&self, mut out: W, bf16: Bf16Policy) -> Result<()> {
+        let dtype = match (self.dtype(), bf16) {
+            (Dtype::Bf16, Bf16Policy::UpcastF32) => Dtype::F32,
+            (Dtype::Bf16, Bf16Policy::Error) => {
+                return Err(Error::UnsupportedDtype(
+                    "BF16 (NumPy has no bfloat16 dtype)".to_string(),
+                ))
+            }
+            (dtype, _) => dtype,
+        };
+        let (_, descr) = DESCRS.iter().find(|(d, _)| *d == dtype).unwrap();
+        out.write_all(&npy_header(descr, self.shape()))?;
+
+        match self {
+            Tensor::Bf16 { data, .. } => write_upcast(&mut out, data)?,
+            _ => out.write_all(self.as_bytes())?,
+        }
+
+        Ok(())
+    }
+
+    pub fn from_npy(path: impl AsRef<Path>) -> Result<Tensor> {
+        Tensor::read_npy_from(BufReader::new(File::open(path)?))
+    }
+
+    pub fn from_npy_bytes(bytes: &[u8]) -> Result<Tensor> {
+        Tensor::read_npy_from(bytes)
+    }
+
+    /// Reads a `.npy` stream of format version 1.0, 2.0 or 3.0.
+    ///
+    /// Only little-endian (or byte-order-free) numeric and bool descrs in C
+    /// order are supported.
+    pub fn read_npy_from<R: Read>(mut reader: R) -> Result<Tensor> {
+        let mut preamble = [0u8; 8];
+        reader.read_exact(&mut preamble)?;
+        if &preamble[..6] != MAGIC {
+            return Err(Error::InvalidNpy("missing magic string".to_string()));
+        }
+
+        let header_len = match preamble[6] {
+            1 => {
+                let mut len = [0u8; 2];
+                reader.read_exact(&mut len)?;
+                u16::from_le_bytes(len) as usize
+            }
+            2 | 3 => {
+                let mut len = [0u8; 4];
+                reader.read_exact(&mut len)?;
+                u32::from_le_bytes(len) as usize
+            }
+            major => {
+                return Err(Error::InvalidNpy(format!(
+                    "unsupported format version {major}.{}",
+                    preamble[7]
+                )))
+            }
+        };
+
+        let mut header = vec![0u8; header_len];
+        reader.read_exact(&mut header)?;
+        let header = std::str::from_utf8(&header)
+            .map_err(|_| Error::InvalidNpy("header is not valid UTF-8".to_string()))?;
+        let header = NpyHeader::parse(header)?;
+
+        if header.fortran_order {
+            return Err(Error::InvalidNpy(
+                "fortran_order=True arrays are not supported".to_string(),
+            ));
+        }
+        let dtype = parse_descr(&header.descr)?;
+        dtype.byte_len(&header.shape)?;
+
+        Ok(read_data(&mut reader, dtype, header.shape)?)
+    }
+}
+
+fn parse_descr(descr: &str) -> Result<Dtype> {
+    if descr.starts_with('>') {
+        return Err(Error::InvalidNpy(format!(
+            "big-endian descr '{descr}' is not supported"
+        )));
+    }
+
+    // Accept `<` on single-byte types too, since it is equivalent to `|`.
+    let code = descr.trim_start_matches(['<', '|']);
+    DESCRS
+        .iter()
+        .find(|(_, d)| d[1..] == *code)
+        .map(|&(dtype, _)| dtype)
+        .ok_or_else(|| Error::InvalidNpy(format!("unsupported descr '{descr}'")))
+}
+
+/// The fields of an `.npy` header dict.
+#[derive(Debug)]
+struct NpyHeader {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+/// A Python literal appearing as a header dict value.
+enum Literal {
+    Str(String),
+    Bool(bool),
+    Tuple(Vec<usize>),
+}
+
+impl NpyHeader {
+    fn parse(header: &str) -> Result<Self> {
+        let mut parser = Parser {
+            input: header.trim_end(),
+            pos: 0,
+        };
+        let (mut descr, mut fortran_order, mut shape) = (None, None, None);
+
+        parser.expect('{')?;
+        while !parser.eat('}') {
+            let key = parser.string()?;
+            parser.expect(':')?;
+            match (key.as_str(), parser.literal()?) {
+                ("descr", Literal::Str(value)) => descr = Some(value),
+                ("fortran_order", Literal::Bool(value)) => fortran_order = Some(value),
+                ("shape", Literal::Tuple(value)) => shape = Some(value),
+                (key, _) => {
+                    return Err(Error::InvalidNpy(format!(
+                        "unexpected header key or value for '{key}'"
+                    )))
+                }
+            }
+            parser.eat(',');
+        }
+
+        let missing = |key: &str| Error::InvalidNpy(format!("header is missing '{key}'"));
+        Ok(Self {
+            descr: descr.ok_or_else(|| missing("descr"))?,
+            fortran_order: fortran_order.ok_or_else(|| missing("fortran_order"))?,
+            shape: shape.ok_or_else(|| missing("shape"))?,
+        })
+    }
+}
+
+/// Parser for the subset of Python literal syntax used by `.npy` headers.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn rest(&self) -> &str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.pos = self.input.len() - self.rest().trim_start().len();
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        let found = self.rest().starts_with(c);
+        if found {
+            self.pos += c.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if !self.eat(c) {
+            return Err(self.error(&format!("'{c}'")));
+        }
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        let quote = match self.rest().chars().next() {
+            Some(q @ ('\'' | '"')) => q,
+            _ => return Err(self.error("a string")),
+        };
+        let body = &self.rest()[1..];
+        let end = body
+            .find(quote)
+            .ok_or_else(|| self.error("a closing quote"))?;
+        let value = body[..end].to_string();
+        self.pos += end + 2;
+        Ok(value)
+    }
+
+    fn literal(&mut self) -> Result<Literal> {
+        self.skip_whitespace();
+        for (word, value) in [("True", true), ("False", false)] {
+            if self.rest().starts_with(word) {
+                self.pos += word.len();
+                return Ok(Literal::Bool(value));
+            }
+        }
+        if !self.eat('(') {
+            return self.string().map(Literal::Str);
+        }
+
+        let mut dims = Vec::new();
+        while !self.eat(')') {
+            self.skip_whitespace();
+            let digits = self.rest().len()
+                - self
+                    .rest()
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+                    .len();
+            let dim = self.rest()[..digits]
+                .parse()
+                .map_err(|_| self.error("a dimension"))?;
+            self.pos += digits;
+            dims.push(dim);
+            self.eat(',');
+        }
+        Ok(Literal::Tuple(dims))
+    }
+
+    fn error(&self, expected: &str) -> Error {
+        Error::InvalidNpy(format!(
+            "expected {expected} at byte {} of header `{}`",
+            self.pos, self.input
+        ))
+    }
+}
+
+/// Builds the magic string, version, header length and padded header dict.
+fn npy_header(descr: &str, shape: &[usize]) -> Vec<u8> {
+    let shape = match shape {
+        [dim] => format!("({dim},)"),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(usize::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut dict =
+        format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}").into_bytes();
+
+    // The dict is padded with spaces and terminated by a newline so the data
+    // starts aligned. Version 1.0 stores the padded length in a u16.
+    let padded_len = |len_bytes: usize| {
+        let preamble = MAGIC.len() + 2 + len_bytes;
+        (preamble + dict.len() + 1).next_multiple_of(HEADER_ALIGN) - preamble
+    };
+    let (version, padded) = match padded_len(2) {
+        len if len <= u16::MAX as usize => (1, len),
+        _ => (2, padded_len(4)),
+    };
+    dict.resize(padded - 1, b' ');
+    dict.push(b'\n');
+
+    let mut header = Vec::with_capacity(MAGIC.len() + 6 + dict.len());
+    header.extend_from_slice(MAGIC);
+    header.extend_from_slice(&[version, 0]);
+    if version == 1 {
+        header.extend_from_slice(&(dict.len() as u16).to_le_bytes());
+    } else {
+        header.extend_from_slice(&(dict.len() as u32).to_le_bytes());
+    }
+    header.extend_from_slice(&dict);
+    header
+}
+
+fn write_upcast<W: Write>(out: &mut W, data: &[bf16]) -> Result<()> {
+    let mut buffer = Vec::with_capacity(data.len().min(UPCAST_CHUNK));
+    for chunk in data.chunks(UPCAST_CHUNK) {
+        buffer.clear();
+        buffer.extend(chunk.iter().map(|x| x.to_f32()));
+        out.write_all(bytemuck::cast_slice(&buffer))?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "npz")]
+mod npz {
+    use super::Bf16Policy;
+    use crate::{Error, Reader, Result, Tensor};
+    use std::collections::{HashMap, HashSet};
+    use std::fs::File;
+    use std::io::{BufReader, BufWriter};
+    use std::path::Path;
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+    impl Reader {
+        /// Loads every `.npy` member of a `.npz` archive, stored or deflated,
+        /// keyed by the member name without its `.npy` suffix.
+        pub fn from_npz(path: impl AsRef<Path>) -> Result<Self> {
+            let mut zip = ZipArchive::new(BufReader::new(File::open(path)?))?;
+            let mut tensors = HashMap::new();
+
+            for i in 0..zip.len() {
+                let member = zip.by_index(i)?;
+                let Some(name) = member.name()?.strip_suffix(".npy").map(str::to_string) else {
+                    continue;
+                };
+                tensors.insert(name, Tensor::read_npy_from(member)?);
+            }
+
+            Ok(Reader {
+                metadata: serde_json::Value::Null,
+                tensors,
+                aliases: HashMap::new(),
+                raw_header: None,
+                placeholder: false,
+            })
+        }
+
+        /// Writes every tensor as an uncompressed `<name>.npy` member of a
+        /// `.npz` archive, in the layout produced by `numpy.savez`.
+        ///
+        /// Path separators in tensor names are replaced with `_` so member
+        /// names stay flat; names that collide after this are an error.
+        /// Aliases are written as copies of the tensor they alias.
+        pub fn write_npz(&self, path: impl AsRef<Path>, bf16: Bf16Policy) -> Result<()> {
+            let mut zip = ZipWriter::new(BufWriter::new(File::create(path)?));
+            let options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Stored)
+                .large_file(true);
+
+            let mut names: Vec<_> = self.tensors.keys().chain(self.aliases.keys()).collect();
+            names.sort();
+
+            let mut members = HashSet::new();
+            for name in names {
+                let member = format!("{}.npy", name.replace(['/', '\\'], "_"));
+                if !members.insert(member.clone()) {
+                    return Err(Error::DuplicateTensor(member));
+                }
+
+                zip.start_file(member, options)?;
+                let tensor = self.tensor(name).expect("listed names exist");
+                tensor.write_npy_to(&mut zip, bf16)?;
+            }
+
+            zip.finish()?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Shape manipulation producing new contiguous tensors.
+
+use crate::index::strides;
+use crate::{Element, Error, Result, Tensor};
+
+/// Side length of the tiles used by the 2-D transpose.
+const TRANSPOSE_BLOCK: usize = 32;
+
+impl Tensor {
+    /// Copies `len` entries starting at `start` along dimension `dim`.
+    pub fn narrow(&self, dim: usize, start: usize, len: usize) -> Result<Tensor> {
+        let shape = self.shape();
+        check_dim(shape, dim)?;
+        if start.checked_add(len).is_none_or(|end| end > shape[dim]) {
+            return Err(Error::RangeOutOfBounds {
+                dim,
+                start,
+                len,
+                shape: shape.to_vec(),
+            });
+        }
+
+        let mut new_shape = shape.to_vec();
+        new_shape[dim] = len;
+        let (_, inner) = outer_inner(shape, dim);
+        let (dim_len, start, len) = (shape[dim] * inner, start * inner, len * inner);
+
+        Ok(with_data!(self, _shape, data => {
+            let narrowed = data
+                .chunks(dim_len.max(1))
+                .flat_map(|block| &block[start..start + len])
+                .copied()
+                .collect();
+            Element::into_tensor(narrowed, new_shape)
+        }))
+    }
+
+    /// Splits the tensor along `dim` into consecutive pieces of the given
+    /// sizes, which must add up to the length of `dim`.
+    pub fn split(&self, dim: usize, sizes: &[usize]) -> Result<Vec<Tensor>> {
+        let shape = self.shape();
+        check_dim(shape, dim)?;
+        if sizes
+            .iter()
+            .try_fold(0usize, |acc, &len| acc.checked_add(len))
+            != Some(shape[dim])
+        {
+            return Err(Error::SplitSizes {
+                dim,
+                sizes: sizes.to_vec(),
+                shape: shape.to_vec(),
+            });
+        }
+
+        let mut start = 0;
+        sizes
+            .iter()
+            .map(|&len| {
+                let piece = self.narrow(dim, start, len);
+                start += len;
+                piece
+            })
+            .collect()
+    }
+
+    /// Splits the tensor along `dim` into `n` pieces of equal size.
+    pub fn chunks(&self, dim: usize, n: usize) -> Result<Vec<Tensor>> {
+        let shape = self.shape();
+        check_dim(shape, dim)?;
+        if n == 0 || !shape[dim].is_multiple_of(n) {
+            return Err(Error::UnevenChunks {
+                dim,
+                chunks: n,
+                shape: shape.to_vec(),
+            });
+        }
+        self.split(dim, &vec![shape[dim] / n; n])
+    }
+
+    /// Joins tensors along `dim`. All inputs must share a dtype and agree in
+    /// every dimension other than `dim`.
+    pub fn concat(tensors: &[&Tensor], dim: usize) -> Result<Tensor> {
+        let first = tensors.first().ok_or(Error::EmptyConcat)?;
+        let shape = first.shape();
+        check_dim(shape, dim)?;
+
+        let mut new_shape = shape.to_vec();
+        new_shape[dim] = 0;
+        for (input, tensor) in tensors.iter().enumerate() {
+            if tensor.dtype() != first.dtype() {
+                return Err(Error::ConcatDtype {
+                    input,
+                    expected: first.dtype(),
+                    actual: tensor.dtype(),
+                });
+            }
+            let other = tensor.shape();
+            let mismatch = if other.len() != shape.len() {
+                Some(None)
+            } else {
+                (0..shape.len())
+                    .find(|&d| d != dim && other[d] != shape[d])
+                    .map(Some)
+            };
+            if let Some(mismatch) = mismatch {
+                return Err(Error::ConcatShape {
+                    input,
+                    dim: mismatch,
+                    expected: shape.to_vec(),
+                    actual: other.to_vec(),
+                });
+            }
+            new_shape[dim] += other[dim];
+        }
+
+        let (outer, inner) = outer_inner(shape, dim);
+        Ok(with_data!(*first, _shape, data => {
+            let parts: Vec<_> = std::iter::once(data.as_slice())
+                .chain(tensors[1..].iter().map(|tensor| {
+                    Element::slice(tensor).expect("dtypes checked above")
+                }))
+                .zip(tensors.iter().map(|tensor| tensor.shape()[dim] * inner))
+                .collect();
+
+            // Each outer index takes one block from every input in turn.
+            let mut joined = Vec::with_capacity(new_shape.iter().product());
+            for o in 0..outer {
+                for &(data, block) in &parts {
+                    joined.extend_from_slice(&data[o * block..(o + 1) * block]);
+                }
+            }
+            Element::into_tensor(joined, new_shape)
+        }))
+    }
+
+    /// Returns a copy with a new shape of the same element count. One entry
+    /// of `new_shape` may be `-1`, in which case it is inferred.
+    pub fn reshape(&self, new_shape: &[isize]) -> Result<Tensor> {
+        let shape = self.resolve_shape(new_shape)?;
+        Ok(self.clone().with_shape(shape))
+    }
+
+    /// Like [`Tensor::reshape`], but reuses this tensor's data without copying.
+    pub fn into_reshaped(self, new_shape: &[isize]) -> Result<Tensor> {
+        let shape = self.resolve_shape(new_shape)?;
+        Ok(self.with_shape(shape))
+    }
+
+    /// Removes dimension `dim`, which must have size 1, or every size-1
+    /// dimension when `dim` is `None`. Squeezing a rank-0 tensor, or one whose
+    /// dimensions are all 1, with `None` gives a rank-0 tensor.
+    pub fn squeeze(&self, dim: Option<usize>) -> Result<Tensor> {
+        let shape = self.squeezed_shape(dim)?;
+        Ok(self.clone().with_shape(shape))
+    }
+
+    /// Like [`Tensor::squeeze`], but reuses this tensor's data without copying.
+    pub fn squeeze_into(self, dim: Option<usize>) -> Result<Tensor> {
+        let shape = self.squeezed_shape(dim)?;
+        Ok(self.with_shape(shape))
+    }
+
+    /// Inserts a size-1 dimension at position `dim`, which may equal the
+    /// rank to append one.
+    pub fn unsqueeze(&self, dim: usize) -> Result<Tensor> {
+        let shape = self.unsqueezed_shape(dim)?;
+        Ok(self.clone().with_shape(shape))
+    }
+
+    /// Like [`Tensor::unsqueeze`], but reuses this tensor's data without copying.
+    pub fn unsqueeze_into(self, dim: usize) -> Result<Tensor> {
+        let shape = self.unsqueezed_shape(dim)?;
+        Ok(self.with_shape(shape))
+    }
+
+    fn squeezed_shape(&self, dim: Option<usize>) -> Result<Vec<usize>> {
+        let shape = self.shape();
+        match dim {
+            None => Ok(shape.iter().copied().filter(|&len| len != 1).collect()),
+            Some(dim) => {
+                check_dim(shape, dim)?;
+                if shape[dim] != 1 {
+                    return Err(Error::NotSqueezable {
+                        dim,
+                        shape: shape.to_vec(),
+                    });
+                }
+                let mut shape = shape.to_vec();
+                shape.remove(dim);
+                Ok(shape)
+            }
+        }
+    }
+
+    fn unsqueezed_shape(&self, dim: usize) -> Result<Vec<usize>> {
+        let mut shape = self.shape().to_vec();
+        if dim > shape.len() {
+            return Err(Error::DimOutOfRange { dim, shape });
+        }
+        shape.insert(dim, 1);
+        Ok(shape)
+    }
+
+    /// Replaces the shape, which must hold the same number of elements.
+    fn with_shape(mut self, shape: Vec<usize>) -> Tensor {
+        with_data!(&mut self, old, _data => *old = shape);
+        self
+    }
+
+    /// Swaps dimensions `d0` and `d1`, physically rearranging the data.
+    pub fn transpose(&self, d0: usize, d1: usize) -> Result<Tensor> {
+        let shape = self.shape();
+        check_dim(shape, d0)?;
+        check_dim(shape, d1)?;
+
+        let mut order: Vec<usize> = (0..shape.len()).collect();
+        order.swap(d0, d1);
+        self.permute(&order)
+    }
+
+    /// Reorders the dimensions so that dimension `i` of the result is
+    /// dimension `order[i]` of this tensor, physically rearranging the data.
+    pub fn permute(&self, order: &[usize]) -> Result<Tensor> {
+        let shape = self.shape();
+        let mut seen = vec![false; shape.len()];
+        let is_permutation = order.len() == shape.len()
+            && order
+                .iter()
+                .all(|&axis| axis < shape.len() && !std::mem::replace(&mut seen[axis], true));
+        if !is_permutation {
+            return Err(Error::InvalidPermutation {
+                order: order.to_vec(),
+                shape: shape.to_vec(),
+            });
+        }
+
+        let new_shape: Vec<usize> = order.iter().map(|&axis| shape[axis]).collect();
+        Ok(with_data!(self, _shape, data => {
+            let permuted = match (shape, order) {
+                (&[rows, cols], [1, 0]) => transpose_2d(data, rows, cols),
+                _ => permute_data(data, shape, order),
+            };
+            Element::into_tensor(permuted, new_shape)
+        }))
+    }
+
+    fn resolve_shape(&self, new_shape: &[isize]) -> Result<Vec<usize>> {
+        let error = || Error::Reshape {
+            shape: self.shape().to_vec(),
+            new_shape: new_shape.to_vec(),
+        };
+
+        let mut inferred = None;
+        let mut known = 1usize;
+        for (i, &dim) in new_shape.iter().enumerate() {
+            match dim {
+                -1 if inferred.is_none() => inferred = Some(i),
+                0.. => known = known.checked_mul(dim as usize).ok_or_else(error)?,
+                _ => return Err(error()),
+            }
+        }
+
+        let count = self.numel();
+        let mut shape: Vec<usize> = new_shape.iter().map(|&dim| dim.max(0) as usize).collect();
+        match inferred {
+            Some(i) if known != 0 && count.is_multiple_of(known) => shape[i] = count / known,
+            None if known == count => {}
+            _ => return Err(error()),
+        }
+        Ok(shape)
+    }
+}
+
+pub(crate) fn check_dim(shape: &[usize], dim: usize) -> Result<()> {
+    if dim >= shape.len() {
+        return Err(Error::DimOutOfRange {
+            dim,
+            shape: shape.to_vec(),
+        });
+    }
+    Ok(())
+}
+
+/// Element counts of the dimensions before and after `dim`, so the tensor can
+/// be viewed as `[outer, shape[dim], inner]`.
+pub(crate) fn outer_inner(shape: &[usize], dim: usize) -> (usize, usize) {
+    (
+        shape[..dim].iter().product(),
+        shape[dim + 1..].iter().product(),
+    )
+}
+
+/// Gathers `data` in the order of the permuted shape by walking the output
+/// indices like an odometer while tracking the matching source offset.
+fn permute_data<T: Copy>(data: &[T], shape: &[usize], order: &[usize]) -> Vec<T> {
+    let source_strides = strides(shape);
+    let new_shape: Vec<usize> = order.iter().map(|&axis| shape[axis]).collect();
+    let strides: Vec<usize> = order.iter().map(|&axis| source_strides[axis]).collect();
+
+    let mut out = Vec::with_capacity(data.len());
+    let mut index = vec![0; shape.len()];
+    let mut offset = 0;
+    for _ in 0..data.len() {
+        out.push(data[offset]);
+        for axis in (0..index.len()).rev() {
+            index[axis] += 1;
+            offset += strides[axis];
+            if index[axis] < new_shape[axis] {
+                break;
+            }
+            offset -= strides[axis] * new_shape[axis];
+            index[axis] = 0;
+        }
+    }
+    out
+}
+
+/// Transposes a row-major `rows x cols` matrix tile by tile, so both the
+/// reads and the writes of each tile stay within a few cache lines.
+fn transpose_2d<T: Copy>(data: &[T], rows: usize, cols: usize) -> Vec<T> {
+    let Some(&first) = data.first() else {
+        return Vec::new();
+    };
+
+    let mut out = vec![first; data.len()];
+    for row_block in (0..rows).step_by(TRANSPOSE_BLOCK) {
+        for col_block in (0..cols).step_by(TRANSPOSE_BLOCK) {
+            for row in row_block..(row_block + TRANSPOSE_BLOCK).min(rows) {
+                for col in col_block..(col_block + TRANSPOSE_BLOCK).min(cols) {
+                    out[col * rows + row] = data[row * cols + col];
+                }
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,144 @@
+//! Options controlling how files are read.
+
+use crate::header::Header;
+use crate::{Error, Result};
+use std::fmt;
+use std::sync::Arc;
+
+/// What to do with per-tensor header fields other than `dtype`, `shape` and
+/// `data_offsets`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownFields {
+    /// Keep them, available through [`TensorInfo::extra`](crate::TensorInfo::extra).
+    #[default]
+    Carry,
+    /// Keep them and, with the `tracing` feature, emit a `warn` event for
+    /// each. Without the feature this is the same as `Carry`.
+    Warn,
+    /// Fail with [`Error::UnknownField`](crate::Error::UnknownField).
+    Error,
+}
+
+/// The order in which [`Reader::from_file`](crate::Reader::from_file) starts
+/// reading tensors on its worker threads.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Schedule {
+    /// Largest tensors first, so the slowest reads start immediately and the
+    /// small ones fill the gaps between them.
+    #[default]
+    LargestFirst,
+    /// Position in the file, which keeps reads close to sequential for media
+    /// that seek slowly, such as spinning disks.
+    OffsetOrder,
+}
+
+/// What to do with a tensor selected for transposition that is not 2-D.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonMatrix {
+    /// Fail with [`Error::RankMismatch`](crate::Error::RankMismatch).
+    #[default]
+    Error,
+    /// Load it unchanged.
+    Skip,
+}
+
+/// A condition on a tensor's name and shape, as used by
+/// [`ReaderOptions::transpose_2d`].
+#[derive(Clone)]
+pub struct TensorPredicate(Arc<PredicateFn>);
+
+type PredicateFn = dyn Fn(&str, &[usize]) -> bool + Send + Sync;
+
+impl TensorPredicate {
+    pub fn new(predicate: impl Fn(&str, &[usize]) -> bool + Send + Sync + 'static) -> Self {
+        Self(Arc::new(predicate))
+    }
+
+    pub fn matches(&self, name: &str, shape: &[usize]) -> bool {
+        (self.0)(name, shape)
+    }
+}
+
+impl fmt::Debug for TensorPredicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TensorPredicate(..)")
+    }
+}
+
+/// Settings shared by [`Reader`](crate::Reader) and
+/// [`LazyReader`](crate::LazyReader). The defaults match `from_file` and
+/// `open`.
+#[derive(Clone, Debug, Default)]
+pub struct ReaderOptions {
+    pub unknown_fields: UnknownFields,
+    /// Only used when loading every tensor of a file at once.
+    pub schedule: Schedule,
+    /// 2-D tensors to load transposed into column-major order, with their
+    /// shape swapped. Only used by [`Reader`](crate::Reader).
+    pub transpose_2d: Option<TensorPredicate>,
+    pub transpose_non_2d: NonMatrix,
+    /// Accept entries that declare exactly the same byte range, dtype and
+    /// shape, reading their data once, and reject any other overlap. The
+    /// data is kept once too, with the alias listed in
+    /// [`Reader::aliases`](crate::Reader::aliases). An alias that
+    /// [`ReaderOptions::transpose_2d`] treats differently from the entry it
+    /// aliases holds different values, so it is read on its own.
+    /// Only used by [`Reader`](crate::Reader).
+    pub allow_aliases: bool,
+}
+
+impl ReaderOptions {
+    pub fn schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    pub fn allow_aliases(mut self, allow: bool) -> Self {
+        self.allow_aliases = allow;
+        self
+    }
+
+    /// Transposes the 2-D tensors for which `predicate` returns true while
+    /// decoding them, so no second buffer of the tensor's size is needed.
+    pub fn transpose_2d(
+        mut self,
+        predicate: impl Fn(&str, &[usize]) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.transpose_2d = Some(TensorPredicate::new(predicate));
+        self
+    }
+
+    /// The `(alias, target)` pairs of `header` whose data is read once, as
+    /// described on [`ReaderOptions::allow_aliases`]. Empty unless aliases
+    /// are allowed.
+    pub(crate) fn aliases<'a>(&self, header: &'a Header) -> Result<Vec<(&'a str, &'a str)>> {
+        if !self.allow_aliases {
+            return Ok(Vec::new());
+        }
+        let mut aliases = Vec::new();
+        for (alias, target) in header.aliases()? {
+            let shape = header.tensors[alias].shape();
+            if self.transposes(alias, shape)? == self.transposes(target, shape)? {
+                aliases.push((alias, target));
+            }
+        }
+        Ok(aliases)
+    }
+
+    /// Whether tensor `name` of `shape` is to be loaded transposed.
+    pub(crate) fn transposes(&self, name: &str, shape: &[usize]) -> Result<bool> {
+        let selected = self
+            .transpose_2d
+            .as_ref()
+            .is_some_and(|predicate| predicate.matches(name, shape));
+        match (selected, shape.len(), self.transpose_non_2d) {
+            (false, _, _) => Ok(false),
+            (true, 2, _) => Ok(true),
+            (true, _, NonMatrix::Skip) => Ok(false),
+            (true, _, NonMatrix::Error) => Err(Error::RankMismatch {
+                expected: 2,
+                shape: shape.to_vec(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Grayscale image dumps of 2-D tensors for quick visual inspection.
+
+use crate::{Element, Error, Result, Tensor};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Pixel value used for NaN. Every other value maps into `1..=255`, so NaNs
+/// are always distinguishable.
+pub const NAN_PIXEL: u8 = 0;
+
+/// How tensor values are mapped onto gray levels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Normalize {
+    /// The smallest finite value is darkest and the largest brightest.
+    MinMax,
+    /// Zero is mid-gray and the largest finite magnitude, of either sign,
+    /// sets the scale. Useful for weights and differences.
+    Symmetric,
+}
+
+impl Tensor {
+    /// Writes a 2-D tensor as a binary (P5) PGM image, one pixel per element
+    /// with rows top to bottom.
+    ///
+    /// Infinities are clamped to the ends of the range. If all finite values
+    /// are equal the image is uniformly mid-gray.
+    pub fn write_pgm(&self, path: impl AsRef<Path>, normalize: Normalize) -> Result<()> {
+        let (width, height, pixels) = self.to_gray(normalize)?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        write!(out, "P5\n{width} {height}\n255\n")?;
+        out.write_all(&pixels)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Writes a 2-D tensor as an 8-bit grayscale PNG, mapped like
+    /// [`Tensor::write_pgm`].
+    #[cfg(feature = "image")]
+    pub fn write_png(&self, path: impl AsRef<Path>, normalize: Normalize) -> Result<()> {
+        let (width, height, pixels) = self.to_gray(normalize)?;
+        let image = image::GrayImage::from_raw(width as u32, height as u32, pixels)
+            .expect("one pixel per element");
+        Ok(image.save(path)?)
+    }
+
+    fn to_gray(&self, normalize: Normalize) -> Result<(usize, usize, Vec<u8>)> {
+        let &[height, width] = self.shape() else {
+            return Err(Error::RankMismatch {
+                expected: 2,
+                shape: self.shape().to_vec(),
+            });
+        };
+        let pixels = with_data!(self, _shape, data => gray(data, normalize));
+        Ok((width, height, pixels))
+    }
+}
+
+fn gray<T: Element>(data: &[T], normalize: Normalize) -> Vec<u8> {
+    let finite = data.iter().map(|&x| x.to_f64()).filter(|x| x.is_finite());
+    let (low, high) = match normalize {
+        Normalize::MinMax => finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| {
+            (lo.min(x), hi.max(x))
+        }),
+        Normalize::Symmetric => {
+            let max = finite.fold(0.0f64, |max, x| max.max(x.abs()));
+            (-max, max)
+        }
+    };
+    let range = high - low;
+
+    data.iter()
+        .map(|&x| {
+            let x = x.to_f64();
+            if x.is_nan() {
+                NAN_PIXEL
+            } else if range <= 0.0 {
+                128
+            } else {
+                let t = ((x - low) / range).clamp(0.0, 1.0);
+                1 + (t * 254.0).round() as u8
+            }
+        })
+        .collect()
+}
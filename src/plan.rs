@@ -0,0 +1,139 @@
+//! Describing what a load will do before doing it.
+
+use crate::header::Header;
+use crate::{transpose_block_rows, Dtype, ReaderOptions, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// One tensor of a [`LoadPlan`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PlannedTensor {
+    pub name: String,
+    pub dtype: Dtype,
+    /// The shape after loading, which is swapped if `transposed`.
+    pub shape: Vec<usize>,
+    pub bytes: u64,
+    pub transposed: bool,
+    /// The tensor whose data this one shares when aliases are allowed. Its
+    /// bytes are not read again.
+    pub alias_of: Option<String>,
+}
+
+/// What [`Reader::from_file_with`](crate::Reader::from_file_with) will do
+/// for a file, computed from its header alone. The file is planned as a
+/// whole, with the safetensors payload at offset zero.
+///
+/// Every tensor is read and decoded into memory at its stored dtype, and
+/// transposed if [`ReaderOptions::transpose_2d`] selects it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LoadPlan {
+    pub path: PathBuf,
+    /// Tensors in the order their reads are started.
+    pub tensors: Vec<PlannedTensor>,
+    /// Bytes read from the file: the length prefix, the header and every
+    /// tensor that is not an alias.
+    pub bytes_read: u64,
+    /// Read requests issued: one for the length prefix, one for the header,
+    /// one per tensor that is not an alias and one per block of rows of a
+    /// transposed tensor.
+    pub read_ops: usize,
+    /// Memory held once loading finishes: the decoded tensors, with aliases
+    /// sharing the data of their target, and the retained raw header. This
+    /// is not the
+    /// peak: while a transposed tensor is read, a block of its stored rows
+    /// is held as well, about 64 KiB or a single row if that is longer.
+    pub resident_bytes: u64,
+}
+
+impl ReaderOptions {
+    /// Reads only the header at `path` and describes the load these options
+    /// would perform. Fails wherever the load itself would fail before
+    /// reading tensor data.
+    pub fn plan(&self, path: impl AsRef<Path>) -> Result<LoadPlan> {
+        let path = path.as_ref();
+        let mut file = File::open(path)?;
+        let (n, header) = Header::read(&mut file)?;
+        header.validate(file.metadata()?.len().saturating_sub(8 + n))?;
+        header.check_unknown_fields(self.unknown_fields)?;
+
+        let aliases: HashMap<&str, &str> = self.aliases(&header)?.into_iter().collect();
+        let tensors: Vec<PlannedTensor> = header
+            .names_by_schedule(self.schedule)
+            .into_iter()
+            .map(|name| {
+                let info = &header.tensors[name];
+                let alias_of = aliases.get(name).copied();
+                let mut shape = info.shape().to_vec();
+                let transposed = self.transposes(name, &shape)?;
+                if transposed {
+                    shape.reverse();
+                }
+                Ok(PlannedTensor {
+                    name: name.to_string(),
+                    dtype: info.dtype(),
+                    shape,
+                    bytes: info.byte_len(),
+                    transposed,
+                    alias_of: alias_of.map(str::to_string),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        let read = || tensors.iter().filter(|tensor| tensor.alias_of.is_none());
+        let data: u64 = read().map(|tensor| tensor.bytes).sum();
+        let tensor_reads: usize = read()
+            .map(|tensor| match tensor.transposed {
+                // The stored shape is the reverse of the planned one.
+                true => {
+                    let stored = [tensor.shape[1], tensor.shape[0]];
+                    stored[0].div_ceil(transpose_block_rows(tensor.dtype, &stored))
+                }
+                false => 1,
+            })
+            .sum();
+        Ok(LoadPlan {
+            path: path.to_path_buf(),
+            bytes_read: 8 + n + data,
+            read_ops: 2 + tensor_reads,
+            resident_bytes: n + data,
+            tensors,
+        })
+    }
+}
+
+impl fmt::Display for LoadPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: {} tensors, {} bytes in {} reads, {} bytes resident",
+            self.path.display(),
+            self.tensors.len(),
+            self.bytes_read,
+            self.read_ops,
+            self.resident_bytes
+        )?;
+        for (i, tensor) in self.tensors.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}. {} {:?} {:?} {} bytes{}",
+                i + 1,
+                tensor.name,
+                tensor.dtype,
+                tensor.shape,
+                tensor.bytes,
+                if tensor.transposed {
+                    ", transposed"
+                } else {
+                    ""
+                }
+            )?;
+            if let Some(target) = &tensor.alias_of {
+                writeln!(f, "      alias of {target}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+//! Heuristic detection of which framework produced a file.
+
+use crate::{Dtype, LazyReader, Reader};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Names of the container whose numbered children are the layers of a
+/// transformer, as in `model.layers.0.` or `transformer.h.11.`.
+const LAYER_CONTAINERS: &[&str] = &["layers", "layer", "h", "blocks"];
+
+/// Framework suggested by the `format` metadata key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Framework {
+    PyTorch,
+    Flax,
+    TensorFlow,
+    NumPy,
+    Mlx,
+    /// A `format` value that is not recognised.
+    Other(String),
+}
+
+/// Evidence about how a file was produced, gathered from its header alone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Provenance {
+    /// The `format` entry of `__metadata__`, such as `"pt"`.
+    pub format: Option<String>,
+    pub framework: Option<Framework>,
+    /// Position and count parsed from a `-00001-of-00004` style file name,
+    /// which implies a sharded checkpoint with an index file.
+    pub shard: Option<(usize, usize)>,
+    /// The most common prefix of numbered layers, such as `model.layers`,
+    /// if the names look like a transformers state dict.
+    pub layer_prefix: Option<String>,
+    /// One more than the highest layer number under `layer_prefix`.
+    pub layer_count: usize,
+    pub dtypes: HashSet<Dtype>,
+}
+
+impl Provenance {
+    fn detect<'a>(
+        metadata: &Value,
+        tensors: impl Iterator<Item = (&'a str, Dtype)>,
+        file_name: Option<&str>,
+    ) -> Self {
+        let format = metadata
+            .get("format")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let framework = format.as_deref().map(|format| match format {
+            "pt" | "torch" | "pytorch" => Framework::PyTorch,
+            "flax" | "jax" => Framework::Flax,
+            "tf" | "tensorflow" => Framework::TensorFlow,
+            "np" | "numpy" => Framework::NumPy,
+            "mlx" => Framework::Mlx,
+            other => Framework::Other(other.to_string()),
+        });
+
+        let mut dtypes = HashSet::new();
+        let mut layers: HashMap<String, (usize, usize)> = HashMap::new();
+        for (name, dtype) in tensors {
+            dtypes.insert(dtype);
+            if let Some((prefix, index)) = layer_of(name) {
+                let (tensors, count) = layers.entry(prefix).or_default();
+                *tensors += 1;
+                *count = (*count).max(index + 1);
+            }
+        }
+        let layer = layers
+            .into_iter()
+            .max_by(|(a_prefix, a), (b_prefix, b)| a.0.cmp(&b.0).then(b_prefix.cmp(a_prefix)));
+
+        Self {
+            format,
+            framework,
+            shard: file_name.and_then(shard_of),
+            layer_count: layer.as_ref().map_or(0, |(_, (_, count))| *count),
+            layer_prefix: layer.map(|(prefix, _)| prefix),
+            dtypes,
+        }
+    }
+
+    /// Whether the tensor names follow the numbered-layer layout of a
+    /// transformers state dict.
+    pub fn is_transformers_layout(&self) -> bool {
+        self.layer_prefix.is_some()
+    }
+}
+
+impl Reader {
+    /// Gathers evidence about the file's producer from its metadata, tensor
+    /// names and dtypes.
+    pub fn provenance(&self) -> Provenance {
+        let tensors = self
+            .tensors
+            .iter()
+            .map(|(name, tensor)| (name.as_str(), tensor.dtype()))
+            .chain(
+                self.aliases
+                    .iter()
+                    .map(|(alias, target)| (alias.as_str(), self.tensors[target].dtype())),
+            );
+        Provenance::detect(&self.metadata, tensors, None)
+    }
+}
+
+impl LazyReader {
+    /// Like [`Reader::provenance`], also recognising shard file names.
+    pub fn provenance(&self) -> Provenance {
+        let tensors = self
+            .names()
+            .into_iter()
+            .map(|name| (name, self.info(name).expect("name from header").dtype()));
+        let file_name = self.path().file_name().and_then(|name| name.to_str());
+        Provenance::detect(self.metadata(), tensors, file_name)
+    }
+}
+
+/// The container prefix and layer number of a name like
+/// `model.layers.3.mlp.up_proj.weight`.
+fn layer_of(name: &str) -> Option<(String, usize)> {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.windows(2).enumerate().find_map(|(i, pair)| {
+        let index = pair[1].parse().ok().filter(|_| i + 2 < parts.len())?;
+        LAYER_CONTAINERS
+            .contains(&pair[0])
+            .then(|| (parts[..=i].join("."), index))
+    })
+}
+
+/// The `(index, count)` of a `model-00002-of-00004.safetensors` file name.
+fn shard_of(file_name: &str) -> Option<(usize, usize)> {
+    let stem = file_name.split('.').next()?;
+    let (head, count) = stem.rsplit_once("-of-")?;
+    let (_, index) = head.rsplit_once('-')?;
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !all_digits(index) || !all_digits(count) {
+        return None;
+    }
+    Some((index.parse().ok()?, count.parse().ok()?))
+}
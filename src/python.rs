@@ -0,0 +1,118 @@
+//! Python bindings exposing a lazy reader whose tensors load as NumPy arrays.
+//!
+//! Tensor data is moved into the returned arrays without copying. NumPy has
+//! no bfloat16 dtype, so BF16 tensors are upcast to float32 unless
+//! `bf16_as_uint16=True` is passed, which hands over the raw bits as uint16.
+
+use crate::{Error, LazyReader, Result, Tensor};
+use numpy::{IntoPyArray, PyArrayMethods};
+use pyo3::exceptions::{PyIOError, PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+use std::path::PathBuf;
+
+impl From<Error> for PyErr {
+    fn from(err: Error) -> PyErr {
+        match err {
+            Error::Io(_) => PyIOError::new_err(err.to_string()),
+            Error::TensorNotFound(_) => PyKeyError::new_err(err.to_string()),
+            _ => PyValueError::new_err(err.to_string()),
+        }
+    }
+}
+
+#[pyclass(name = "Reader", module = "safetensors_reader", frozen)]
+struct PyReader {
+    inner: LazyReader,
+}
+
+#[pymethods]
+impl PyReader {
+    /// Opens a safetensors file, reading only its header.
+    #[new]
+    fn new(py: Python<'_>, path: PathBuf) -> PyResult<Self> {
+        let inner = py.detach(|| LazyReader::open(&path))?;
+        Ok(Self { inner })
+    }
+
+    /// Tensor names in file order.
+    fn names(&self) -> Vec<String> {
+        self.inner.names().into_iter().map(str::to_string).collect()
+    }
+
+    /// The `__metadata__` map as a dict, or None if the file has none.
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let json = serde_json::to_string(self.inner.metadata()).map_err(Error::from)?;
+        py.import("json")?.call_method1("loads", (json,))
+    }
+
+    /// Loads a single tensor as a NumPy array.
+    #[pyo3(signature = (name, bf16_as_uint16 = false))]
+    fn get<'py>(
+        &self,
+        py: Python<'py>,
+        name: &str,
+        bf16_as_uint16: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let tensor = py.detach(|| self.inner.load(name))?;
+        to_numpy(py, tensor, bf16_as_uint16)
+    }
+
+    /// Loads every tensor in parallel into a dict of NumPy arrays, in file
+    /// order. Uses the header read when the reader was opened.
+    #[pyo3(signature = (bf16_as_uint16 = false))]
+    fn load_all<'py>(&self, py: Python<'py>, bf16_as_uint16: bool) -> PyResult<Bound<'py, PyDict>> {
+        let tensors = py.detach(|| {
+            self.inner
+                .names()
+                .into_par_iter()
+                .map(|name| Ok((name, self.inner.load(name)?)))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        let dict = PyDict::new(py);
+        for (name, tensor) in tensors {
+            dict.set_item(name, to_numpy(py, tensor, bf16_as_uint16)?)?;
+        }
+        Ok(dict)
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.names().len()
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        self.inner.info(name).is_some()
+    }
+}
+
+fn to_numpy(py: Python<'_>, tensor: Tensor, bf16_as_uint16: bool) -> PyResult<Bound<'_, PyAny>> {
+    match tensor {
+        Tensor::Bf16 { data, shape } if bf16_as_uint16 => {
+            into_array(py, bytemuck::allocation::cast_vec::<_, u16>(data), &shape)
+        }
+        Tensor::Bf16 { data, shape } => into_array(
+            py,
+            data.iter().map(|x| x.to_f32()).collect::<Vec<_>>(),
+            &shape,
+        ),
+        tensor => with_data!(tensor, shape, data => into_array(py, data, &shape)),
+    }
+}
+
+fn into_array<'py, T: numpy::Element>(
+    py: Python<'py>,
+    data: Vec<T>,
+    shape: &[usize],
+) -> PyResult<Bound<'py, PyAny>> {
+    // Reshaping a freshly created contiguous array returns a view of it.
+    Ok(data.into_pyarray(py).reshape(shape)?.into_any())
+}
+
+/// The `safetensors_reader` Python module. Built as an extension module by
+/// maturin; a Rust program embedding Python can register it with
+/// `pyo3::append_to_inittab!`.
+#[pymodule]
+pub fn safetensors_reader(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyReader>()
+}
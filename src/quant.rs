@@ -0,0 +1,119 @@
+//! Dequantization of integer tensors with scale and zero-point parameters.
+
+use crate::ops::{check_dim, outer_inner};
+use crate::{Error, Reader, Result, Tensor};
+
+/// How the quantization parameters of a tensor are named and laid out.
+///
+/// The scale of tensor `name` is stored as `name + scale_suffix`, and its zero
+/// point, if any, as `name + zero_point_suffix`. With `axis` set, parameters
+/// may hold one value per index of that dimension instead of a single value.
+#[derive(Clone, Debug)]
+pub struct QuantScheme {
+    pub scale_suffix: String,
+    pub zero_point_suffix: Option<String>,
+    pub axis: Option<usize>,
+}
+
+impl Default for QuantScheme {
+    fn default() -> Self {
+        Self {
+            scale_suffix: ".scale".to_string(),
+            zero_point_suffix: Some(".zero_point".to_string()),
+            axis: None,
+        }
+    }
+}
+
+impl QuantScheme {
+    /// The default suffixes with per-channel parameters along `axis`.
+    pub fn per_channel(axis: usize) -> Self {
+        Self {
+            axis: Some(axis),
+            ..Self::default()
+        }
+    }
+}
+
+impl Reader {
+    /// Dequantizes tensor `name` using the companion tensors described by
+    /// `scheme`; see [`Tensor::dequantize_with`].
+    pub fn dequantize(&self, name: &str, scheme: &QuantScheme) -> Result<Tensor> {
+        let quantized = self
+            .tensor(name)
+            .ok_or_else(|| Error::TensorNotFound(name.to_string()))?;
+        let companion = |suffix: &str| {
+            let key = format!("{name}{suffix}");
+            self.tensor(&key).ok_or(Error::MissingCompanion {
+                tensor: name.to_string(),
+                companion: key,
+            })
+        };
+
+        let scales = companion(&scheme.scale_suffix)?;
+        let zero_points = scheme
+            .zero_point_suffix
+            .as_deref()
+            .map(companion)
+            .transpose()?;
+        quantized.dequantize_with(scales, zero_points, scheme.axis)
+    }
+}
+
+impl Tensor {
+    /// Computes `(q - zero_point) * scale` for every element `q` as an F32
+    /// tensor of the same shape. A missing zero point means zero.
+    ///
+    /// `scales` and `zero_points` either hold a single value, or, when `axis`
+    /// is given, one value per index of that dimension (as a `[n]` tensor or
+    /// any shape whose only non-unit dimension has length `n`).
+    pub fn dequantize_with(
+        &self,
+        scales: &Tensor,
+        zero_points: Option<&Tensor>,
+        axis: Option<usize>,
+    ) -> Result<Tensor> {
+        let shape = self.shape();
+        let (channels, inner) = match axis {
+            Some(axis) => {
+                check_dim(shape, axis)?;
+                (shape[axis], outer_inner(shape, axis).1)
+            }
+            None => (1, 1),
+        };
+
+        let params = |param: &Tensor| {
+            let values = param.to_f32();
+            let non_unit = param.shape().iter().filter(|&&dim| dim != 1).count();
+            if values.len() == 1 || (axis.is_some() && values.len() == channels && non_unit <= 1) {
+                Ok(values)
+            } else {
+                Err(Error::QuantParamShape {
+                    shape: param.shape().to_vec(),
+                    tensor_shape: shape.to_vec(),
+                    axis,
+                })
+            }
+        };
+        let scales = params(scales)?;
+        let zero_points = zero_points.map(params).transpose()?;
+
+        let at = |values: &[f32], i: usize| match values {
+            [value] => *value,
+            _ => values[(i / inner) % channels],
+        };
+        let data = self
+            .iter_f32()
+            .enumerate()
+            .map(|(i, q)| {
+                let zero_point = zero_points.as_deref().map_or(0.0, |z| at(z, i));
+                (q - zero_point) * at(&scales, i)
+            })
+            .collect();
+
+        Ok(Tensor::F32 {
+            data,
+            shape: shape.to_vec(),
+        })
+    }
+}
@@ -0,0 +1,80 @@
+//! Handing tensor buffers over to foreign code and taking them back.
+
+use crate::{Dtype, Element, Error, Tensor};
+use std::mem::ManuallyDrop;
+
+/// The parts of the `Vec` that backs a tensor, as returned by
+/// [`Tensor::into_raw_parts`].
+///
+/// `ptr` points to `len` initialized elements of the Rust type matching the
+/// tensor's dtype (`bool` for `Bool`, `half::f16` for `F16` and so on), in
+/// row-major order and native byte order, inside an allocation of
+/// `capacity` elements made by Rust's global allocator. `len` and `capacity`
+/// count elements, not bytes.
+///
+/// Whoever holds a `RawData` owns that allocation. It is not freed when the
+/// `RawData` is dropped: give it back with [`Tensor::from_raw_parts`] so Rust
+/// frees it, or free it with the same global allocator and the layout of an
+/// array of `capacity` elements. Freeing it with C's `free` is not allowed
+/// unless the global allocator is the system `malloc`.
+#[derive(Debug)]
+pub struct RawData {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub capacity: usize,
+}
+
+impl Tensor {
+    /// Decomposes the tensor into its dtype, shape and data buffer, without
+    /// copying and without freeing the buffer. See [`RawData`] for who must
+    /// free it and how.
+    pub fn into_raw_parts(self) -> (Dtype, Vec<usize>, RawData) {
+        let dtype = self.dtype();
+        with_data!(self, shape, data => {
+            let mut data = ManuallyDrop::new(data);
+            let raw = RawData {
+                ptr: data.as_mut_ptr().cast(),
+                len: data.len(),
+                capacity: data.capacity(),
+            };
+            (dtype, shape, raw)
+        })
+    }
+
+    /// Rebuilds a tensor from parts returned by [`Tensor::into_raw_parts`],
+    /// taking ownership of the buffer.
+    ///
+    /// Fails with [`Error::ElementCount`] if `raw.len` is not the number of
+    /// elements of `shape`, including when that number overflows `usize`.
+    /// The buffer is then not taken: it comes back with the error, still
+    /// owned by the caller.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must describe an allocation as documented on [`RawData`] for the
+    /// element type of `dtype`: allocated by Rust's global allocator for
+    /// exactly `raw.capacity` elements of that type, with the first `raw.len`
+    /// initialized, and not owned or freed by anyone else afterwards. For
+    /// `Bool`, every byte must be 0 or 1. Parts from `into_raw_parts` with the
+    /// same dtype meet all of these.
+    pub unsafe fn from_raw_parts(
+        dtype: Dtype,
+        shape: Vec<usize>,
+        raw: RawData,
+    ) -> std::result::Result<Tensor, (Error, RawData)> {
+        let elements = shape.iter().try_fold(1usize, |n, &dim| n.checked_mul(dim));
+        if elements != Some(raw.len) {
+            let error = Error::ElementCount {
+                shape,
+                len: raw.len,
+            };
+            return Err((error, raw));
+        }
+
+        Ok(with_dtype!(dtype, T => {
+            // SAFETY: guaranteed by the caller as documented above.
+            let data = unsafe { Vec::from_raw_parts(raw.ptr.cast::<T>(), raw.len, raw.capacity) };
+            Element::into_tensor(data, shape)
+        }))
+    }
+}
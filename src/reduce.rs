@@ -0,0 +1,164 @@
+//! Reductions over one dimension or over all elements.
+//!
+//! Every reduction takes `dim: Option<usize>`, where `None` reduces over all
+//! elements, and a `keepdim` flag that keeps reduced dimensions with size 1
+//! instead of removing them.
+
+use crate::ops::{check_dim, outer_inner};
+use crate::{Element, Error, Result, Tensor};
+
+/// A tensor viewed as `[outer, len, inner]` around the reduced dimension.
+struct Layout {
+    outer: usize,
+    len: usize,
+    inner: usize,
+    shape: Vec<usize>,
+}
+
+impl Layout {
+    /// Offsets of the elements along the reduced dimension for each output
+    /// position, in row-major output order.
+    fn lanes(&self) -> impl Iterator<Item = impl Iterator<Item = usize>> {
+        let (outer, len, inner) = (self.outer, self.len, self.inner);
+        (0..outer).flat_map(move |o| {
+            (0..inner).map(move |i| {
+                let base = o * len * inner + i;
+                (0..len).map(move |k| base + k * inner)
+            })
+        })
+    }
+}
+
+impl Tensor {
+    /// Sum of the elements, accumulated and returned as F64. An empty
+    /// reduction gives 0.
+    pub fn sum(&self, dim: Option<usize>, keepdim: bool) -> Result<Tensor> {
+        let layout = self.layout(dim, keepdim)?;
+        Ok(with_data!(self, _shape, data => {
+            let sums = layout
+                .lanes()
+                .map(|lane| lane.fold(0.0, |sum, k| sum + data[k].to_f64()))
+                .collect();
+            Tensor::F64 { data: sums, shape: layout.shape }
+        }))
+    }
+
+    /// Mean of the elements, accumulated and returned as F64. An empty
+    /// reduction gives NaN.
+    pub fn mean(&self, dim: Option<usize>, keepdim: bool) -> Result<Tensor> {
+        let len = self.layout(dim, keepdim)?.len as f64;
+        let mut sums = self.sum(dim, keepdim)?;
+        if let Tensor::F64 { data, .. } = &mut sums {
+            data.iter_mut().for_each(|sum| *sum /= len);
+        }
+        Ok(sums)
+    }
+
+    /// Largest element, with the input's dtype. NaN propagates; an empty
+    /// reduction is an error.
+    pub fn max(&self, dim: Option<usize>, keepdim: bool) -> Result<Tensor> {
+        self.extreme(dim, keepdim, true)
+    }
+
+    /// Smallest element, with the input's dtype. NaN propagates; an empty
+    /// reduction is an error.
+    pub fn min(&self, dim: Option<usize>, keepdim: bool) -> Result<Tensor> {
+        self.extreme(dim, keepdim, false)
+    }
+
+    /// Index of the first largest element along `dim` (or into the flattened
+    /// tensor for `None`) as I64. A NaN counts as larger than any number; an
+    /// empty reduction is an error.
+    pub fn argmax(&self, dim: Option<usize>, keepdim: bool) -> Result<Tensor> {
+        let layout = self.nonempty_layout(dim, keepdim)?;
+        Ok(with_data!(self, _shape, data => {
+            let indices = layout
+                .lanes()
+                .map(|lane| arg_extreme(data, lane, true).0 as i64)
+                .collect();
+            Tensor::I64 { data: indices, shape: layout.shape }
+        }))
+    }
+
+    fn extreme(&self, dim: Option<usize>, keepdim: bool, largest: bool) -> Result<Tensor> {
+        let layout = self.nonempty_layout(dim, keepdim)?;
+        Ok(with_data!(self, _shape, data => {
+            let values = layout
+                .lanes()
+                .map(|lane| data[arg_extreme(data, lane, largest).1])
+                .collect();
+            Element::into_tensor(values, layout.shape)
+        }))
+    }
+
+    fn layout(&self, dim: Option<usize>, keepdim: bool) -> Result<Layout> {
+        let shape = self.shape();
+        let Some(dim) = dim else {
+            return Ok(Layout {
+                outer: 1,
+                len: self.numel(),
+                inner: 1,
+                shape: if keepdim {
+                    vec![1; shape.len()]
+                } else {
+                    Vec::new()
+                },
+            });
+        };
+
+        check_dim(shape, dim)?;
+        let (outer, inner) = outer_inner(shape, dim);
+        let mut new_shape = shape.to_vec();
+        if keepdim {
+            new_shape[dim] = 1;
+        } else {
+            new_shape.remove(dim);
+        }
+        Ok(Layout {
+            outer,
+            len: shape[dim],
+            inner,
+            shape: new_shape,
+        })
+    }
+
+    fn nonempty_layout(&self, dim: Option<usize>, keepdim: bool) -> Result<Layout> {
+        let layout = self.layout(dim, keepdim)?;
+        if layout.len == 0 {
+            return Err(Error::EmptyReduction {
+                dim,
+                shape: self.shape().to_vec(),
+            });
+        }
+        Ok(layout)
+    }
+}
+
+/// Position within the lane and offset into `data` of the first largest (or
+/// smallest) element, where the first NaN beats everything. Values are
+/// compared as `T`, so 64-bit integers beyond 2^53 stay distinct. The lane
+/// must not be empty.
+fn arg_extreme<T: Element + PartialOrd>(
+    data: &[T],
+    lane: impl Iterator<Item = usize>,
+    largest: bool,
+) -> (usize, usize) {
+    // Only NaN is unordered with itself.
+    let is_nan = |x: &T| x.partial_cmp(x).is_none();
+    let mut best: Option<(usize, usize, T)> = None;
+    for (pos, offset) in lane.enumerate() {
+        let value = data[offset];
+        let better = match best {
+            None => true,
+            Some((_, _, current)) if is_nan(&current) => false,
+            Some(_) if is_nan(&value) => true,
+            Some((_, _, current)) if largest => value > current,
+            Some((_, _, current)) => value < current,
+        };
+        if better {
+            best = Some((pos, offset, value));
+        }
+    }
+    let (pos, offset, _) = best.expect("lane is not empty");
+    (pos, offset)
+}
@@ -0,0 +1,126 @@
+//! Zero-copy iteration over the rows of a tensor.
+
+use crate::{Dtype, Element, Error, Result, Tensor};
+use half::{bf16, f16};
+
+/// One row of a tensor, borrowing the tensor's storage.
+#[derive(Clone, Copy, Debug)]
+pub struct RowView<'a> {
+    tensor: &'a Tensor,
+    index: usize,
+    start: usize,
+    len: usize,
+}
+
+macro_rules! typed_accessors {
+    ($($name:ident => $ty:ty),* $(,)?) => {
+        $(
+            #[doc = concat!("Borrows the row as `", stringify!($ty), "`.")]
+            pub fn $name(&self) -> Result<&'a [$ty]> {
+                self.as_slice()
+            }
+        )*
+    };
+}
+
+impl<'a> RowView<'a> {
+    /// Position of this row among the tensor's rows.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn dtype(&self) -> Dtype {
+        self.tensor.dtype()
+    }
+
+    /// Borrows the row as `T`, failing if the tensor's dtype is not `T::DTYPE`.
+    pub fn as_slice<T: Element>(&self) -> Result<&'a [T]> {
+        let data = self.tensor.as_slice::<T>()?;
+        Ok(&data[self.start..self.start + self.len])
+    }
+
+    typed_accessors!(
+        as_bool => bool,
+        as_u8 => u8,
+        as_i8 => i8,
+        as_u16 => u16,
+        as_i16 => i16,
+        as_f16 => f16,
+        as_bf16 => bf16,
+        as_u32 => u32,
+        as_i32 => i32,
+        as_f32 => f32,
+        as_u64 => u64,
+        as_i64 => i64,
+        as_f64 => f64,
+    );
+}
+
+impl Tensor {
+    /// Iterates over the rows of a 2-D tensor.
+    pub fn rows(&self) -> Result<impl ExactSizeIterator<Item = RowView<'_>> + DoubleEndedIterator> {
+        self.check_rank_2()?;
+        self.rows_of_last_dim()
+    }
+
+    /// Row `i` of a 2-D tensor.
+    pub fn row(&self, i: usize) -> Result<RowView<'_>> {
+        self.check_rank_2()?;
+        let (count, len) = self.row_layout()?;
+        if i >= count {
+            return Err(Error::IndexOutOfBounds {
+                axis: 0,
+                index: i,
+                shape: self.shape().to_vec(),
+            });
+        }
+        Ok(self.row_view(i, len))
+    }
+
+    /// Iterates over the last dimension of a tensor of any rank of at least
+    /// one, treating all leading dimensions as a single flattened row index.
+    pub fn rows_of_last_dim(
+        &self,
+    ) -> Result<impl ExactSizeIterator<Item = RowView<'_>> + DoubleEndedIterator> {
+        let (count, len) = self.row_layout()?;
+        Ok((0..count).map(move |i| self.row_view(i, len)))
+    }
+
+    pub(crate) fn check_rank_2(&self) -> Result<()> {
+        if self.shape().len() != 2 {
+            return Err(Error::RankMismatch {
+                expected: 2,
+                shape: self.shape().to_vec(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Number of rows and their length when viewed as `[-1, last_dim]`.
+    fn row_layout(&self) -> Result<(usize, usize)> {
+        match self.shape().split_last() {
+            Some((&len, leading)) => Ok((leading.iter().product(), len)),
+            None => Err(Error::RankMismatch {
+                expected: 1,
+                shape: Vec::new(),
+            }),
+        }
+    }
+
+    fn row_view(&self, index: usize, len: usize) -> RowView<'_> {
+        RowView {
+            tensor: self,
+            index,
+            start: index * len,
+            len,
+        }
+    }
+}
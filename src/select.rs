@@ -0,0 +1,91 @@
+use crate::{Error, Result};
+
+/// Chooses a subset of tensors by name.
+#[derive(Clone, Debug)]
+pub enum Selector {
+    /// Exactly these names, all of which must exist.
+    Names(Vec<String>),
+    /// Names matching a glob pattern, where `*` matches any run of characters
+    /// and `?` matches a single character.
+    Pattern(String),
+}
+
+impl Selector {
+    pub fn names<I, S>(names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Names(names.into_iter().map(Into::into).collect())
+    }
+
+    pub fn pattern(pattern: impl Into<String>) -> Self {
+        Self::Pattern(pattern.into())
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Names(names) => names.iter().any(|n| n == name),
+            Self::Pattern(pattern) => glob_match(pattern, name),
+        }
+    }
+
+    /// Filters `available` down to the selected names, preserving its order.
+    ///
+    /// Fails if an explicitly named tensor is missing or if a pattern selects
+    /// nothing.
+    pub(crate) fn resolve<'a>(&self, available: &[&'a str]) -> Result<Vec<&'a str>> {
+        if let Self::Names(names) = self {
+            let missing: Vec<_> = names
+                .iter()
+                .filter(|name| !available.contains(&name.as_str()))
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                return Err(Error::MissingTensors(missing));
+            }
+        }
+
+        let selected: Vec<_> = available
+            .iter()
+            .copied()
+            .filter(|name| self.matches(name))
+            .collect();
+        if let (Self::Pattern(pattern), true) = (self, selected.is_empty()) {
+            return Err(Error::NoMatch(pattern.clone()));
+        }
+
+        Ok(selected)
+    }
+}
+
+pub(crate) fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` seen and the name position it was tried at.
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, at)) => {
+                    p = star + 1;
+                    n = at + 1;
+                    backtrack = Some((star, at + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,115 @@
+//! Checkpoints split across several files by a `*.safetensors.index.json`.
+
+use crate::header::TensorInfo;
+use crate::{Error, LazyReader, ReaderOptions, Result, Tensor};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Deserialize)]
+struct Index {
+    #[serde(default)]
+    metadata: serde_json::Value,
+    weight_map: BTreeMap<String, String>,
+}
+
+/// Reads the index of a sharded checkpoint and opens every shard it names
+/// lazily, so tensors can be looked up by name regardless of their shard.
+/// Like [`LazyReader`] it is `Send + Sync` and can be loaded from many
+/// threads at once.
+pub struct ShardedReader {
+    index_path: PathBuf,
+    metadata: serde_json::Value,
+    weight_map: BTreeMap<String, String>,
+    shards: HashMap<String, LazyReader>,
+}
+
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ShardedReader>();
+};
+
+impl ShardedReader {
+    pub fn open(index_path: impl AsRef<Path>) -> Result<Self> {
+        Self::open_with(index_path, &ReaderOptions::default())
+    }
+
+    /// Opens the index at `index_path`. Shard file names are resolved
+    /// relative to the directory containing the index.
+    pub fn open_with(index_path: impl AsRef<Path>, options: &ReaderOptions) -> Result<Self> {
+        let index_path = index_path.as_ref().to_path_buf();
+        let index: Index = serde_json::from_slice(&fs::read(&index_path)?)?;
+        let dir = index_path.parent().unwrap_or(Path::new(""));
+
+        let mut shards = HashMap::new();
+        for (name, file) in &index.weight_map {
+            if !shards.contains_key(file) {
+                shards.insert(
+                    file.clone(),
+                    LazyReader::open_with(dir.join(file), options)?,
+                );
+            }
+            if shards[file].info(name).is_none() {
+                return Err(Error::InvalidIndex(format!(
+                    "tensor `{name}` is mapped to `{file}`, which does not contain it"
+                )));
+            }
+        }
+
+        Ok(Self {
+            index_path,
+            metadata: index.metadata,
+            weight_map: index.weight_map,
+            shards,
+        })
+    }
+
+    pub fn index_path(&self) -> &Path {
+        &self.index_path
+    }
+
+    /// The `metadata` object of the index, such as `total_size`.
+    pub fn metadata(&self) -> &serde_json::Value {
+        &self.metadata
+    }
+
+    /// Tensor names ordered by shard file name, then by position in the shard.
+    pub fn names(&self) -> Vec<&str> {
+        let mut files: Vec<_> = self.shards.keys().collect();
+        files.sort();
+        files
+            .into_iter()
+            .flat_map(|file| {
+                self.shards[file]
+                    .names()
+                    .into_iter()
+                    .filter(move |name| self.weight_map.get(*name) == Some(file))
+            })
+            .collect()
+    }
+
+    /// The shard holding tensor `name`.
+    pub fn shard(&self, name: &str) -> Option<&LazyReader> {
+        self.weight_map.get(name).map(|file| &self.shards[file])
+    }
+
+    pub fn info(&self, name: &str) -> Option<&TensorInfo> {
+        self.shard(name)?.info(name)
+    }
+
+    pub fn load(&self, name: &str) -> Result<Tensor> {
+        self.get_shard(name)?.load(name)
+    }
+
+    /// Returns a reader over the tensor's raw bytes, without decoding them.
+    pub fn raw_reader(&self, name: &str) -> Result<impl Read> {
+        self.get_shard(name)?.raw_reader(name)
+    }
+
+    fn get_shard(&self, name: &str) -> Result<&LazyReader> {
+        self.shard(name)
+            .ok_or_else(|| Error::TensorNotFound(name.to_string()))
+    }
+}
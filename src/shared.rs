@@ -0,0 +1,76 @@
+//! Reference-counted tensors that can be cloned without copying their data.
+
+use crate::{Reader, Tensor};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A [`Tensor`] behind an [`Arc`], so that `clone` is O(1) and clones can be
+/// sent to other threads. The data is freed when the last clone is dropped.
+///
+/// It dereferences to [`Tensor`], so every read-only accessor works on it
+/// unchanged.
+#[derive(Clone, Debug)]
+pub struct SharedTensor(Arc<Tensor>);
+
+impl SharedTensor {
+    /// Copies the data into an owned tensor, leaving this one untouched.
+    pub fn to_owned(&self) -> Tensor {
+        Tensor::clone(&self.0)
+    }
+
+    /// Unwraps the tensor without copying if this is the only clone, and
+    /// copies it otherwise.
+    pub fn into_owned(self) -> Tensor {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| Tensor::clone(&shared))
+    }
+
+    /// Whether two handles share the same data.
+    pub fn ptr_eq(a: &Self, b: &Self) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+
+    /// Number of handles sharing this data, including this one.
+    pub fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.0)
+    }
+}
+
+impl Deref for SharedTensor {
+    type Target = Tensor;
+
+    fn deref(&self) -> &Tensor {
+        &self.0
+    }
+}
+
+impl From<Tensor> for SharedTensor {
+    fn from(tensor: Tensor) -> Self {
+        tensor.into_shared()
+    }
+}
+
+impl Tensor {
+    /// Moves the tensor into shared storage without copying its data.
+    pub fn into_shared(self) -> SharedTensor {
+        SharedTensor(Arc::new(self))
+    }
+}
+
+impl Reader {
+    /// Moves every tensor into shared storage without copying, dropping the
+    /// rest of the reader. Each of [`Reader::aliases`] gets a handle to the
+    /// data of the tensor it aliases.
+    pub fn into_shared_tensors(self) -> HashMap<String, SharedTensor> {
+        let mut shared: HashMap<String, SharedTensor> = self
+            .tensors
+            .into_iter()
+            .map(|(name, tensor)| (name, tensor.into_shared()))
+            .collect();
+        for (alias, target) in self.aliases {
+            let tensor = shared[&target].clone();
+            shared.insert(alias, tensor);
+        }
+        shared
+    }
+}
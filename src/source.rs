@@ -0,0 +1,42 @@
+//! A common interface over readers that load tensors on demand.
+
+use crate::{LazyReader, Result, ShardedReader, Tensor, TensorInfo};
+
+/// Anything that can list its tensors from a header and load them one at a
+/// time.
+pub trait TensorSource {
+    /// Tensor names in storage order.
+    fn names(&self) -> Vec<&str>;
+
+    fn info(&self, name: &str) -> Option<&TensorInfo>;
+
+    fn load(&self, name: &str) -> Result<Tensor>;
+}
+
+impl TensorSource for LazyReader {
+    fn names(&self) -> Vec<&str> {
+        LazyReader::names(self)
+    }
+
+    fn info(&self, name: &str) -> Option<&TensorInfo> {
+        LazyReader::info(self, name)
+    }
+
+    fn load(&self, name: &str) -> Result<Tensor> {
+        LazyReader::load(self, name)
+    }
+}
+
+impl TensorSource for ShardedReader {
+    fn names(&self) -> Vec<&str> {
+        ShardedReader::names(self)
+    }
+
+    fn info(&self, name: &str) -> Option<&TensorInfo> {
+        ShardedReader::info(self, name)
+    }
+
+    fn load(&self, name: &str) -> Result<Tensor> {
+        ShardedReader::load(self, name)
+    }
+}
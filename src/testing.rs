@@ -0,0 +1,209 @@
+//! Small safetensors files for tests, both valid and deliberately broken.
+
+use crate::{Dtype, Element, Result, Tensor};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// How [`FixtureBuilder::tensor`] fills a tensor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Fill {
+    /// `0, 1, 2, ...` in row-major order, saturating at the bounds of
+    /// integer dtypes and alternating for bool.
+    Sequence,
+    /// Every element set to this value, converted as by
+    /// [`Element::from_f64`].
+    Constant(f64),
+    /// Values uniform in `[-1, 1)` from a generator seeded with this value,
+    /// rounded for integer and bool dtypes. The same seed always gives the
+    /// same data.
+    Random(u64),
+}
+
+struct Entry {
+    name: String,
+    dtype: Value,
+    shape: Vec<usize>,
+    bytes: Vec<u8>,
+    /// Points the header entry at the start of the data section instead of
+    /// the tensor's own bytes.
+    overlap: bool,
+    /// Written twice in the header.
+    duplicate: bool,
+}
+
+/// Assembles a safetensors file in memory.
+///
+/// Tensors are laid out in the order they are added. The header is written
+/// with metadata first and is padded with spaces to a multiple of 8 bytes
+/// unless [`FixtureBuilder::header_padding`] says otherwise. The corruption
+/// methods produce files that break the format in one specific way, for
+/// negative tests.
+#[derive(Default)]
+pub struct FixtureBuilder {
+    entries: Vec<Entry>,
+    metadata: BTreeMap<String, String>,
+    padding: Option<usize>,
+    truncate_at: Option<usize>,
+}
+
+impl FixtureBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a tensor of `dtype` and `shape` filled as described by `fill`.
+    pub fn tensor(self, name: &str, dtype: Dtype, shape: &[usize], fill: Fill) -> Self {
+        let numel = shape.iter().product::<usize>();
+        let mut rng = match fill {
+            Fill::Random(seed) => SplitMix64(seed),
+            _ => SplitMix64(0),
+        };
+        let values = (0..numel).map(|i| match fill {
+            Fill::Sequence if dtype == Dtype::Bool => (i % 2) as f64,
+            Fill::Sequence => i as f64,
+            Fill::Constant(value) => value,
+            Fill::Random(_) => rng.next_f64() * 2.0 - 1.0,
+        });
+        let tensor = with_dtype!(dtype, T => {
+            let data: Vec<T> = values.map(T::from_f64).collect();
+            Element::into_tensor(data, shape.to_vec())
+        });
+        self.add_tensor(name, &tensor)
+    }
+
+    /// Adds an existing tensor.
+    pub fn add_tensor(mut self, name: &str, tensor: &Tensor) -> Self {
+        self.entries.push(Entry {
+            name: name.to_string(),
+            dtype: json!(tensor.dtype()),
+            shape: tensor.shape().to_vec(),
+            bytes: tensor.as_bytes().to_vec(),
+            overlap: false,
+            duplicate: false,
+        });
+        self
+    }
+
+    /// Adds an entry to `__metadata__`.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Appends exactly `spaces` spaces to the header JSON instead of padding
+    /// it to a multiple of 8 bytes.
+    pub fn header_padding(mut self, spaces: usize) -> Self {
+        self.padding = Some(spaces);
+        self
+    }
+
+    /// Cuts the finished file off after `len` bytes.
+    pub fn truncate_at(mut self, len: usize) -> Self {
+        self.truncate_at = Some(len);
+        self
+    }
+
+    /// Points the offsets of `name` at the start of the data section, so
+    /// they overlap the first tensor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no tensor `name` has been added.
+    pub fn overlap_offsets(mut self, name: &str) -> Self {
+        self.entry(name).overlap = true;
+        self
+    }
+
+    /// Gives `name` a dtype that does not exist.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no tensor `name` has been added.
+    pub fn bogus_dtype(mut self, name: &str) -> Self {
+        self.entry(name).dtype = json!("BOGUS");
+        self
+    }
+
+    /// Writes the header entry of `name` twice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no tensor `name` has been added.
+    pub fn duplicate_key(mut self, name: &str) -> Self {
+        self.entry(name).duplicate = true;
+        self
+    }
+
+    /// The complete file contents.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Built by hand rather than through a map so entries can repeat.
+        let mut fields = Vec::new();
+        if !self.metadata.is_empty() {
+            fields.push(format!("\"__metadata__\":{}", json!(self.metadata)));
+        }
+        let mut offset = 0;
+        for entry in &self.entries {
+            let len = entry.bytes.len() as u64;
+            let start = if entry.overlap { 0 } else { offset };
+            let field = format!(
+                "{}:{}",
+                json!(entry.name),
+                json!({
+                    "dtype": entry.dtype,
+                    "shape": entry.shape,
+                    "data_offsets": [start, start + len],
+                })
+            );
+            if entry.duplicate {
+                fields.push(field.clone());
+            }
+            fields.push(field);
+            offset += len;
+        }
+
+        let mut header = format!("{{{}}}", fields.join(","));
+        let padding = self.padding.unwrap_or((8 - header.len() % 8) % 8);
+        header.extend(std::iter::repeat_n(' ', padding));
+
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header.as_bytes());
+        for entry in &self.entries {
+            bytes.extend_from_slice(&entry.bytes);
+        }
+        if let Some(len) = self.truncate_at {
+            bytes.truncate(len);
+        }
+        bytes
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    fn entry(&mut self, name: &str) -> &mut Entry {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.name == name)
+            .unwrap_or_else(|| panic!("no fixture tensor named `{name}`"))
+    }
+}
+
+/// The SplitMix64 generator, which is small and good enough for test data.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,99 @@
+//! Conversion to and from LibTorch tensors via `tch`.
+//!
+//! Conversions only ever involve CPU tensors: [`Tensor::to_tch`] produces a
+//! CPU tensor and [`Tensor::from_tch`] rejects tensors on other devices. Move
+//! tensors with `tch::Tensor::to_device` before or after converting.
+
+use crate::{Dtype, Error, Result, Tensor};
+use tch::{Device, Kind, TchError};
+
+/// How [`Tensor::from_tch`] treats tensors whose memory is not contiguous,
+/// such as the result of a transpose.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonContiguous {
+    /// Fail with an error explaining why.
+    #[default]
+    Reject,
+    /// Make a contiguous copy first.
+    Copy,
+}
+
+fn kind(dtype: Dtype) -> Option<Kind> {
+    match dtype {
+        Dtype::Bool => Some(Kind::Bool),
+        Dtype::U8 => Some(Kind::Uint8),
+        Dtype::I8 => Some(Kind::Int8),
+        Dtype::I16 => Some(Kind::Int16),
+        Dtype::F16 => Some(Kind::Half),
+        Dtype::Bf16 => Some(Kind::BFloat16),
+        Dtype::I32 => Some(Kind::Int),
+        Dtype::F32 => Some(Kind::Float),
+        Dtype::I64 => Some(Kind::Int64),
+        Dtype::F64 => Some(Kind::Double),
+        Dtype::U16 | Dtype::U32 | Dtype::U64 => None,
+    }
+}
+
+fn dtype(kind: Kind) -> Option<Dtype> {
+    match kind {
+        Kind::Bool => Some(Dtype::Bool),
+        Kind::Uint8 => Some(Dtype::U8),
+        Kind::Int8 => Some(Dtype::I8),
+        Kind::Int16 => Some(Dtype::I16),
+        Kind::Half => Some(Dtype::F16),
+        Kind::BFloat16 => Some(Dtype::Bf16),
+        Kind::Int => Some(Dtype::I32),
+        Kind::Float => Some(Dtype::F32),
+        Kind::Int64 => Some(Dtype::I64),
+        Kind::Double => Some(Dtype::F64),
+        _ => None,
+    }
+}
+
+impl Tensor {
+    /// Copies the tensor into a new CPU `tch::Tensor` of the matching kind.
+    pub fn to_tch(&self) -> Result<tch::Tensor> {
+        let kind = kind(self.dtype())
+            .ok_or_else(|| Error::UnsupportedDtype(format!("{:?} (no tch kind)", self.dtype())))?;
+        let size: Vec<i64> = self.shape().iter().map(|&dim| dim as i64).collect();
+        Ok(tch::Tensor::f_from_data_size(self.as_bytes(), &size, kind)?)
+    }
+
+    /// Copies a CPU `tch::Tensor` into a new tensor.
+    pub fn from_tch(tensor: &tch::Tensor, non_contiguous: NonContiguous) -> Result<Tensor> {
+        if tensor.device() != Device::Cpu {
+            return Err(TchError::Convert(format!(
+                "tensor is on {:?}; only CPU tensors can be converted",
+                tensor.device()
+            ))
+            .into());
+        }
+
+        let contiguous;
+        let tensor = match (tensor.is_contiguous(), non_contiguous) {
+            (true, _) => tensor,
+            (false, NonContiguous::Copy) => {
+                contiguous = tensor.f_contiguous()?;
+                &contiguous
+            }
+            (false, NonContiguous::Reject) => {
+                return Err(TchError::Convert(format!(
+                    "tensor with size {:?} and strides {:?} is not contiguous",
+                    tensor.size(),
+                    tensor.stride()
+                ))
+                .into())
+            }
+        };
+
+        let kind = tensor.f_kind()?;
+        let dtype =
+            dtype(kind).ok_or_else(|| Error::UnsupportedDtype(format!("{kind:?} (tch kind)")))?;
+        let shape: Vec<usize> = tensor.size().iter().map(|&dim| dim as usize).collect();
+
+        let numel = tensor.numel();
+        let mut bytes = vec![0u8; numel * dtype.size()];
+        tensor.f_copy_data_u8(&mut bytes, numel)?;
+        Tensor::from_bytes(dtype, shape, &bytes)
+    }
+}
@@ -0,0 +1,101 @@
+//! Spans and events for the optional `tracing` feature, which compile to
+//! nothing without it.
+
+/// The span that per-tensor spans are parented to. Rayon workers do not
+/// inherit the current span, so it is captured before entering a parallel
+/// iterator and passed in explicitly.
+#[cfg(feature = "tracing")]
+pub(crate) type Parent = tracing::Span;
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Parent;
+
+#[cfg(feature = "tracing")]
+pub(crate) fn current() -> Parent {
+    tracing::Span::current()
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn current() -> Parent {
+    Parent
+}
+
+/// Runs `read` inside a `read_tensor` span recording the tensor's name, byte
+/// count and the elapsed time in microseconds.
+///
+/// The span is created with the subscriber `parent` belongs to, so a
+/// thread-local subscriber set on the calling thread also sees the spans of
+/// rayon workers.
+#[cfg(feature = "tracing")]
+pub(crate) fn read_tensor<T>(
+    parent: &Parent,
+    name: &str,
+    bytes: u64,
+    read: impl FnOnce() -> T,
+) -> T {
+    let traced = || {
+        let span = tracing::info_span!(
+            parent: parent,
+            "read_tensor",
+            name,
+            bytes,
+            elapsed_us = tracing::field::Empty,
+        );
+        let _entered = span.enter();
+        let start = std::time::Instant::now();
+        let result = read();
+        span.record("elapsed_us", start.elapsed().as_micros() as u64);
+        result
+    };
+    match parent.with_subscriber(|(_, dispatch)| dispatch.clone()) {
+        Some(dispatch) => tracing::dispatcher::with_default(&dispatch, traced),
+        None => traced(),
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn read_tensor<T>(_: &Parent, _: &str, _: u64, read: impl FnOnce() -> T) -> T {
+    read()
+}
+
+/// Reports an unknown header field under [`UnknownFields::Warn`].
+///
+/// [`UnknownFields::Warn`]: crate::UnknownFields::Warn
+#[cfg(feature = "tracing")]
+pub(crate) fn unknown_field(tensor: &str, field: &str) {
+    tracing::warn!(tensor, field, "unknown tensor header field");
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn unknown_field(_: &str, _: &str) {}
+
+/// Reports how many reads [`LazyReader::gather_rows`] coalesced the requested
+/// rows into.
+///
+/// [`LazyReader::gather_rows`]: crate::LazyReader::gather_rows
+#[cfg(feature = "tracing")]
+pub(crate) fn gather_reads(tensor: &str, rows: usize, reads: usize) {
+    tracing::debug!(tensor, rows, reads, "coalesced row reads");
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn gather_reads(_: &str, _: usize, _: usize) {}
+
+/// Reports a tensor read block by block and transposed, rather than with a
+/// single read.
+#[cfg(feature = "tracing")]
+pub(crate) fn transposed_read(tensor: &str) {
+    tracing::debug!(tensor, "transposing while reading");
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn transposed_read(_: &str) {}
+
+/// Reports a compressed zip member, which is decompressed front to back
+/// instead of read with ranged reads.
+#[cfg(all(feature = "zip", feature = "tracing"))]
+pub(crate) fn sequential_read(member: &str) {
+    tracing::debug!(member, "compressed member, reading sequentially");
+}
+
+#[cfg(all(feature = "zip", not(feature = "tracing")))]
+pub(crate) fn sequential_read(_: &str) {}
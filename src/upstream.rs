@@ -0,0 +1,97 @@
+//! Interop with the official `safetensors` crate.
+
+use crate::{Dtype, Error, Result, Tensor};
+use safetensors::tensor::{TensorView, View};
+use std::borrow::Cow;
+
+/// Pairs of equivalent dtypes; every other upstream dtype is unsupported.
+macro_rules! dtype_pairs {
+    ($($ours:ident <=> $theirs:ident),* $(,)?) => {
+        impl TryFrom<safetensors::Dtype> for Dtype {
+            type Error = Error;
+
+            fn try_from(dtype: safetensors::Dtype) -> Result<Self> {
+                match dtype {
+                    $(safetensors::Dtype::$theirs => Ok(Dtype::$ours),)*
+                    other => Err(Error::UnsupportedDtype(format!("{other:?}"))),
+                }
+            }
+        }
+
+        impl From<Dtype> for safetensors::Dtype {
+            fn from(dtype: Dtype) -> Self {
+                match dtype {
+                    $(Dtype::$ours => safetensors::Dtype::$theirs,)*
+                }
+            }
+        }
+    };
+}
+
+dtype_pairs!(
+    Bool <=> BOOL,
+    U8 <=> U8,
+    I8 <=> I8,
+    U16 <=> U16,
+    I16 <=> I16,
+    F16 <=> F16,
+    Bf16 <=> BF16,
+    U32 <=> U32,
+    I32 <=> I32,
+    F32 <=> F32,
+    U64 <=> U64,
+    I64 <=> I64,
+    F64 <=> F64,
+);
+
+impl View for &Tensor {
+    fn dtype(&self) -> safetensors::Dtype {
+        Tensor::dtype(self).into()
+    }
+
+    fn shape(&self) -> &[usize] {
+        Tensor::shape(self)
+    }
+
+    fn data(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+
+    fn data_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+}
+
+impl View for Tensor {
+    fn dtype(&self) -> safetensors::Dtype {
+        View::dtype(&self)
+    }
+
+    fn shape(&self) -> &[usize] {
+        Tensor::shape(self)
+    }
+
+    fn data(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+
+    fn data_len(&self) -> usize {
+        self.as_bytes().len()
+    }
+}
+
+impl TryFrom<&TensorView<'_>> for Tensor {
+    type Error = Error;
+
+    fn try_from(view: &TensorView<'_>) -> Result<Self> {
+        Tensor::from_bytes(view.dtype().try_into()?, view.shape().to_vec(), view.data())
+    }
+}
+
+impl TryFrom<TensorView<'_>> for Tensor {
+    type Error = Error;
+
+    fn try_from(view: TensorView<'_>) -> Result<Self> {
+        Tensor::try_from(&view)
+    }
+}
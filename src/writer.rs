@@ -0,0 +1,248 @@
+use crate::header::{TensorInfo, KNOWN_FIELDS};
+use crate::{Dtype, Error, Result, Tensor};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Tensor bytes are produced and written in blocks of at most this size.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Incrementally writes a safetensors file without holding the tensors in memory.
+///
+/// Tensor bytes are streamed to a temporary `<path>.partial` file as they are
+/// added. [`StreamWriter::finish`] then writes the header to `path` and copies
+/// the data section after it, so peak memory stays at one chunk regardless of
+/// model size.
+pub struct StreamWriter {
+    path: PathBuf,
+    temp_path: PathBuf,
+    data: File,
+    offset: u64,
+    entries: Vec<(String, TensorInfo)>,
+    names: HashSet<String>,
+    metadata: BTreeMap<String, String>,
+}
+
+impl StreamWriter {
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut temp_path = path.clone().into_os_string();
+        temp_path.push(".partial");
+        let temp_path = PathBuf::from(temp_path);
+
+        let data = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&temp_path)?;
+
+        Ok(Self {
+            path,
+            temp_path,
+            data,
+            offset: 0,
+            entries: Vec::new(),
+            names: HashSet::new(),
+            metadata: BTreeMap::new(),
+        })
+    }
+
+    /// Adds an entry to the `__metadata__` map of the header.
+    pub fn insert_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.metadata.insert(key.into(), value.into());
+    }
+
+    /// Adds an extra field to the header entry of the already added tensor
+    /// `name`, next to its `dtype`, `shape` and `data_offsets`.
+    pub fn insert_tensor_extra(
+        &mut self,
+        name: &str,
+        key: impl Into<String>,
+        value: serde_json::Value,
+    ) -> Result<()> {
+        let key = key.into();
+        if KNOWN_FIELDS.contains(&key.as_str()) {
+            return Err(Error::InvalidHeader(format!(
+                "`{key}` is a standard tensor field and cannot be set as an extra"
+            )));
+        }
+
+        let (_, info) = self
+            .entries
+            .iter_mut()
+            .find(|(entry, _)| entry == name)
+            .ok_or_else(|| Error::TensorNotFound(name.to_string()))?;
+        info.extra_mut().insert(key, value);
+        Ok(())
+    }
+
+    /// Adds a tensor whose bytes are produced by `fill`.
+    ///
+    /// `fill` is called repeatedly with buffers of at most one chunk and must
+    /// fill each one completely; together the buffers cover exactly the
+    /// tensor's byte size in order. Chunks that are entirely zero are skipped
+    /// over rather than written, so on file systems that support it they
+    /// take no disk space.
+    pub fn add<F>(&mut self, name: &str, dtype: Dtype, shape: &[usize], mut fill: F) -> Result<()>
+    where
+        F: FnMut(&mut [u8]) -> io::Result<()>,
+    {
+        let len = self.check_new(name, dtype, shape)?;
+        let start = self.offset;
+
+        let result = (|| -> Result<()> {
+            let mut buffer = vec![0u8; len.min(CHUNK_SIZE as u64) as usize];
+            let zeros = vec![0u8; buffer.len()];
+            let mut out = &self.data;
+            let mut remaining = len;
+
+            while remaining > 0 {
+                let chunk = &mut buffer[..remaining.min(CHUNK_SIZE as u64) as usize];
+                fill(chunk)?;
+                write_sparse(&mut out, chunk, &zeros[..chunk.len()])?;
+                remaining -= chunk.len() as u64;
+            }
+
+            // A trailing hole is only part of the file once its length says so.
+            self.data.set_len(start + len)?;
+            Ok(())
+        })();
+
+        self.commit(name, dtype, shape, start, len, result)
+    }
+
+    /// Adds a tensor by copying exactly its byte size from `reader`.
+    ///
+    /// Returns [`Error::LengthMismatch`] if the reader yields fewer or more
+    /// bytes than `dtype` and `shape` require.
+    pub fn add_from_reader<R: Read>(
+        &mut self,
+        name: &str,
+        dtype: Dtype,
+        shape: &[usize],
+        mut reader: R,
+    ) -> Result<()> {
+        let len = self.check_new(name, dtype, shape)?;
+        let start = self.offset;
+
+        let result = (|| -> Result<()> {
+            let mut out = BufWriter::with_capacity(CHUNK_SIZE, &self.data);
+            let copied = io::copy(&mut reader.by_ref().take(len), &mut out)?;
+            out.flush()?;
+
+            let extra = io::copy(&mut reader, &mut io::sink())?;
+            if copied != len || extra != 0 {
+                return Err(Error::LengthMismatch {
+                    name: name.to_string(),
+                    expected: len,
+                    actual: copied + extra,
+                });
+            }
+
+            Ok(())
+        })();
+
+        self.commit(name, dtype, shape, start, len, result)
+    }
+
+    /// Adds an already loaded tensor.
+    pub fn add_tensor(&mut self, name: &str, tensor: &Tensor) -> Result<()> {
+        self.add_from_reader(name, tensor.dtype(), tensor.shape(), tensor.as_bytes())
+    }
+
+    /// Writes the header and the streamed data to the destination path.
+    pub fn finish(self) -> Result<()> {
+        let mut header = serde_json::Map::new();
+        if !self.metadata.is_empty() {
+            header.insert(
+                "__metadata__".to_string(),
+                serde_json::to_value(&self.metadata)?,
+            );
+        }
+        for (name, entry) in &self.entries {
+            header.insert(name.clone(), serde_json::to_value(entry)?);
+        }
+
+        // Pad the header with spaces so the data section starts 8-byte aligned.
+        let mut header = serde_json::to_vec(&header)?;
+        header.resize(header.len().next_multiple_of(8), b' ');
+
+        let mut out = File::create(&self.path)?;
+        out.write_all(&(header.len() as u64).to_le_bytes())?;
+        out.write_all(&header)?;
+
+        // Copy chunk by chunk so holes in the data file stay holes.
+        let mut data = &self.data;
+        data.seek(SeekFrom::Start(0))?;
+        let mut buffer = vec![0u8; CHUNK_SIZE];
+        let zeros = vec![0u8; CHUNK_SIZE];
+        loop {
+            let n = data.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            write_sparse(&mut out, &buffer[..n], &zeros[..n])?;
+        }
+        out.set_len(8 + header.len() as u64 + self.offset)?;
+
+        Ok(())
+    }
+
+    fn check_new(&self, name: &str, dtype: Dtype, shape: &[usize]) -> Result<u64> {
+        if name == "__metadata__" {
+            return Err(Error::InvalidHeader(
+                "`__metadata__` is the header's metadata key and cannot name a tensor".to_string(),
+            ));
+        }
+        if self.names.contains(name) {
+            return Err(Error::DuplicateTensor(name.to_string()));
+        }
+        dtype.byte_len(shape)
+    }
+
+    /// Records a successfully written tensor, or rolls the data file back to
+    /// `start` if writing failed part way.
+    fn commit(
+        &mut self,
+        name: &str,
+        dtype: Dtype,
+        shape: &[usize],
+        start: u64,
+        len: u64,
+        result: Result<()>,
+    ) -> Result<()> {
+        if let Err(err) = result {
+            self.data.set_len(start)?;
+            self.data.seek(SeekFrom::Start(start))?;
+            return Err(err);
+        }
+
+        self.offset = start + len;
+        self.names.insert(name.to_string());
+        self.entries.push((
+            name.to_string(),
+            TensorInfo::new(dtype, shape.to_vec(), start, self.offset),
+        ));
+
+        Ok(())
+    }
+}
+
+/// Writes `chunk` at the current position of `out`, or seeks past it if it
+/// equals `zeros`, leaving a hole.
+fn write_sparse<W: Write + Seek>(out: &mut W, chunk: &[u8], zeros: &[u8]) -> io::Result<()> {
+    if chunk == zeros {
+        out.seek(SeekFrom::Current(chunk.len() as i64))?;
+        Ok(())
+    } else {
+        out.write_all(chunk)
+    }
+}
+
+impl Drop for StreamWriter {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.temp_path);
+    }
+}
@@ -0,0 +1,203 @@
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{
+    Bf16Policy, Dtype, Error, LazyReader, NormKind, Reader, ReaderOptions, SharedTensor,
+};
+use std::path::{Path, PathBuf};
+
+/// A model whose output projection is tied to its embedding: both entries
+/// declare the same bytes.
+fn tied() -> FixtureBuilder {
+    FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[4, 3], Fill::Random(5))
+        .tensor("norm.weight", Dtype::F16, &[3], Fill::Constant(1.0))
+        .tensor("lm_head.weight", Dtype::F32, &[4, 3], Fill::Random(6))
+        .overlap_offsets("lm_head.weight")
+}
+
+fn write(dir: &Path, builder: FixtureBuilder) -> PathBuf {
+    let path = dir.join("model.safetensors");
+    builder.write(&path).unwrap();
+    path
+}
+
+fn header_len(path: &Path) -> u64 {
+    Reader::from_file(path).unwrap().header_len().unwrap()
+}
+
+#[test]
+fn tied_embeddings_share_one_allocation() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), tied());
+    let options = ReaderOptions::default().allow_aliases(true);
+
+    let shared = Reader::from_file_shared(&path, &options).unwrap();
+    let (embed, head) = (&shared["embed.weight"], &shared["lm_head.weight"]);
+    assert!(SharedTensor::ptr_eq(embed, head));
+    assert_eq!(SharedTensor::strong_count(embed), 2);
+    assert_eq!(embed.to_f64(), head.to_f64());
+    assert_eq!(embed.shape(), [4, 3]);
+
+    let plan = options.plan(&path).unwrap();
+    let head_plan = plan
+        .tensors
+        .iter()
+        .find(|t| t.name == "lm_head.weight")
+        .unwrap();
+    assert_eq!(head_plan.alias_of.as_deref(), Some("embed.weight"));
+    assert_eq!(plan.bytes_read, 8 + header_len(&path) + 48 + 6);
+
+    // Without the option each name gets its own copy of the same bytes.
+    let separate = Reader::from_file_shared(&path, &ReaderOptions::default()).unwrap();
+    let (embed, head) = (&separate["embed.weight"], &separate["lm_head.weight"]);
+    assert!(!SharedTensor::ptr_eq(embed, head));
+    assert_eq!(embed.to_f64(), head.to_f64());
+}
+
+#[test]
+fn owned_and_in_memory_loads_keep_one_copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), tied());
+    let options = ReaderOptions::default().allow_aliases(true);
+    let from_file = Reader::from_file_with(&path, &options).unwrap();
+    let from_bytes = Reader::from_bytes_with(&tied().to_bytes(), &options).unwrap();
+    for reader in [&from_file, &from_bytes] {
+        assert_eq!(reader.tensors.len(), 2);
+        assert!(!reader.tensors.contains_key("lm_head.weight"));
+        assert_eq!(reader.aliases["lm_head.weight"], "embed.weight");
+        let (embed, head) = (
+            reader.tensor("embed.weight").unwrap(),
+            reader.tensor("lm_head.weight").unwrap(),
+        );
+        assert!(std::ptr::eq(embed, head));
+        assert_eq!(head.shape(), [4, 3]);
+        assert!(reader.tensor("missing").is_none());
+    }
+
+    // The plan counts the shared data once.
+    let plan = options.plan(&path).unwrap();
+    let stored: u64 = from_file
+        .tensors
+        .values()
+        .map(|tensor| tensor.as_bytes().len() as u64)
+        .sum();
+    assert_eq!(plan.resident_bytes, header_len(&path) + stored);
+    assert_eq!(plan.resident_bytes, header_len(&path) + 48 + 6);
+
+    // Without the option each name gets its own copy of the same bytes.
+    let separate = Reader::from_file(&path).unwrap();
+    assert!(separate.aliases.is_empty());
+    assert_eq!(
+        separate.tensors["lm_head.weight"].as_bytes(),
+        separate.tensors["embed.weight"].as_bytes()
+    );
+}
+
+#[test]
+fn whole_reader_results_cover_aliases() {
+    let options = ReaderOptions::default().allow_aliases(true);
+    let reader = Reader::from_bytes_with(&tied().to_bytes(), &options).unwrap();
+
+    let norms = reader.norms(NormKind::L2);
+    assert_eq!(norms.len(), 3);
+    assert_eq!(norms["lm_head.weight"], norms["embed.weight"]);
+    let histograms = reader.histograms(4);
+    assert_eq!(histograms["lm_head.weight"], histograms["embed.weight"]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let npz = dir.path().join("model.npz");
+    reader.write_npz(&npz, Bf16Policy::Error).unwrap();
+    let back = Reader::from_npz(&npz).unwrap();
+    assert_eq!(back.tensors.len(), 3);
+    assert_eq!(
+        back.tensors["lm_head.weight"].as_bytes(),
+        back.tensors["embed.weight"].as_bytes()
+    );
+}
+
+#[test]
+fn lazy_readers_report_aliases() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), tied());
+    let lazy = LazyReader::open(&path).unwrap();
+    assert_eq!(
+        lazy.aliases().unwrap(),
+        [("lm_head.weight", "embed.weight")]
+    );
+
+    let untied = FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[4, 3], Fill::Random(5))
+        .tensor("lm_head.weight", Dtype::F32, &[4, 3], Fill::Random(6));
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), untied);
+    assert!(LazyReader::open(&path)
+        .unwrap()
+        .aliases()
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn partial_overlaps_are_rejected() {
+    let builder = FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[4, 3], Fill::Random(5))
+        .tensor("scale", Dtype::F32, &[2], Fill::Sequence)
+        .overlap_offsets("scale");
+    let dir = tempfile::tempdir().unwrap();
+    let bytes = builder.to_bytes();
+    let path = dir.path().join("model.safetensors");
+    std::fs::write(&path, &bytes).unwrap();
+    let options = ReaderOptions::default().allow_aliases(true);
+
+    let Err(from_file) = Reader::from_file_with(&path, &options) else {
+        panic!("loaded overlapping tensors from a file");
+    };
+    let Err(from_bytes) = Reader::from_bytes_with(&bytes, &options) else {
+        panic!("loaded overlapping tensors from memory");
+    };
+    for err in [from_file, from_bytes] {
+        assert!(matches!(&err, Error::InvalidHeader(message) if message.contains("overlapping")));
+    }
+    assert!(options.plan(&path).is_err());
+}
+
+#[test]
+fn alias_transposed_differently_is_read_on_its_own() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), tied());
+    let options = ReaderOptions::default()
+        .allow_aliases(true)
+        .transpose_2d(|name, _| name == "lm_head.weight");
+
+    let shared = Reader::from_file_shared(&path, &options).unwrap();
+    let (embed, head) = (&shared["embed.weight"], &shared["lm_head.weight"]);
+    assert!(!SharedTensor::ptr_eq(embed, head));
+    assert_eq!(embed.shape(), [4, 3]);
+    assert_eq!(head.shape(), [3, 4]);
+    assert_eq!(head.to_f64(), embed.transpose(0, 1).unwrap().to_f64());
+
+    let from_file = Reader::from_file_with(&path, &options).unwrap();
+    let from_bytes = Reader::from_bytes_with(&tied().to_bytes(), &options).unwrap();
+    for reader in [&from_file, &from_bytes] {
+        assert_eq!(reader.tensors["embed.weight"].shape(), [4, 3]);
+        assert_eq!(reader.tensors["lm_head.weight"].to_f64(), head.to_f64());
+    }
+
+    let plan = options.plan(&path).unwrap();
+    let head_plan = plan
+        .tensors
+        .iter()
+        .find(|t| t.name == "lm_head.weight")
+        .unwrap();
+    assert_eq!(head_plan.alias_of, None);
+    assert!(head_plan.transposed);
+    assert_eq!(plan.bytes_read, 8 + header_len(&path) + 48 + 6 + 48);
+
+    // Transposing both keeps them shared.
+    let options = options.transpose_2d(|_, shape| shape.len() == 2);
+    let shared = Reader::from_file_shared(&path, &options).unwrap();
+    assert!(SharedTensor::ptr_eq(
+        &shared["embed.weight"],
+        &shared["lm_head.weight"]
+    ));
+    assert_eq!(shared["lm_head.weight"].to_f64(), head.to_f64());
+}
@@ -0,0 +1,86 @@
+use half::f16;
+use safetensors_reader::Tensor;
+
+fn f32s(data: &[f32], shape: &[usize]) -> Tensor {
+    Tensor::from_vec(data.to_vec(), shape.to_vec()).unwrap()
+}
+
+#[test]
+fn identical_and_nearby_tensors_pass() {
+    let a = f32s(&[1.0, -2.0, 3.0, 1e6], &[2, 2]);
+    let result = a.allclose(&a, 0.0, 0.0);
+    assert!(result.passed && result.shape_matches);
+    assert_eq!(result.violations, 0);
+    assert_eq!(result.max_violation, 0.0);
+    assert_eq!(result.max_violation_index, None);
+
+    let b = f32s(&[1.0 + 1e-6, -2.0, 3.0, 1e6 + 5.0], &[2, 2]);
+    assert!(a.allclose(&b, 1e-5, 1e-5).passed);
+    assert!(!a.allclose(&b, 0.0, 1e-5).passed);
+}
+
+#[test]
+fn compares_across_dtypes() {
+    let values = [0.1f32, 0.2, 1.0 / 3.0, 1000.5];
+    let original = f32s(&values, &[4]);
+    let halved =
+        Tensor::from_vec(values.iter().map(|&x| f16::from_f32(x)).collect(), vec![4]).unwrap();
+    assert!(halved.allclose(&original, 1e-3, 0.0).passed);
+    assert!(!halved.allclose(&original, 1e-6, 0.0).passed);
+
+    let ints = Tensor::from_vec(vec![1i64, 2, 3], vec![3]).unwrap();
+    let floats = Tensor::from_vec(vec![1.0f64, 2.0, 3.0], vec![3]).unwrap();
+    assert!(ints.allclose(&floats, 0.0, 0.0).passed);
+}
+
+#[test]
+fn single_violation_reports_its_index() {
+    let a = f32s(&[0.0; 6], &[2, 3]);
+    let mut values = [0.0f32; 6];
+    values[4] = 0.5;
+    let b = f32s(&values, &[2, 3]);
+
+    let result = a.allclose(&b, 0.0, 0.1);
+    assert!(!result.passed);
+    assert_eq!(result.violations, 1);
+    assert_eq!(result.max_violation_index, Some(4));
+    assert!((result.max_violation - 0.4).abs() < 1e-9, "{result:?}");
+}
+
+#[test]
+fn largest_of_several_violations_is_reported() {
+    let a = f32s(&[0.0, 0.0, 0.0, 0.0], &[4]);
+    let b = f32s(&[1.0, 0.0, 3.0, 2.0], &[4]);
+    let result = a.allclose(&b, 0.0, 0.0);
+    assert_eq!(result.violations, 3);
+    assert_eq!(result.max_violation_index, Some(2));
+    assert_eq!(result.max_violation, 3.0);
+}
+
+#[test]
+fn shape_mismatch_fails() {
+    let a = f32s(&[1.0; 6], &[2, 3]);
+    let b = f32s(&[1.0; 6], &[3, 2]);
+    let result = a.allclose(&b, 1.0, 1.0);
+    assert!(!result.passed);
+    assert!(!result.shape_matches);
+    assert_eq!(result.max_violation_index, None);
+}
+
+#[test]
+fn nan_flag_both_ways() {
+    let a = f32s(&[1.0, f32::NAN], &[2]);
+    let b = f32s(&[1.0, f32::NAN], &[2]);
+
+    let strict = a.allclose_with(&b, 0.0, 0.0, false);
+    assert!(!strict.passed);
+    assert_eq!(strict.max_violation_index, Some(1));
+    assert_eq!(strict.max_violation, f64::INFINITY);
+    assert!(!a.allclose(&b, 0.0, 0.0).passed);
+
+    assert!(a.allclose_with(&b, 0.0, 0.0, true).passed);
+
+    // A NaN against a number is never close.
+    let c = f32s(&[1.0, 2.0], &[2]);
+    assert!(!a.allclose_with(&c, 1.0, 1.0, true).passed);
+}
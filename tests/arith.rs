@@ -0,0 +1,119 @@
+use half::bf16;
+use safetensors_reader::{Dtype, Error, Tensor};
+
+/// Large enough to be split across threads.
+const LEN: usize = 100_000;
+
+/// Deterministic weights in roughly [-1, 1) and a small delta, as f32.
+fn lora_pair() -> (Vec<f32>, Vec<f32>) {
+    let weight = (0..LEN)
+        .map(|i| ((i * 7919 % 2000) as f32 - 1000.0) / 1000.0)
+        .collect();
+    let delta = (0..LEN)
+        .map(|i| ((i * 104_729 % 200) as f32 - 100.0) / 5000.0)
+        .collect();
+    (weight, delta)
+}
+
+#[test]
+fn merges_a_delta_into_f32_weights() {
+    let (weight, delta) = lora_pair();
+    let alpha = 0.75;
+    let mut merged = Tensor::from_vec_f32(weight.clone(), vec![100, LEN / 100]).unwrap();
+    let delta_tensor = Tensor::from_vec_f32(delta.clone(), vec![100, LEN / 100]).unwrap();
+    merged.add_scaled(&delta_tensor, alpha).unwrap();
+
+    let merged = merged.as_slice::<f32>().unwrap();
+    for i in 0..LEN {
+        let expected = weight[i] + alpha * delta[i];
+        assert!(
+            (merged[i] - expected).abs() <= f32::EPSILON * weight[i].abs().max(expected.abs()),
+            "{i}: {} vs {expected}",
+            merged[i]
+        );
+    }
+}
+
+#[test]
+fn merges_a_delta_into_bf16_weights_with_one_rounding() {
+    let (weight, delta) = lora_pair();
+    let weight: Vec<bf16> = weight.into_iter().map(bf16::from_f32).collect();
+    let delta: Vec<bf16> = delta.into_iter().map(bf16::from_f32).collect();
+    let alpha = 0.75;
+    let mut merged = Tensor::from_vec_bf16(weight.clone(), vec![LEN]).unwrap();
+    merged
+        .add_scaled(
+            &Tensor::from_vec_bf16(delta.clone(), vec![LEN]).unwrap(),
+            alpha,
+        )
+        .unwrap();
+
+    let merged = merged.as_slice::<bf16>().unwrap();
+    let mut exact = 0;
+    for i in 0..LEN {
+        // The reference is computed in f32 from the stored inputs; the merged
+        // value must be that, rounded once to bf16.
+        let reference = weight[i].to_f32() + alpha * delta[i].to_f32();
+        let error = (merged[i].to_f32() - reference).abs();
+        assert!(
+            error <= weight[i].to_f32().abs().max(reference.abs()) * 2f32.powi(-8),
+            "{i}: {} vs {reference}",
+            merged[i]
+        );
+        exact += usize::from(merged[i] == bf16::from_f32(reference));
+    }
+    // Rounding through f64 and f32 only differ at exact ties.
+    assert!(
+        exact >= LEN - LEN / 1000,
+        "{exact} of {LEN} match the f32 reference"
+    );
+}
+
+#[test]
+fn rejects_mismatched_operands() {
+    let mut weight = Tensor::zeros(Dtype::F32, vec![4, 3]).unwrap();
+
+    let transposed = Tensor::zeros(Dtype::F32, vec![3, 4]).unwrap();
+    match weight.add_scaled(&transposed, 1.0) {
+        Err(Error::ShapeMismatch { left, right }) => {
+            assert_eq!((left, right), (vec![4, 3], vec![3, 4]))
+        }
+        other => panic!("expected ShapeMismatch, got {other:?}"),
+    }
+    let err = weight.add_scaled(&transposed, 1.0).unwrap_err();
+    assert!(
+        err.to_string().contains("[4, 3]") && err.to_string().contains("[3, 4]"),
+        "{err}"
+    );
+
+    let half = Tensor::zeros(Dtype::Bf16, vec![4, 3]).unwrap();
+    assert!(matches!(
+        weight.add_scaled(&half, 1.0),
+        Err(Error::DtypeMismatch {
+            expected: Dtype::F32,
+            actual: Dtype::Bf16
+        })
+    ));
+    assert!(weight.as_slice::<f32>().unwrap().iter().all(|&x| x == 0.0));
+}
+
+#[test]
+fn scales_fills_and_mutates_in_place() {
+    let mut tensor =
+        Tensor::from_vec_bf16([1.0, -2.0, 3.0].map(bf16::from_f32).to_vec(), vec![3]).unwrap();
+    tensor.scale(0.5);
+    assert_eq!(tensor.to_f64(), [0.5, -1.0, 1.5]);
+
+    tensor.as_mut_bf16().unwrap()[1] = bf16::ZERO;
+    assert_eq!(tensor.to_f64(), [0.5, 0.0, 1.5]);
+    assert!(matches!(
+        tensor.as_mut_f32(),
+        Err(Error::DtypeMismatch {
+            expected: Dtype::F32,
+            actual: Dtype::Bf16
+        })
+    ));
+
+    tensor.fill(2.0);
+    assert_eq!(tensor.to_f64(), [2.0; 3]);
+}
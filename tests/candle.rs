@@ -0,0 +1,110 @@
+#![cfg(feature = "candle")]
+
+mod common;
+
+use candle_core::{DType, Device};
+use common::{filled, sequence};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Error, Reader, ReaderOptions};
+
+fn values(tensor: &candle_core::Tensor) -> Vec<f64> {
+    tensor
+        .flatten_all()
+        .unwrap()
+        .to_dtype(DType::F64)
+        .unwrap()
+        .to_vec1()
+        .unwrap()
+}
+
+#[test]
+fn converts_every_supported_dtype_on_cpu() {
+    let cases = [
+        (Dtype::U8, DType::U8),
+        (Dtype::I16, DType::I16),
+        (Dtype::F16, DType::F16),
+        (Dtype::Bf16, DType::BF16),
+        (Dtype::U32, DType::U32),
+        (Dtype::I32, DType::I32),
+        (Dtype::F32, DType::F32),
+        (Dtype::I64, DType::I64),
+        (Dtype::F64, DType::F64),
+    ];
+    for (ours, theirs) in cases {
+        let tensor = filled(ours, &[2, 3, 4], Fill::Random(5));
+        let converted = tensor.to_candle(&Device::Cpu).unwrap();
+        assert!(converted.device().is_cpu(), "{ours:?}");
+        assert_eq!(converted.dtype(), theirs, "{ours:?}");
+        assert_eq!(converted.dims(), [2, 3, 4], "{ours:?}");
+        assert_eq!(values(&converted), tensor.to_f64(), "{ours:?}");
+    }
+}
+
+#[test]
+fn keeps_row_major_order_and_scalars() {
+    let matrix = sequence(Dtype::F32, &[2, 3])
+        .to_candle(&Device::Cpu)
+        .unwrap();
+    assert_eq!(
+        matrix.to_vec2::<f32>().unwrap(),
+        [[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]
+    );
+
+    let scalar = filled(Dtype::I64, &[], Fill::Constant(42.0))
+        .to_candle(&Device::Cpu)
+        .unwrap();
+    assert_eq!(scalar.dims(), [] as [usize; 0]);
+    assert_eq!(scalar.to_scalar::<i64>().unwrap(), 42);
+}
+
+#[test]
+fn rejects_dtypes_candle_lacks() {
+    for dtype in [Dtype::Bool, Dtype::I8, Dtype::U16, Dtype::U64] {
+        let err = sequence(dtype, &[2]).to_candle(&Device::Cpu).unwrap_err();
+        assert!(
+            matches!(err, Error::UnsupportedDtype(_)),
+            "{dtype:?}: {err}"
+        );
+    }
+}
+
+#[test]
+fn varmap_holds_every_tensor() {
+    let bytes = FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[4, 2], Fill::Random(1))
+        .tensor("norm.bias", Dtype::Bf16, &[2], Fill::Constant(0.5))
+        .to_bytes();
+    let reader = Reader::from_bytes(&bytes).unwrap();
+    let varmap = reader.to_candle_varmap(&Device::Cpu).unwrap();
+
+    assert_eq!(varmap.len(), 2);
+    for (name, tensor) in &reader.tensors {
+        assert_eq!(varmap[name].dims(), tensor.shape(), "{name}");
+        assert_eq!(values(&varmap[name]), tensor.to_f64(), "{name}");
+    }
+
+    // A tied output projection is a handle to the embedding's candle tensor.
+    let bytes = FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[4, 2], Fill::Random(1))
+        .tensor("lm_head.weight", Dtype::F32, &[4, 2], Fill::Random(1))
+        .overlap_offsets("lm_head.weight")
+        .to_bytes();
+    let options = ReaderOptions::default().allow_aliases(true);
+    let reader = Reader::from_bytes_with(&bytes, &options).unwrap();
+    let varmap = reader.to_candle_varmap(&Device::Cpu).unwrap();
+    assert_eq!(varmap.len(), 2);
+    assert_eq!(varmap["lm_head.weight"].id(), varmap["embed.weight"].id());
+    assert_eq!(
+        values(&varmap["lm_head.weight"]),
+        values(&varmap["embed.weight"])
+    );
+
+    let bytes = FixtureBuilder::new()
+        .tensor("mask", Dtype::Bool, &[2], Fill::Constant(1.0))
+        .to_bytes();
+    let reader = Reader::from_bytes(&bytes).unwrap();
+    assert!(matches!(
+        reader.to_candle_varmap(&Device::Cpu),
+        Err(Error::UnsupportedDtype(_))
+    ));
+}
@@ -0,0 +1,98 @@
+use half::f16;
+use safetensors_reader::testing::FixtureBuilder;
+use safetensors_reader::{Dtype, Reader, Tensor};
+
+/// Smallest positive f16 subnormal, 2^-24.
+const F16_MIN_SUBNORMAL: f64 = 5.960464477539063e-8;
+
+fn f32s(values: &[f32]) -> Tensor {
+    Tensor::from_vec(values.to_vec(), vec![values.len()]).unwrap()
+}
+
+#[test]
+fn f16_max_is_exact_and_beyond_it_overflows() {
+    let max = f16::MAX.to_f64() as f32;
+    assert_eq!(max, 65504.0);
+    // 65519 still rounds down to the max; 65520 rounds up to infinity.
+    let report = f32s(&[max, -max, 65519.0, 65520.0, -1.0e5, 1.0]).cast_report(Dtype::F16);
+    assert_eq!(report.target, Dtype::F16);
+    assert_eq!(report.elements, 6);
+    assert_eq!(report.overflow, 2);
+    assert_eq!(report.underflow, 0);
+    assert_eq!(report.measured, 4);
+    // Only 65519 -> 65504 loses anything.
+    assert_eq!(report.max_rel_error_index, Some(2));
+    assert!((report.max_rel_error - 15.0 / 65519.0).abs() < 1e-12);
+    assert!(!report.is_lossless());
+
+    let exact = f32s(&[max, -max, 0.5, 1024.0]).cast_report(Dtype::F16);
+    assert!(exact.is_lossless());
+    assert_eq!(exact.overflow, 0);
+}
+
+#[test]
+fn subnormals_and_underflow() {
+    let min = F16_MIN_SUBNORMAL as f32;
+    let report = f32s(&[
+        min,       // exactly the smallest subnormal
+        3.0 * min, // another exact subnormal
+        1.0e-5,    // subnormal in f16, rounded
+        min / 4.0, // rounds to zero
+        -1.0e-9,   // rounds to zero
+        6.2e-5,    // just above the smallest normal, 2^-14
+        0.0,
+    ])
+    .cast_report(Dtype::F16);
+
+    assert_eq!(report.subnormal, 3);
+    assert_eq!(report.underflow, 2);
+    assert_eq!(report.overflow, 0);
+    // Zero is neither measured nor counted as underflow.
+    assert_eq!(report.measured, 6);
+    // Values that vanish have a relative error of exactly 1.
+    assert_eq!(report.max_rel_error, 1.0);
+    assert_eq!(report.max_rel_error_index, Some(3));
+
+    let exact = f32s(&[min, 3.0 * min]).cast_report(Dtype::F16);
+    assert_eq!(exact.subnormal, 2);
+    assert!(exact.is_lossless());
+}
+
+#[test]
+fn nan_and_infinities() {
+    let report = f32s(&[f32::NAN, f32::INFINITY, f32::NEG_INFINITY, 2.0]).cast_report(Dtype::F16);
+    assert_eq!(report.nan, 1);
+    assert_eq!(report.overflow, 0);
+    assert_eq!(report.measured, 1);
+    assert!(report.is_lossless());
+
+    // Integer targets cannot hold NaN, and saturate out-of-range values.
+    let report = f32s(&[f32::NAN, 300.0, -1.0, 2.4]).cast_report(Dtype::U8);
+    assert_eq!(report.nan, 1);
+    assert_eq!(report.overflow, 2);
+    assert!((report.max_rel_error - 0.4 / 2.4).abs() < 1e-6);
+    assert!(!report.is_lossless());
+}
+
+#[test]
+fn reader_report_puts_the_worst_tensor_first() {
+    let bytes = FixtureBuilder::new()
+        .add_tensor("clean", &f32s(&[1.0, 2.0]))
+        .add_tensor("rounded", &f32s(&[0.1, 0.2]))
+        .add_tensor("tiny", &f32s(&[1.0e-9, 1.0]))
+        .add_tensor("huge", &f32s(&[1.0e6, 1.0e-9]))
+        .to_bytes();
+    let summary = Reader::from_bytes(&bytes).unwrap().cast_report(Dtype::F16);
+
+    let order: Vec<&str> = summary
+        .tensors
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(order, ["huge", "tiny", "rounded", "clean"]);
+    assert_eq!(summary.total.elements, 8);
+    assert_eq!(summary.total.overflow, 1);
+    assert_eq!(summary.total.underflow, 2);
+    assert_eq!(summary.total.max_rel_error, 1.0);
+    assert_eq!(summary.total.max_rel_error_index, None);
+}
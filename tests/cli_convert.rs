@@ -0,0 +1,128 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{model, sharded_model, st_inspect, stdout, write};
+use safetensors_reader::{Dtype, Reader};
+use serde_json::{json, Value};
+
+#[test]
+fn converted_file_reloads_within_half_precision_tolerance() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write(dir.path(), "model.safetensors", model());
+    let original = Reader::from_file(&input).unwrap();
+
+    for (to, dtype, rtol) in [("f16", Dtype::F16, 1e-3), ("bf16", Dtype::Bf16, 1e-2)] {
+        let output = dir.path().join(format!("model-{to}.safetensors"));
+        let printed = stdout(&st_inspect(&[&"convert", &"--to", &to, &input, &output]));
+        assert!(printed.starts_with("4 tensors: 62 -> "), "{printed}");
+
+        let converted = Reader::from_file(&output).unwrap();
+        assert_eq!(converted.tensors.len(), original.tensors.len());
+        for (name, tensor) in &original.tensors {
+            let new = &converted.tensors[name];
+            let expected = match tensor.dtype().is_float() {
+                true => dtype,
+                false => tensor.dtype(),
+            };
+            assert_eq!(new.dtype(), expected, "{name}");
+            assert_eq!(new.shape(), tensor.shape(), "{name}");
+            let close = new.allclose(tensor, rtol, 1e-3);
+            assert!(close.passed, "{to} {name}: {close:?}");
+        }
+
+        assert_eq!(converted.metadata["format"], "pt");
+        let record: Value =
+            serde_json::from_str(converted.metadata["conversion"].as_str().unwrap()).unwrap();
+        assert_eq!(record, json!({"to": dtype, "keep_f32": []}));
+    }
+}
+
+#[test]
+fn keep_f32_overrides_the_target() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write(dir.path(), "model.safetensors", model());
+    let output = dir.path().join("out.safetensors");
+    stdout(&st_inspect(&[
+        &"convert",
+        &"--to",
+        &"bf16",
+        &"--keep-f32",
+        &"lm_head.*",
+        &input,
+        &output,
+    ]));
+
+    let converted = Reader::from_file(&output).unwrap();
+    let dtype = |name: &str| converted.tensors[name].dtype();
+    assert_eq!(dtype("model.embed.weight"), Dtype::Bf16);
+    assert_eq!(dtype("model.norm.bias"), Dtype::Bf16);
+    assert_eq!(dtype("lm_head.weight"), Dtype::F32);
+    assert_eq!(dtype("step"), Dtype::I64);
+    let original = Reader::from_file(&input).unwrap();
+    assert_eq!(
+        converted.tensors["lm_head.weight"].to_f64(),
+        original.tensors["lm_head.weight"].to_f64()
+    );
+}
+
+#[test]
+fn dry_run_prints_the_size_change_and_writes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write(dir.path(), "model.safetensors", model());
+    let output = dir.path().join("out.safetensors");
+    let printed = stdout(&st_inspect(&[
+        &"convert",
+        &"--to",
+        &"bf16",
+        &"--keep-f32",
+        &"lm_head.*",
+        &"--dry-run",
+        &input,
+        &output,
+    ]));
+    assert_eq!(
+        printed,
+        "\
+model.embed.weight: F32 -> Bf16, 24 -> 12 bytes
+model.norm.bias: F16 -> Bf16, 6 -> 6 bytes
+lm_head.weight: F32 -> F32, 24 -> 24 bytes
+step: I64 -> I64, 8 -> 8 bytes
+4 tensors: 62 -> 50 bytes (-19.4%)
+"
+    );
+    assert!(!output.exists());
+}
+
+#[test]
+fn converts_a_sharded_checkpoint_into_one_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = sharded_model(dir.path());
+    let output = dir.path().join("out.safetensors");
+    stdout(&st_inspect(&[&"convert", &"--to", &"f16", &index, &output]));
+    let converted = Reader::from_file(&output).unwrap();
+    assert_eq!(converted.tensors.len(), 4);
+    assert_eq!(converted.tensors["model.embed.weight"].dtype(), Dtype::F16);
+}
+
+#[test]
+fn exit_codes_distinguish_io_from_validation_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("out.safetensors");
+    let missing = dir.path().join("missing.safetensors");
+    let run = st_inspect(&[&"convert", &"--to", &"f16", &missing, &output]);
+    assert_eq!(run.status.code(), Some(3));
+
+    let corrupt = write(
+        dir.path(),
+        "corrupt.safetensors",
+        model().bogus_dtype("step"),
+    );
+    let run = st_inspect(&[&"convert", &"--to", &"f16", &corrupt, &output]);
+    assert_eq!(run.status.code(), Some(2));
+
+    let input = write(dir.path(), "model.safetensors", model());
+    let run = st_inspect(&[&"convert", &"--to", &"i32", &input, &output]);
+    assert_eq!(run.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&run.stderr).contains("not a floating-point dtype"));
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{model, sharded_model, st_inspect, stdout, write};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::Dtype;
+use serde_json::{json, Value};
+
+/// [`model`] with `lm_head.weight` filled from a different seed.
+fn perturbed() -> FixtureBuilder {
+    FixtureBuilder::new()
+        .tensor("model.embed.weight", Dtype::F32, &[2, 3], Fill::Sequence)
+        .tensor("model.norm.bias", Dtype::F16, &[3], Fill::Constant(0.5))
+        .tensor("lm_head.weight", Dtype::F32, &[3, 2], Fill::Random(2))
+        .tensor("step", Dtype::I64, &[], Fill::Constant(42.0))
+}
+
+#[test]
+fn identical_pair_matches() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = write(dir.path(), "a.safetensors", model());
+    let b = write(dir.path(), "b.safetensors", model());
+    let printed = stdout(&st_inspect(&[&"diff", &a, &b]));
+    assert!(printed.starts_with("4 tensors compared, largest difference 0 in "));
+    assert!(printed.ends_with("\nmatch\n"), "{printed}");
+}
+
+#[test]
+fn perturbed_values_exit_1_and_name_the_tensor() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = write(dir.path(), "a.safetensors", model());
+    let b = write(dir.path(), "b.safetensors", perturbed());
+    let run = st_inspect(&[&"diff", &a, &b]);
+    assert_eq!(run.status.code(), Some(1));
+    let printed = String::from_utf8(run.stdout).unwrap();
+    let lines: Vec<&str> = printed.lines().collect();
+    assert!(lines[0].starts_with("differs:   lm_head.weight: max abs diff "));
+    assert!(lines[1].ends_with(" in lm_head.weight"));
+    assert_eq!(lines[2], "differ");
+    assert_eq!(lines.len(), 3);
+
+    // Tolerances wide enough for any two draws pass.
+    let run = st_inspect(&[&"diff", &"--atol", &"10", &a, &b]);
+    assert!(run.status.success());
+}
+
+#[test]
+fn header_mismatches_without_reading_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = write(dir.path(), "a.safetensors", model());
+    let b = FixtureBuilder::new()
+        .tensor("model.embed.weight", Dtype::F32, &[3, 2], Fill::Sequence)
+        .tensor("model.norm.bias", Dtype::F16, &[3], Fill::Constant(0.5))
+        .tensor("lm_head.weight", Dtype::F32, &[3, 2], Fill::Random(1))
+        .tensor("extra", Dtype::U8, &[1], Fill::Sequence);
+    let b = write(dir.path(), "b.safetensors", b);
+    let run = st_inspect(&[&"diff", &"--names-only", &a, &b]);
+    assert_eq!(run.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(run.stdout).unwrap(),
+        "\
+only in a: step
+only in b: extra
+mismatch:  model.embed.weight: F32 [2, 3] vs F32 [3, 2]
+differ
+"
+    );
+}
+
+#[test]
+fn converted_dtypes_compare_by_value() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = write(dir.path(), "a.safetensors", model());
+    let b = dir.path().join("b.safetensors");
+    stdout(&st_inspect(&[&"convert", &"--to", &"bf16", &a, &b]));
+
+    let printed = stdout(&st_inspect(&[
+        &"diff", &"--rtol", &"1e-2", &"--atol", &"1e-3", &a, &b,
+    ]));
+    let lines: Vec<&str> = printed.lines().collect();
+    assert_eq!(
+        lines[..3],
+        [
+            "dtype:     model.embed.weight: F32 vs Bf16",
+            "dtype:     model.norm.bias: F16 vs Bf16",
+            "dtype:     lm_head.weight: F32 vs Bf16",
+        ]
+    );
+    assert_eq!(lines.last(), Some(&"match"));
+
+    let run = st_inspect(&[
+        &"diff",
+        &"--rtol",
+        &"1e-2",
+        &"--atol",
+        &"1e-3",
+        &"--strict-dtype",
+        &a,
+        &b,
+    ]);
+    assert_eq!(run.status.code(), Some(1));
+
+    // Values that differ by more than bf16 rounding still fail.
+    let run = st_inspect(&[&"diff", &"--rtol", &"0", &"--atol", &"0", &a, &b]);
+    assert_eq!(run.status.code(), Some(1));
+}
+
+#[test]
+fn accepts_an_index_on_either_side() {
+    let dir = tempfile::tempdir().unwrap();
+    let single = write(dir.path(), "single.safetensors", model());
+    let index = sharded_model(dir.path());
+    stdout(&st_inspect(&[&"diff", &single, &index]));
+    stdout(&st_inspect(&[&"diff", &index, &single]));
+}
+
+#[test]
+fn json_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let a = write(dir.path(), "a.safetensors", model());
+    let b = write(
+        dir.path(),
+        "b.safetensors",
+        FixtureBuilder::new()
+            .tensor("model.embed.weight", Dtype::F64, &[2, 3], Fill::Sequence)
+            .tensor("step", Dtype::I64, &[2], Fill::Constant(42.0)),
+    );
+    let run = st_inspect(&[&"diff", &"--json", &a, &b]);
+    assert_eq!(run.status.code(), Some(1));
+    let report: Value = serde_json::from_slice(&run.stdout).unwrap();
+    assert_eq!(
+        report,
+        json!({
+            "passed": false,
+            "only_in_a": ["model.norm.bias", "lm_head.weight"],
+            "only_in_b": [],
+            "header_mismatches": [{
+                "name": "step",
+                "a": {"dtype": "I64", "shape": []},
+                "b": {"dtype": "I64", "shape": [2]},
+            }],
+            "dtype_changes": [{
+                "name": "model.embed.weight",
+                "a": {"dtype": "F32", "shape": [2, 3]},
+                "b": {"dtype": "F64", "shape": [2, 3]},
+            }],
+            "values": [{
+                "name": "model.embed.weight",
+                "max_abs_diff": 0.0,
+                "passed": true,
+                "violations": 0,
+                "max_violation_index": null,
+            }],
+        })
+    );
+}
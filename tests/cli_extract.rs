@@ -0,0 +1,160 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{model, sharded_model, st_inspect, stdout, write};
+use safetensors_reader::{LazyReader, Reader};
+
+/// Sorted tensor names of the file at `path`, which must load.
+fn names(path: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = Reader::from_file(path)
+        .unwrap()
+        .tensors
+        .into_keys()
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn include_exclude_and_names_select_tensors() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write(dir.path(), "model.safetensors", model());
+    let out = dir.path().join("out.safetensors");
+
+    let printed = stdout(&st_inspect(&[
+        &"extract",
+        &"--out",
+        &out,
+        &"--include",
+        &"model.*",
+        &input,
+    ]));
+    assert_eq!(printed, "2 tensors, 30 bytes\n");
+    assert_eq!(names(&out), ["model.embed.weight", "model.norm.bias"]);
+
+    stdout(&st_inspect(&[
+        &"extract",
+        &"--out",
+        &out,
+        &"--include",
+        &"model.*",
+        &"--exclude",
+        &"*.bias",
+        &input,
+    ]));
+    assert_eq!(names(&out), ["model.embed.weight"]);
+
+    stdout(&st_inspect(&[
+        &"extract",
+        &"--out",
+        &out,
+        &"--name",
+        &"step",
+        &"--name",
+        &"lm_head.weight",
+        &input,
+    ]));
+    assert_eq!(names(&out), ["lm_head.weight", "step"]);
+
+    // Without a selection, everything but the excluded tensors is copied.
+    stdout(&st_inspect(&[
+        &"extract",
+        &"--out",
+        &out,
+        &"--exclude",
+        &"model.*",
+        &input,
+    ]));
+    assert_eq!(names(&out), ["lm_head.weight", "step"]);
+}
+
+#[test]
+fn output_is_a_byte_exact_standalone_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write(dir.path(), "model.safetensors", model());
+    let out = dir.path().join("out.safetensors");
+    stdout(&st_inspect(&[
+        &"extract",
+        &"--out",
+        &out,
+        &"--strip-prefix",
+        &"model.",
+        &"--include",
+        &"model.*",
+        &"--name",
+        &"step",
+        &input,
+    ]));
+
+    let original = Reader::from_file(&input).unwrap();
+    let extracted = Reader::from_file(&out).unwrap();
+    assert_eq!(names(&out), ["embed.weight", "norm.bias", "step"]);
+    for (new, old) in [
+        ("embed.weight", "model.embed.weight"),
+        ("norm.bias", "model.norm.bias"),
+        ("step", "step"),
+    ] {
+        let (new, old) = (&extracted.tensors[new], &original.tensors[old]);
+        assert_eq!(new.dtype(), old.dtype());
+        assert_eq!(new.shape(), old.shape());
+        assert_eq!(new.as_bytes(), old.as_bytes());
+    }
+    assert_eq!(extracted.metadata["format"], "pt");
+
+    let lazy = LazyReader::open(&out).unwrap();
+    let file_len = std::fs::metadata(&out).unwrap().len();
+    assert_eq!(file_len, 8 + lazy.header_len() + 24 + 6 + 8);
+}
+
+#[test]
+fn reads_from_a_sharded_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = sharded_model(dir.path());
+    let out = dir.path().join("out.safetensors");
+    let printed = stdout(&st_inspect(&[
+        &"extract",
+        &"--out",
+        &out,
+        &"--name",
+        &"step",
+        &"--include",
+        &"*.bias",
+        &index,
+    ]));
+    assert_eq!(printed, "2 tensors, 14 bytes\n");
+    let extracted = Reader::from_file(&out).unwrap();
+    assert_eq!(extracted.tensors["step"].to_f64(), [42.0]);
+    assert_eq!(extracted.tensors["model.norm.bias"].to_f64(), [0.5; 3]);
+}
+
+#[test]
+fn empty_selection_fails_unless_allowed() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write(dir.path(), "model.safetensors", model());
+    let out = dir.path().join("out.safetensors");
+
+    let run = st_inspect(&[&"extract", &"--out", &out, &"--include", &"nope.*", &input]);
+    assert_eq!(run.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&run.stderr).contains("nope.*"));
+    assert!(!out.exists());
+
+    let run = st_inspect(&[&"extract", &"--out", &out, &"--name", &"nope", &input]);
+    assert_eq!(run.status.code(), Some(2));
+
+    let printed = stdout(&st_inspect(&[
+        &"extract",
+        &"--out",
+        &out,
+        &"--include",
+        &"nope.*",
+        &"--allow-empty",
+        &input,
+    ]));
+    assert_eq!(printed, "0 tensors, 0 bytes\n");
+    assert!(Reader::from_file(&out).unwrap().tensors.is_empty());
+
+    let missing = dir.path().join("missing.safetensors");
+    let run = st_inspect(&[&"extract", &"--out", &out, &missing]);
+    assert_eq!(run.status.code(), Some(3));
+}
@@ -0,0 +1,209 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use common::{model, sharded_model, st_inspect, stdout, write};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::Dtype;
+use serde_json::{json, Value};
+use std::process::{Command, Stdio};
+
+#[test]
+fn prints_header_table_in_offset_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "model.safetensors", model());
+    assert_eq!(
+        stdout(&st_inspect(&[&path])),
+        "\
+name                dtype  shape   params  bytes
+model.embed.weight  F32    [2, 3]       6     24
+model.norm.bias     F16    [3]          3      6
+lm_head.weight      F32    [3, 2]       6     24
+step                I64    []           1      8
+
+4 tensors, 16 parameters, 62 bytes
+
+metadata:
+  format: pt
+"
+    );
+}
+
+#[test]
+fn prints_shard_files_for_an_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = sharded_model(dir.path());
+    assert_eq!(
+        stdout(&st_inspect(&[&index])),
+        "\
+name                dtype  shape   params  bytes  file
+model.embed.weight  F32    [2, 3]       6     24  model-00001-of-00002.safetensors
+step                I64    []           1      8  model-00001-of-00002.safetensors
+model.norm.bias     F16    [3]          3      6  model-00002-of-00002.safetensors
+lm_head.weight      F32    [3, 2]       6     24  model-00002-of-00002.safetensors
+
+4 tensors, 16 parameters, 62 bytes
+
+metadata:
+  total_size: 62
+"
+    );
+}
+
+#[test]
+fn json_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "model.safetensors", model());
+    let report: Value = serde_json::from_str(&stdout(&st_inspect(&[&path, &"--json"]))).unwrap();
+    let entry = |name: &str, dtype: &str, shape: &[usize], params: usize, bytes: u64| {
+        json!({
+            "name": name,
+            "dtype": dtype,
+            "shape": shape,
+            "params": params,
+            "bytes": bytes,
+        })
+    };
+    assert_eq!(
+        report,
+        json!({
+            "tensors": [
+                entry("model.embed.weight", "F32", &[2, 3], 6, 24),
+                entry("model.norm.bias", "F16", &[3], 3, 6),
+                entry("lm_head.weight", "F32", &[3, 2], 6, 24),
+                entry("step", "I64", &[], 1, 8),
+            ],
+            "total_tensors": 4,
+            "total_params": 16,
+            "total_bytes": 62,
+            "metadata": {"format": "pt"},
+        })
+    );
+
+    let index = sharded_model(dir.path());
+    let report: Value = serde_json::from_str(&stdout(&st_inspect(&[&index, &"--json"]))).unwrap();
+    assert_eq!(report["tensors"][1]["name"], "step");
+    assert_eq!(
+        report["tensors"][1]["file"],
+        "model-00001-of-00002.safetensors"
+    );
+    assert_eq!(report["total_bytes"], 62);
+}
+
+#[test]
+fn reports_aliases_and_rejects_partial_overlaps() {
+    let dir = tempfile::tempdir().unwrap();
+    let tied = FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[2, 3], Fill::Sequence)
+        .tensor("lm_head.weight", Dtype::F32, &[2, 3], Fill::Sequence)
+        .overlap_offsets("lm_head.weight");
+    let path = write(dir.path(), "tied.safetensors", tied);
+    assert_eq!(
+        stdout(&st_inspect(&[&path])),
+        "\
+name            dtype  shape   params  bytes
+embed.weight    F32    [2, 3]       6     24
+lm_head.weight  F32    [2, 3]       6     24
+
+2 tensors, 12 parameters, 48 bytes
+
+aliases:
+  lm_head.weight -> embed.weight
+"
+    );
+    let report: Value = serde_json::from_str(&stdout(&st_inspect(&[&path, &"--json"]))).unwrap();
+    assert_eq!(report["tensors"][0].get("alias_of"), None);
+    assert_eq!(report["tensors"][1]["alias_of"], "embed.weight");
+
+    let partial = FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[2, 3], Fill::Sequence)
+        .tensor("scale", Dtype::F32, &[2], Fill::Sequence)
+        .overlap_offsets("scale");
+    let path = write(dir.path(), "partial.safetensors", partial);
+    let output = st_inspect(&[&path]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("overlapping"));
+}
+
+#[test]
+fn prints_head_and_stats_of_one_tensor() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "model.safetensors", model());
+    assert_eq!(
+        stdout(&st_inspect(&[
+            &path,
+            &"--tensor",
+            &"model.embed.weight",
+            &"--head",
+            &"4"
+        ])),
+        "\
+name:  model.embed.weight
+dtype: F32
+shape: [2, 3]
+first 4 values: [0.0, 1.0, 2.0, 3.0]
+min: 0.0
+max: 5.0
+mean: 2.5
+l2_norm: 7.416198487095663
+nan: 0
+inf: 0
+"
+    );
+
+    let index = sharded_model(dir.path());
+    let report: Value = serde_json::from_str(&stdout(&st_inspect(&[
+        &index,
+        &"--tensor",
+        &"model.norm.bias",
+        &"--json",
+    ])))
+    .unwrap();
+    assert_eq!(report["head"], json!([0.5, 0.5, 0.5]));
+    assert_eq!(report["stats"]["mean"], 0.5);
+}
+
+#[test]
+fn exit_codes_distinguish_io_from_invalid_input() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = st_inspect(&[&dir.path().join("missing.safetensors")]);
+    assert_eq!(missing.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&missing.stderr).starts_with("st-inspect: "));
+
+    let path = write(dir.path(), "model.safetensors", model());
+    let unknown = st_inspect(&[&path, &"--tensor", &"nope"]);
+    assert_eq!(unknown.status.code(), Some(2));
+    let corrupt = write(
+        dir.path(),
+        "corrupt.safetensors",
+        model().bogus_dtype("step"),
+    );
+    assert_eq!(st_inspect(&[&corrupt]).status.code(), Some(2));
+}
+
+#[test]
+fn closed_stdout_is_a_clean_exit() {
+    let dir = tempfile::tempdir().unwrap();
+    // Enough rows to fill the pipe, so writes fail once the reader is gone.
+    let builder = (0..5000).fold(FixtureBuilder::new(), |builder, i| {
+        builder.tensor(
+            &format!("layer.{i}.weight"),
+            Dtype::U8,
+            &[1],
+            Fill::Sequence,
+        )
+    });
+    let path = write(dir.path(), "many.safetensors", builder);
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_st-inspect"))
+        .arg(&path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{:?}", output.status);
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "");
+}
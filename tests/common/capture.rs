@@ -0,0 +1,135 @@
+//! A subscriber that records spans and events for tests to inspect.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record as Values};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+
+/// A span when it was created, or an event, with its fields formatted.
+#[derive(Clone, Debug)]
+pub struct Record {
+    /// True for a span, false for an event.
+    pub span: bool,
+    /// The span's name, or the event's message.
+    pub name: String,
+    pub level: Level,
+    /// Name of the parent span, if any.
+    pub parent: Option<String>,
+    /// Fields recorded so far, including ones recorded after creation.
+    pub fields: BTreeMap<String, String>,
+}
+
+impl Record {
+    pub fn field(&self, name: &str) -> &str {
+        &self.fields[name]
+    }
+}
+
+/// Spans and events in the order they were created.
+#[derive(Clone, Debug, Default)]
+pub struct Records(pub Vec<Record>);
+
+impl Records {
+    pub fn spans<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Record> {
+        self.0.iter().filter(move |r| r.span && r.name == name)
+    }
+
+    pub fn events<'a>(&'a self, message: &'a str) -> impl Iterator<Item = &'a Record> {
+        self.0.iter().filter(move |r| !r.span && r.name == message)
+    }
+
+    /// Names of the spans, in creation order.
+    pub fn span_names(&self) -> Vec<&str> {
+        self.0
+            .iter()
+            .filter(|r| r.span)
+            .map(|r| r.name.as_str())
+            .collect()
+    }
+}
+
+/// Runs `f` with a capturing subscriber as the default on this thread and
+/// returns what it recorded, from any thread that used that subscriber.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Records) {
+    let layer = CaptureLayer::default();
+    let state = layer.state.clone();
+    let subscriber = tracing_subscriber::registry().with(layer);
+    let result = tracing::subscriber::with_default(subscriber, f);
+    let records = state.lock().unwrap().records.clone();
+    (result, Records(records))
+}
+
+#[derive(Default)]
+struct State {
+    records: Vec<Record>,
+    /// Index into `records` of each live span.
+    spans: HashMap<Id, usize>,
+}
+
+#[derive(Default)]
+struct CaptureLayer {
+    state: Arc<Mutex<State>>,
+}
+
+struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for CaptureLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = BTreeMap::new();
+        attrs.record(&mut Fields(&mut fields));
+        let parent = ctx
+            .span(id)
+            .and_then(|span| span.parent())
+            .map(|parent| parent.name().to_string());
+        let mut state = self.state.lock().unwrap();
+        let index = state.records.len();
+        state.records.push(Record {
+            span: true,
+            name: attrs.metadata().name().to_string(),
+            level: *attrs.metadata().level(),
+            parent,
+            fields,
+        });
+        state.spans.insert(id.clone(), index);
+    }
+
+    fn on_record(&self, id: &Id, values: &Values<'_>, _: Context<'_, S>) {
+        let mut state = self.state.lock().unwrap();
+        let index = state.spans[id];
+        values.record(&mut Fields(&mut state.records[index].fields));
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = BTreeMap::new();
+        event.record(&mut Fields(&mut fields));
+        let parent = ctx
+            .event_span(event)
+            .map(|parent| parent.name().to_string());
+        self.state.lock().unwrap().records.push(Record {
+            span: false,
+            name: fields.remove("message").unwrap_or_default(),
+            level: *event.metadata().level(),
+            parent,
+            fields,
+        });
+    }
+
+    fn on_close(&self, id: Id, _: Context<'_, S>) {
+        self.state.lock().unwrap().spans.remove(&id);
+    }
+}
@@ -0,0 +1,118 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+#[cfg(feature = "tracing")]
+pub mod capture;
+
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Reader, Tensor};
+#[cfg(feature = "cli")]
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "cli")]
+use std::process::{Command, Output};
+
+pub const DTYPES: [Dtype; 13] = [
+    Dtype::Bool,
+    Dtype::U8,
+    Dtype::I8,
+    Dtype::U16,
+    Dtype::I16,
+    Dtype::F16,
+    Dtype::Bf16,
+    Dtype::U32,
+    Dtype::I32,
+    Dtype::F32,
+    Dtype::U64,
+    Dtype::I64,
+    Dtype::F64,
+];
+
+/// A tensor filled as by [`FixtureBuilder::tensor`].
+pub fn filled(dtype: Dtype, shape: &[usize], fill: Fill) -> Tensor {
+    let bytes = FixtureBuilder::new()
+        .tensor("t", dtype, shape, fill)
+        .to_bytes();
+    Reader::from_bytes(&bytes)
+        .unwrap()
+        .tensors
+        .remove("t")
+        .unwrap()
+}
+
+/// `0, 1, 2, ...` in row-major order.
+pub fn sequence(dtype: Dtype, shape: &[usize]) -> Tensor {
+    filled(dtype, shape, Fill::Sequence)
+}
+
+/// Writes `builder` to `dir/name` and returns the path.
+pub fn write(dir: &Path, name: &str, builder: FixtureBuilder) -> PathBuf {
+    let path = dir.join(name);
+    builder.write(&path).unwrap();
+    path
+}
+
+/// The checkpoint used by the CLI tests, as a single file.
+pub fn model() -> FixtureBuilder {
+    FixtureBuilder::new()
+        .tensor("model.embed.weight", Dtype::F32, &[2, 3], Fill::Sequence)
+        .tensor("model.norm.bias", Dtype::F16, &[3], Fill::Constant(0.5))
+        .tensor("lm_head.weight", Dtype::F32, &[3, 2], Fill::Random(1))
+        .tensor("step", Dtype::I64, &[], Fill::Constant(42.0))
+        .metadata("format", "pt")
+}
+
+/// Splits the tensors of [`model`] across two shard files with an index
+/// naming them, and returns the index path.
+pub fn sharded_model(dir: &Path) -> PathBuf {
+    let full = Reader::from_bytes(&model().to_bytes()).unwrap();
+    let shard = |names: &[&str]| {
+        names.iter().fold(FixtureBuilder::new(), |builder, &name| {
+            builder.add_tensor(name, &full.tensors[name])
+        })
+    };
+    write(
+        dir,
+        "model-00001-of-00002.safetensors",
+        shard(&["model.embed.weight", "step"]),
+    );
+    write(
+        dir,
+        "model-00002-of-00002.safetensors",
+        shard(&["model.norm.bias", "lm_head.weight"]),
+    );
+
+    let index = serde_json::json!({
+        "metadata": {"total_size": 62},
+        "weight_map": {
+            "model.embed.weight": "model-00001-of-00002.safetensors",
+            "step": "model-00001-of-00002.safetensors",
+            "model.norm.bias": "model-00002-of-00002.safetensors",
+            "lm_head.weight": "model-00002-of-00002.safetensors",
+        },
+    });
+    let path = dir.join("model.safetensors.index.json");
+    std::fs::write(&path, index.to_string()).unwrap();
+    path
+}
+
+/// Runs the `st-inspect` binary with `args`.
+#[cfg(feature = "cli")]
+pub fn st_inspect(args: &[&dyn AsRef<OsStr>]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_st-inspect"))
+        .args(args.iter().map(|arg| arg.as_ref()))
+        .output()
+        .unwrap()
+}
+
+/// The stdout of a run, which must have succeeded.
+#[cfg(feature = "cli")]
+pub fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "status {:?}, stderr: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
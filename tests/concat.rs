@@ -0,0 +1,121 @@
+mod common;
+
+use common::{filled, sequence, DTYPES};
+use safetensors_reader::testing::Fill;
+use safetensors_reader::{Dtype, Error, Tensor};
+
+#[test]
+fn concatenates_along_dim_0_for_every_dtype() {
+    for dtype in DTYPES {
+        let a = sequence(dtype, &[2, 3]);
+        let b = filled(dtype, &[1, 3], Fill::Constant(1.0));
+        let joined = Tensor::concat(&[&a, &b], 0).unwrap();
+        assert_eq!(joined.dtype(), dtype);
+        assert_eq!(joined.shape(), [3, 3]);
+        // Along dim 0 the inputs are laid end to end.
+        assert_eq!(
+            joined.as_bytes(),
+            [a.as_bytes(), b.as_bytes()].concat(),
+            "{dtype:?}"
+        );
+    }
+}
+
+#[test]
+fn concatenates_along_dim_1_for_every_dtype() {
+    for dtype in DTYPES {
+        let a = sequence(dtype, &[2, 2]);
+        let b = filled(dtype, &[2, 1], Fill::Constant(1.0));
+        let joined = Tensor::concat(&[&a, &b], 1).unwrap();
+        assert_eq!(joined.shape(), [2, 3]);
+        // Rows interleave: [a00, a01, b0], [a10, a11, b1].
+        let (a, b) = (a.to_f64(), b.to_f64());
+        assert_eq!(
+            joined.to_f64(),
+            [a[0], a[1], b[0], a[2], a[3], b[1]],
+            "{dtype:?}"
+        );
+    }
+}
+
+#[test]
+fn concat_then_split_round_trips() {
+    for dim in 0..3 {
+        let sizes = [3, 1, 5];
+        let parts: Vec<Tensor> = sizes
+            .iter()
+            .zip(1..)
+            .map(|(&len, seed)| {
+                let mut shape = [2, 3, 4];
+                shape[dim] = len;
+                filled(Dtype::F32, &shape, Fill::Random(seed))
+            })
+            .collect();
+        let refs: Vec<&Tensor> = parts.iter().collect();
+
+        let joined = Tensor::concat(&refs, dim).unwrap();
+        let mut shape = [2, 3, 4];
+        shape[dim] = 9;
+        assert_eq!(joined.shape(), shape, "{dim}");
+        let split = joined.split(dim, &sizes).unwrap();
+        for (piece, part) in split.iter().zip(&parts) {
+            assert_eq!(piece.shape(), part.shape(), "{dim}");
+            assert_eq!(piece.as_bytes(), part.as_bytes(), "{dim}");
+        }
+    }
+
+    let single = sequence(Dtype::I32, &[2, 3]);
+    let joined = Tensor::concat(&[&single], 1).unwrap();
+    assert_eq!(joined.as_bytes(), single.as_bytes());
+}
+
+#[test]
+fn rejects_mismatched_dtypes() {
+    let a = sequence(Dtype::F32, &[2, 2]);
+    let b = sequence(Dtype::F32, &[2, 2]);
+    let c = sequence(Dtype::F16, &[2, 2]);
+    match Tensor::concat(&[&a, &b, &c], 0) {
+        Err(Error::ConcatDtype {
+            input,
+            expected,
+            actual,
+        }) => assert_eq!((input, expected, actual), (2, Dtype::F32, Dtype::F16)),
+        other => panic!("expected ConcatDtype, got {other:?}"),
+    }
+}
+
+#[test]
+fn rejects_mismatched_shapes() {
+    let a = sequence(Dtype::F32, &[2, 3]);
+    let wider = sequence(Dtype::F32, &[2, 4]);
+    match Tensor::concat(&[&a, &wider], 0) {
+        Err(Error::ConcatShape {
+            input,
+            dim,
+            expected,
+            actual,
+        }) => {
+            assert_eq!((input, dim), (1, Some(1)));
+            assert_eq!(expected, [2, 3]);
+            assert_eq!(actual, [2, 4]);
+        }
+        other => panic!("expected ConcatShape, got {other:?}"),
+    }
+
+    // A different rank has no single mismatched dim.
+    let flat = sequence(Dtype::F32, &[6]);
+    assert!(matches!(
+        Tensor::concat(&[&a, &flat], 0),
+        Err(Error::ConcatShape {
+            input: 1,
+            dim: None,
+            ..
+        })
+    ));
+
+    assert!(matches!(
+        Tensor::concat(&[&a, &a], 2),
+        Err(Error::DimOutOfRange { dim: 2, .. })
+    ));
+    assert!(matches!(Tensor::concat(&[], 0), Err(Error::EmptyConcat)));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::{sharded_model, write, DTYPES};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{LazyReader, Reader, ShardedReader, Tensor, TensorSource};
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+const THREADS: usize = 8;
+const ROUNDS: usize = 4;
+
+const fn assert_send_sync<T: Send + Sync>() {}
+const _: () = assert_send_sync::<LazyReader>();
+const _: () = assert_send_sync::<ShardedReader>();
+const _: () = assert_send_sync::<Arc<LazyReader>>();
+
+fn assert_same(name: &str, loaded: &Tensor, eager: &Tensor) {
+    assert_eq!(loaded.dtype(), eager.dtype(), "{name}");
+    assert_eq!(loaded.shape(), eager.shape(), "{name}");
+    assert!(loaded.as_bytes() == eager.as_bytes(), "{name}");
+}
+
+/// Loads overlapping subsets of `names` from `reader` on [`THREADS`] threads
+/// released together, and checks every result against `eager`.
+fn stress<R: TensorSource + Send + Sync + 'static>(reader: Arc<R>, eager: Arc<Reader>) {
+    let names: Arc<Vec<String>> = Arc::new(reader.names().iter().map(|n| n.to_string()).collect());
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let (reader, eager, names, barrier) = (
+                reader.clone(),
+                eager.clone(),
+                names.clone(),
+                barrier.clone(),
+            );
+            thread::spawn(move || {
+                barrier.wait();
+                let mut loads = 0;
+                for round in 0..ROUNDS {
+                    // Each thread takes two thirds of the names, starting at
+                    // a different one each round, so every tensor is loaded
+                    // by several threads at once.
+                    for i in 0..names.len() {
+                        let name = &names[(i + t + round) % names.len()];
+                        if (i + t) % 3 == 0 {
+                            continue;
+                        }
+                        assert_same(name, &reader.load(name).unwrap(), &eager.tensors[name]);
+                        loads += 1;
+                    }
+                }
+                loads
+            })
+        })
+        .collect();
+    let loads: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert!(loads >= THREADS * ROUNDS * names.len() / 2);
+}
+
+#[test]
+fn shared_lazy_reader_matches_eager_loads() {
+    let dir = tempfile::tempdir().unwrap();
+    let builder = DTYPES
+        .iter()
+        .enumerate()
+        .fold(FixtureBuilder::new(), |builder, (i, &dtype)| {
+            builder
+                .tensor(
+                    &format!("small.{i}"),
+                    dtype,
+                    &[3, i + 1],
+                    Fill::Random(i as u64),
+                )
+                .tensor(
+                    &format!("large.{i}"),
+                    dtype,
+                    &[64, 257],
+                    Fill::Random(100 + i as u64),
+                )
+        });
+    let path = write(dir.path(), "model.safetensors", builder);
+
+    let eager = Arc::new(Reader::from_file(&path).unwrap());
+    let reader = Arc::new(LazyReader::open(&path).unwrap());
+    assert_eq!(reader.names().len(), 2 * DTYPES.len());
+    stress(reader, eager);
+}
+
+#[test]
+fn shared_sharded_reader_matches_eager_loads() {
+    let dir = tempfile::tempdir().unwrap();
+    let index = sharded_model(dir.path());
+    let mut eager = Reader::from_file(dir.path().join("model-00001-of-00002.safetensors")).unwrap();
+    let second = Reader::from_file(dir.path().join("model-00002-of-00002.safetensors")).unwrap();
+    eager.tensors.extend(second.tensors);
+
+    let reader = Arc::new(ShardedReader::open(&index).unwrap());
+    stress(reader, Arc::new(eager));
+}
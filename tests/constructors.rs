@@ -0,0 +1,126 @@
+mod common;
+
+use common::DTYPES;
+use half::{bf16, f16};
+use safetensors_reader::{Dtype, Error, LazyReader, StreamWriter, Tensor};
+
+/// One tensor of each dtype, built with the typed constructors.
+fn one_of_each() -> Vec<Tensor> {
+    let shape = || vec![2, 2];
+    vec![
+        Tensor::from_vec_bool(vec![true, false, false, true], shape()).unwrap(),
+        Tensor::from_vec_u8(vec![0, 1, 254, 255], shape()).unwrap(),
+        Tensor::from_vec_i8(vec![-128, -1, 0, 127], shape()).unwrap(),
+        Tensor::from_vec_u16(vec![0, 1, 2, u16::MAX], shape()).unwrap(),
+        Tensor::from_vec_i16(vec![i16::MIN, -1, 0, i16::MAX], shape()).unwrap(),
+        Tensor::from_vec_f16(
+            [0.5, -1.0, 65504.0, 0.0].map(f16::from_f32).to_vec(),
+            shape(),
+        )
+        .unwrap(),
+        Tensor::from_vec_bf16(
+            [0.5, -1.0, 3.0e38, 0.0].map(bf16::from_f32).to_vec(),
+            shape(),
+        )
+        .unwrap(),
+        Tensor::from_vec_u32(vec![0, 1, 2, u32::MAX], shape()).unwrap(),
+        Tensor::from_vec_i32(vec![i32::MIN, -1, 0, i32::MAX], shape()).unwrap(),
+        Tensor::from_vec_f32(vec![0.5, -1.0, f32::MAX, f32::MIN_POSITIVE], shape()).unwrap(),
+        Tensor::from_vec_u64(vec![0, 1, 2, u64::MAX], shape()).unwrap(),
+        Tensor::from_vec_i64(vec![i64::MIN, -1, 0, i64::MAX], shape()).unwrap(),
+        Tensor::from_vec_f64(vec![0.5, -1.0, f64::MAX, 1e-300], shape()).unwrap(),
+    ]
+}
+
+#[test]
+fn builds_every_dtype() {
+    let tensors = one_of_each();
+    for (tensor, dtype) in tensors.iter().zip(DTYPES) {
+        assert_eq!(tensor.dtype(), dtype);
+        assert_eq!(tensor.shape(), [2, 2]);
+        assert_eq!(tensor.numel(), 4);
+        assert_eq!(tensor.as_bytes().len(), 4 * dtype.size(), "{dtype:?}");
+    }
+    assert_eq!(
+        tensors[11].as_slice::<i64>().unwrap(),
+        [i64::MIN, -1, 0, i64::MAX]
+    );
+
+    let scalar = Tensor::from_vec(vec![7u32], vec![]).unwrap();
+    assert_eq!(scalar.shape(), [] as [usize; 0]);
+    let empty = Tensor::from_vec(Vec::<f32>::new(), vec![3, 0]).unwrap();
+    assert_eq!(empty.numel(), 0);
+}
+
+#[test]
+fn rejects_a_length_that_does_not_match_the_shape() {
+    for (len, shape) in [(5, vec![2, 3]), (7, vec![2, 3]), (0, vec![]), (1, vec![0])] {
+        match Tensor::from_vec_f32(vec![0.0; len], shape.clone()) {
+            Err(Error::ElementCount {
+                shape: got,
+                len: got_len,
+            }) => assert_eq!((got, got_len), (shape, len)),
+            other => panic!("{len} for {shape:?}: expected ElementCount, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn rejects_shapes_that_overflow() {
+    let huge = vec![usize::MAX, 2];
+    let overflow = |result: Result<Tensor, Error>| match result {
+        Err(Error::ShapeOverflow(shape)) => shape,
+        other => panic!("expected ShapeOverflow, got {other:?}"),
+    };
+    assert_eq!(overflow(Tensor::from_vec_u8(vec![], huge.clone())), huge);
+    assert_eq!(overflow(Tensor::zeros(Dtype::U8, huge.clone())), huge);
+    // The element count fits, but not its byte size.
+    assert_eq!(
+        overflow(Tensor::full(Dtype::F64, vec![1 << 62], 0.0)),
+        [1 << 62]
+    );
+}
+
+#[test]
+fn zeros_and_full() {
+    for dtype in DTYPES {
+        let zeros = Tensor::zeros(dtype, vec![3, 2]).unwrap();
+        assert_eq!(zeros.dtype(), dtype);
+        assert_eq!(zeros.shape(), [3, 2]);
+        assert!(zeros.as_bytes().iter().all(|&b| b == 0), "{dtype:?}");
+
+        let full = Tensor::full(dtype, vec![4], 1.0).unwrap();
+        assert_eq!(full.to_f64(), [1.0; 4], "{dtype:?}");
+    }
+    // Values are rounded and saturated into the dtype.
+    let saturated = Tensor::full(Dtype::U8, vec![2], 300.7).unwrap();
+    assert_eq!(saturated.as_slice::<u8>().unwrap(), [255, 255]);
+    let rounded = Tensor::full(Dtype::F16, vec![1], 0.1).unwrap();
+    assert_eq!(rounded.as_slice::<f16>().unwrap(), [f16::from_f64(0.1)]);
+}
+
+#[test]
+fn constructed_tensors_round_trip_through_the_writer() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("built.safetensors");
+    let tensors = one_of_each();
+
+    let mut writer = StreamWriter::create(&path).unwrap();
+    for (tensor, dtype) in tensors.iter().zip(DTYPES) {
+        writer.add_tensor(&format!("{dtype:?}"), tensor).unwrap();
+    }
+    writer
+        .add_tensor("zeros", &Tensor::zeros(Dtype::Bf16, vec![3]).unwrap())
+        .unwrap();
+    writer.finish().unwrap();
+
+    let reader = LazyReader::open(&path).unwrap();
+    assert_eq!(reader.names().len(), DTYPES.len() + 1);
+    for (tensor, dtype) in tensors.iter().zip(DTYPES) {
+        let back = reader.load(&format!("{dtype:?}")).unwrap();
+        assert_eq!(back.dtype(), dtype);
+        assert_eq!(back.shape(), tensor.shape());
+        assert_eq!(back.as_bytes(), tensor.as_bytes(), "{dtype:?}");
+    }
+    assert_eq!(reader.load("zeros").unwrap().to_f64(), [0.0; 3]);
+}
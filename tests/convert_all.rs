@@ -0,0 +1,145 @@
+use half::bf16;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{ConvertPolicy, ConvertSummary, Dtype, Error, Reader};
+
+/// Floats of every width, one already bf16, and integer and bool tensors.
+fn mixed() -> Reader {
+    let bytes = FixtureBuilder::new()
+        .tensor("attn.weight", Dtype::F32, &[64, 64], Fill::Random(1))
+        .tensor("attn.bias", Dtype::F16, &[64], Fill::Random(2))
+        .tensor("scale", Dtype::F64, &[3], Fill::Constant(1.0 / 3.0))
+        .tensor("embed", Dtype::Bf16, &[8, 4], Fill::Random(3))
+        .tensor("position_ids", Dtype::I64, &[300], Fill::Sequence)
+        .tensor("vocab", Dtype::U8, &[16], Fill::Sequence)
+        .tensor("mask", Dtype::Bool, &[4], Fill::Sequence)
+        .to_bytes();
+    Reader::from_bytes(&bytes).unwrap()
+}
+
+const FLOATS: [&str; 4] = ["attn.weight", "attn.bias", "scale", "embed"];
+const NON_FLOATS: [&str; 3] = ["position_ids", "vocab", "mask"];
+
+fn total_bytes(reader: &Reader) -> u64 {
+    reader
+        .tensors
+        .values()
+        .map(|t| t.as_bytes().len() as u64)
+        .sum()
+}
+
+/// Every value of `name` is the original rounded once to bf16.
+fn assert_rounded_to_bf16(original: &Reader, converted: &Reader, name: &str) {
+    let tensor = &converted.tensors[name];
+    assert_eq!(tensor.dtype(), Dtype::Bf16, "{name}");
+    assert_eq!(tensor.shape(), original.tensors[name].shape(), "{name}");
+    for (&x, &y) in original.tensors[name]
+        .to_f64()
+        .iter()
+        .zip(tensor.as_slice::<bf16>().unwrap())
+    {
+        assert_eq!(y, bf16::from_f64(x), "{name}");
+        assert!(
+            (y.to_f64() - x).abs() <= x.abs() * 2f64.powi(-8),
+            "{name}: {y} vs {x}"
+        );
+    }
+}
+
+#[test]
+fn skips_integer_and_bool_tensors() {
+    let original = mixed();
+    let mut reader = mixed();
+    let summary = reader
+        .convert_all(Dtype::Bf16, ConvertPolicy::Skip)
+        .unwrap();
+    assert_eq!(
+        summary,
+        ConvertSummary {
+            // The bf16 tensor is already in the target dtype.
+            converted: 3,
+            skipped: 4,
+            bytes: total_bytes(&reader),
+        }
+    );
+    assert_eq!(
+        summary.bytes,
+        total_bytes(&original) - (64 * 64 * 2 + 3 * 6),
+        "f32 and f64 shrink, f16 keeps its size"
+    );
+
+    for name in FLOATS {
+        assert_rounded_to_bf16(&original, &reader, name);
+    }
+    for name in NON_FLOATS {
+        assert_eq!(
+            reader.tensors[name].dtype(),
+            original.tensors[name].dtype(),
+            "{name}"
+        );
+        assert_eq!(
+            reader.tensors[name].as_bytes(),
+            original.tensors[name].as_bytes(),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn converts_integer_and_bool_tensors_when_asked() {
+    let original = mixed();
+    let mut reader = mixed();
+    let summary = reader
+        .convert_all(Dtype::Bf16, ConvertPolicy::Convert)
+        .unwrap();
+    assert_eq!((summary.converted, summary.skipped), (6, 1));
+    assert_eq!(summary.bytes, total_bytes(&reader));
+
+    for name in FLOATS.into_iter().chain(NON_FLOATS) {
+        assert_rounded_to_bf16(&original, &reader, name);
+    }
+    // bf16 holds integers exactly only up to 256.
+    let ids = reader.tensors["position_ids"].to_f64();
+    assert_eq!(ids[256], 256.0);
+    assert_eq!(ids[257], 256.0);
+    assert_eq!(ids[299], 300.0);
+    assert_eq!(reader.tensors["mask"].to_f64(), [0.0, 1.0, 0.0, 1.0]);
+}
+
+#[test]
+fn refuses_integer_and_bool_tensors_before_converting_anything() {
+    let original = mixed();
+    let mut reader = mixed();
+    match reader.convert_all(Dtype::Bf16, ConvertPolicy::Error) {
+        // The first offender by name.
+        Err(Error::NonFloatTensor { name, dtype }) => {
+            assert_eq!((name.as_str(), dtype), ("mask", Dtype::Bool))
+        }
+        other => panic!("expected NonFloatTensor, got {other:?}"),
+    }
+    for (name, tensor) in &original.tensors {
+        assert_eq!(reader.tensors[name].dtype(), tensor.dtype(), "{name}");
+        assert_eq!(reader.tensors[name].as_bytes(), tensor.as_bytes(), "{name}");
+    }
+
+    // Only float tensors left, so the policy has nothing to refuse.
+    for name in NON_FLOATS {
+        reader.tensors.remove(name);
+    }
+    let summary = reader
+        .convert_all(Dtype::Bf16, ConvertPolicy::Error)
+        .unwrap();
+    assert_eq!((summary.converted, summary.skipped), (3, 1));
+}
+
+#[test]
+fn into_dtype_keeps_a_tensor_already_in_the_target_dtype() {
+    let reader = mixed();
+    let embed = reader.tensors["embed"].clone();
+    let data = embed.as_bytes().as_ptr();
+    let same = embed.into_dtype(Dtype::Bf16);
+    assert_eq!(same.as_bytes().as_ptr(), data);
+
+    let widened = same.into_dtype(Dtype::F32);
+    assert_eq!(widened.dtype(), Dtype::F32);
+    assert_eq!(widened.to_f64(), reader.tensors["embed"].to_f64());
+}
@@ -0,0 +1,140 @@
+use safetensors_reader::testing::FixtureBuilder;
+use safetensors_reader::{Error, QuantScheme, Reader, Tensor};
+
+/// A [2, 3] int8 weight: [[-128, 0, 127], [10, -10, 5]].
+fn weight() -> Tensor {
+    Tensor::from_vec(vec![-128i8, 0, 127, 10, -10, 5], vec![2, 3]).unwrap()
+}
+
+fn f32s(values: &[f32], shape: &[usize]) -> Tensor {
+    Tensor::from_vec(values.to_vec(), shape.to_vec()).unwrap()
+}
+
+#[test]
+fn per_tensor_scale_and_zero_point() {
+    let scale = f32s(&[0.5], &[]);
+    let zero_point = Tensor::from_vec(vec![2i8], vec![]).unwrap();
+
+    let scaled = weight().dequantize_with(&scale, None, None).unwrap();
+    assert_eq!(scaled.shape(), [2, 3]);
+    assert_eq!(
+        scaled.as_slice::<f32>().unwrap(),
+        [-64.0, 0.0, 63.5, 5.0, -5.0, 2.5]
+    );
+
+    // (q - 2) * 0.5
+    let shifted = weight()
+        .dequantize_with(&scale, Some(&zero_point), None)
+        .unwrap();
+    assert_eq!(
+        shifted.as_slice::<f32>().unwrap(),
+        [-65.0, -1.0, 62.5, 4.0, -6.0, 1.5]
+    );
+}
+
+#[test]
+fn per_channel_along_rows_and_columns() {
+    // One scale and zero point per row: (q - z[r]) * s[r].
+    let scales = f32s(&[0.5, 2.0], &[2]);
+    let zero_points = Tensor::from_vec(vec![0u8, 5], vec![2, 1]).unwrap();
+    let rows = weight()
+        .dequantize_with(&scales, Some(&zero_points), Some(0))
+        .unwrap();
+    assert_eq!(
+        rows.as_slice::<f32>().unwrap(),
+        [-64.0, 0.0, 63.5, 10.0, -30.0, 0.0]
+    );
+
+    // One scale per column: q * s[c].
+    let scales = f32s(&[1.0, 0.25, -1.0], &[1, 3]);
+    let cols = weight().dequantize_with(&scales, None, Some(1)).unwrap();
+    assert_eq!(
+        cols.as_slice::<f32>().unwrap(),
+        [-128.0, 0.0, -127.0, 10.0, -2.5, -5.0]
+    );
+
+    // A single value still applies to every channel.
+    let single = weight()
+        .dequantize_with(&f32s(&[2.0], &[1]), None, Some(1))
+        .unwrap();
+    assert_eq!(
+        single.as_slice::<f32>().unwrap(),
+        [-256.0, 0.0, 254.0, 20.0, -20.0, 10.0]
+    );
+}
+
+#[test]
+fn per_channel_in_the_middle_of_a_cube() {
+    // A [2, 2, 2] sequence with one scale per index of dim 1.
+    let q = Tensor::from_vec((0u8..8).collect(), vec![2, 2, 2]).unwrap();
+    let scales = f32s(&[1.0, 10.0], &[2]);
+    let out = q.dequantize_with(&scales, None, Some(1)).unwrap();
+    assert_eq!(
+        out.as_slice::<f32>().unwrap(),
+        [0.0, 1.0, 20.0, 30.0, 4.0, 5.0, 60.0, 70.0]
+    );
+}
+
+#[test]
+fn rejects_parameters_that_fit_neither_layout() {
+    let w = weight();
+    for (scales, axis) in [
+        (f32s(&[1.0, 2.0], &[2]), None),
+        (f32s(&[1.0, 2.0], &[2]), Some(1)),
+        (f32s(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], &[2, 3]), Some(1)),
+    ] {
+        assert!(
+            matches!(
+                w.dequantize_with(&scales, None, axis),
+                Err(Error::QuantParamShape { ref shape, axis: got, .. })
+                    if shape == scales.shape() && got == axis
+            ),
+            "{:?} {axis:?}",
+            scales.shape()
+        );
+    }
+    assert!(matches!(
+        w.dequantize_with(&f32s(&[1.0], &[]), None, Some(2)),
+        Err(Error::DimOutOfRange { dim: 2, .. })
+    ));
+}
+
+#[test]
+fn reader_finds_companion_tensors() {
+    let bytes = FixtureBuilder::new()
+        .add_tensor("w", &weight())
+        .add_tensor("w.scale", &f32s(&[0.5, 2.0], &[2]))
+        .add_tensor(
+            "w.zero_point",
+            &Tensor::from_vec(vec![0i8, 5], vec![2]).unwrap(),
+        )
+        .add_tensor("v", &weight())
+        .add_tensor("v.scale", &f32s(&[0.5], &[]))
+        .to_bytes();
+    let reader = Reader::from_bytes(&bytes).unwrap();
+
+    let w = reader
+        .dequantize("w", &QuantScheme::per_channel(0))
+        .unwrap();
+    assert_eq!(
+        w.as_slice::<f32>().unwrap(),
+        [-64.0, 0.0, 63.5, 10.0, -30.0, 0.0]
+    );
+
+    // `v` has no zero point, which the default scheme requires.
+    assert!(matches!(
+        reader.dequantize("v", &QuantScheme::default()),
+        Err(Error::MissingCompanion { ref companion, .. }) if companion == "v.zero_point"
+    ));
+    let scale_only = QuantScheme {
+        zero_point_suffix: None,
+        ..QuantScheme::default()
+    };
+    let v = reader.dequantize("v", &scale_only).unwrap();
+    assert_eq!(v.as_slice::<f32>().unwrap()[2], 63.5);
+
+    assert!(matches!(
+        reader.dequantize("missing", &scale_only),
+        Err(Error::TensorNotFound(_))
+    ));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use std::path::Path;
+
+use common::model;
+use safetensors_reader::{Error, LazyReader, Reader};
+
+const JUNK_BEFORE: usize = 1234;
+const JUNK_AFTER: usize = 777;
+
+/// Writes `payload` between runs of junk bytes and returns its offset.
+fn embed(path: &Path, payload: &[u8]) -> u64 {
+    let mut container = vec![0xAB; JUNK_BEFORE];
+    container.extend_from_slice(payload);
+    container.extend(std::iter::repeat_n(0xCD, JUNK_AFTER));
+    std::fs::write(path, container).unwrap();
+    JUNK_BEFORE as u64
+}
+
+#[test]
+fn reads_a_payload_surrounded_by_junk() {
+    let dir = tempfile::tempdir().unwrap();
+    let payload = model().metadata("format", "pt").to_bytes();
+    let standalone = dir.path().join("model.safetensors");
+    std::fs::write(&standalone, &payload).unwrap();
+    let container = dir.path().join("bundle.bin");
+    let offset = embed(&container, &payload);
+    let len = Some(payload.len() as u64);
+
+    let expected = Reader::from_file(&standalone).unwrap();
+    let embedded = Reader::from_file_at(&container, offset, len).unwrap();
+    assert_eq!(embedded.metadata, expected.metadata);
+    assert_eq!(embedded.tensors.len(), expected.tensors.len());
+    for (name, tensor) in &expected.tensors {
+        assert_eq!(embedded.tensors[name].shape(), tensor.shape());
+        assert_eq!(
+            embedded.tensors[name].as_bytes(),
+            tensor.as_bytes(),
+            "{name}"
+        );
+    }
+
+    let expected = LazyReader::open(&standalone).unwrap();
+    let lazy = LazyReader::open_at(&container, offset, len).unwrap();
+    assert_eq!(lazy.names(), expected.names());
+    assert_eq!(lazy.raw_header(), expected.raw_header());
+    for name in expected.names() {
+        assert_eq!(
+            lazy.info(name).unwrap().data_offsets(),
+            expected.info(name).unwrap().data_offsets()
+        );
+        assert_eq!(
+            lazy.load(name).unwrap().as_bytes(),
+            expected.load(name).unwrap().as_bytes(),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn without_a_length_the_region_runs_to_the_end_of_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let payload = model().to_bytes();
+    let path = dir.path().join("tail.bin");
+    let mut container = vec![0xAB; JUNK_BEFORE];
+    container.extend_from_slice(&payload);
+    std::fs::write(&path, container).unwrap();
+
+    let expected = Reader::from_bytes(&payload).unwrap().tensors.len();
+    let reader = Reader::from_file_at(&path, JUNK_BEFORE as u64, None).unwrap();
+    assert_eq!(reader.tensors.len(), expected);
+    assert_eq!(
+        LazyReader::open_at(&path, JUNK_BEFORE as u64, None)
+            .unwrap()
+            .names()
+            .len(),
+        expected
+    );
+}
+
+#[test]
+fn tensors_may_not_reach_past_the_region() {
+    let dir = tempfile::tempdir().unwrap();
+    let payload = model().to_bytes();
+    let path = dir.path().join("bundle.bin");
+    let offset = embed(&path, &payload);
+    // The junk after the payload would satisfy an unbounded read.
+    let short = Some(payload.len() as u64 - 1);
+
+    let Err(err) = Reader::from_file_at(&path, offset, short) else {
+        panic!("a region one byte short of the payload was accepted");
+    };
+    assert!(
+        matches!(&err, Error::InvalidHeader(message) if message.contains("outside")),
+        "{err}"
+    );
+    let Err(err) = LazyReader::open_at(&path, offset, short) else {
+        panic!("a region one byte short of the payload was accepted");
+    };
+    assert!(
+        matches!(&err, Error::InvalidHeader(message) if message.contains("outside")),
+        "{err}"
+    );
+}
+
+#[test]
+fn rejects_a_region_past_the_end_of_the_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let payload = model().to_bytes();
+    let path = dir.path().join("bundle.bin");
+    let offset = embed(&path, &payload);
+    let file_len = (JUNK_BEFORE + payload.len() + JUNK_AFTER) as u64;
+    let len = payload.len() as u64 + JUNK_AFTER as u64 + 1;
+
+    let Err(err) = Reader::from_file_at(&path, offset, Some(len)) else {
+        panic!("a region past the end of the file was accepted");
+    };
+    assert!(matches!(
+        err,
+        Error::RegionOutOfBounds { offset: o, len: l, file_len: f } if (o, l, f) == (offset, len, file_len)
+    ));
+    assert!(matches!(
+        LazyReader::open_at(&path, file_len + 1, None),
+        Err(Error::RegionOutOfBounds { .. })
+    ));
+}
@@ -0,0 +1,58 @@
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{extract, Dtype, LazyReader, Reader, Selector};
+
+/// Bytes this process has passed through `read`-like calls so far.
+#[cfg(target_os = "linux")]
+fn bytes_read() -> u64 {
+    let io = std::fs::read_to_string("/proc/self/io").unwrap();
+    let line = io.lines().find(|line| line.starts_with("rchar:")).unwrap();
+    line["rchar:".len()..].trim().parse().unwrap()
+}
+
+// The only test in this binary, so no other thread reads while it measures.
+#[cfg(target_os = "linux")]
+#[test]
+fn reads_only_the_selected_byte_ranges() {
+    const MIB: usize = 1 << 20;
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("model.safetensors");
+    let dst = dir.path().join("embed.safetensors");
+    FixtureBuilder::new()
+        .tensor("model.embed.weight", Dtype::F32, &[4, 8], Fill::Random(3))
+        .tensor(
+            "model.layers.0.weight",
+            Dtype::U8,
+            &[MIB],
+            Fill::Constant(1.0),
+        )
+        .tensor("model.embed.bias", Dtype::F32, &[8], Fill::Sequence)
+        .tensor(
+            "model.layers.1.weight",
+            Dtype::U8,
+            &[MIB],
+            Fill::Constant(2.0),
+        )
+        .write(&src)
+        .unwrap();
+    let header_len = LazyReader::open(&src).unwrap().header_len() as usize;
+
+    let before = bytes_read();
+    extract(&src, &dst, &Selector::pattern("model.embed.*")).unwrap();
+    let read = (bytes_read() - before) as usize;
+
+    // The header (possibly twice) plus the 160 selected bytes, and none of
+    // the 2 MiB of layers.
+    let selected = 4 * 8 * 4 + 8 * 4;
+    assert!(read >= selected, "{read} bytes read");
+    assert!(
+        read < 2 * header_len + selected + 64 * 1024,
+        "{read} bytes read"
+    );
+
+    let original = Reader::from_file(&src).unwrap();
+    let extracted = Reader::from_file(&dst).unwrap();
+    assert_eq!(extracted.tensors.len(), 2);
+    for (name, tensor) in &extracted.tensors {
+        assert_eq!(tensor.as_bytes(), original.tensors[name].as_bytes());
+    }
+}
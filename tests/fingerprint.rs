@@ -0,0 +1,150 @@
+use safetensors_reader::header_fingerprint;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// Computed by `tests/fixtures/fingerprint/generate.py` from the documented
+/// canonical form. If this changes, so do the cache keys of every user.
+const PINNED: &str = "86a25ca871c438d3197eaf46b213d39b03b24e2146c1f00edeb876ff74de6963";
+
+fn hex(bytes: [u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Writes `header` padded with `padding` spaces, followed by `data_len`
+/// bytes of `fill`.
+fn write(
+    dir: &Path,
+    name: &str,
+    header: &str,
+    padding: usize,
+    data_len: usize,
+    fill: u8,
+) -> PathBuf {
+    let header = format!("{header}{}", " ".repeat(padding));
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend(std::iter::repeat_n(fill, data_len));
+    let path = dir.join(name);
+    std::fs::write(&path, bytes).unwrap();
+    path
+}
+
+fn base() -> Value {
+    json!({
+        "__metadata__": {"format": "pt", "nested": {"b": 1, "a": [true, null]}},
+        "w": {"dtype": "F32", "shape": [2, 2], "data_offsets": [0, 16]},
+        "b": {"dtype": "F16", "shape": [2], "data_offsets": [16, 20]},
+    })
+}
+
+fn fingerprint_of(dir: &Path, header: &Value) -> [u8; 32] {
+    let path = write(dir, "file.safetensors", &header.to_string(), 0, 20, 0);
+    header_fingerprint(path).unwrap()
+}
+
+#[test]
+fn pinned_test_vector() {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fingerprint/pinned.safetensors");
+    assert_eq!(hex(header_fingerprint(path).unwrap()), PINNED);
+}
+
+#[test]
+fn ignores_key_order_padding_extra_fields_and_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let expected = fingerprint_of(dir.path(), &base());
+
+    // The same entries with every object's keys written in reverse.
+    let reordered = r#"{"b":{"data_offsets":[16,20],"shape":[2],"dtype":"F16"},"w":{"shape":[2,2],"data_offsets":[0,16],"dtype":"F32"},"__metadata__":{"nested":{"a":[true,null],"b":1},"format":"pt"}}"#;
+    let variants = [
+        write(dir.path(), "reordered.safetensors", reordered, 0, 20, 0),
+        write(
+            dir.path(),
+            "padded.safetensors",
+            &base().to_string(),
+            13,
+            20,
+            0,
+        ),
+        write(
+            dir.path(),
+            "pretty.safetensors",
+            &serde_json::to_string_pretty(&base()).unwrap(),
+            0,
+            20,
+            0,
+        ),
+        write(
+            dir.path(),
+            "data.safetensors",
+            &base().to_string(),
+            0,
+            20,
+            0xAB,
+        ),
+    ];
+    for path in &variants {
+        assert_eq!(
+            header_fingerprint(path).unwrap(),
+            expected,
+            "{}",
+            path.display()
+        );
+    }
+
+    let mut extra = base();
+    extra["w"]["quant"] = json!("int4");
+    assert_eq!(fingerprint_of(dir.path(), &extra), expected);
+}
+
+type Edit = fn(&mut Value);
+
+#[test]
+fn changes_with_layout_and_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let expected = fingerprint_of(dir.path(), &base());
+
+    let edits: [(&str, Edit); 6] = [
+        ("shape", |h| h["w"]["shape"] = json!([4, 1])),
+        ("dtype", |h| h["w"]["dtype"] = json!("I32")),
+        ("offsets", |h| {
+            h["w"]["data_offsets"] = json!([4, 20]);
+            h["b"]["data_offsets"] = json!([0, 4]);
+        }),
+        ("metadata value", |h| {
+            h["__metadata__"]["format"] = json!("tf")
+        }),
+        ("nested metadata", |h| {
+            h["__metadata__"]["nested"]["b"] = json!(2)
+        }),
+        ("name", |h| {
+            let b = h.as_object_mut().unwrap().remove("b").unwrap();
+            h["bias"] = b;
+        }),
+    ];
+    for (what, edit) in edits {
+        let mut header = base();
+        edit(&mut header);
+        assert_ne!(fingerprint_of(dir.path(), &header), expected, "{what}");
+    }
+
+    let longer = write(
+        dir.path(),
+        "longer.safetensors",
+        &base().to_string(),
+        0,
+        24,
+        0,
+    );
+    assert_ne!(header_fingerprint(longer).unwrap(), expected, "data length");
+
+    let mut without = base();
+    without.as_object_mut().unwrap().remove("__metadata__");
+    let mut empty = base();
+    empty["__metadata__"] = json!({});
+    assert_ne!(fingerprint_of(dir.path(), &without), expected);
+    assert_eq!(
+        fingerprint_of(dir.path(), &without),
+        fingerprint_of(dir.path(), &empty)
+    );
+}
@@ -0,0 +1,87 @@
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, LazyReader, Reader, ReaderOptions};
+
+fn sample() -> FixtureBuilder {
+    FixtureBuilder::new()
+        .tensor("a", Dtype::F32, &[2, 3], Fill::Sequence)
+        .tensor("b", Dtype::I64, &[4], Fill::Random(7))
+        .tensor("c", Dtype::Bool, &[3], Fill::Sequence)
+        .metadata("format", "pt")
+}
+
+#[test]
+fn builder_output_is_readable() {
+    let reader = Reader::from_bytes(&sample().to_bytes()).unwrap();
+    assert_eq!(reader.tensors.len(), 3);
+    assert_eq!(reader.tensors["a"].to_f64(), [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(reader.tensors["b"].shape(), [4]);
+    assert_eq!(reader.tensors["c"].to_f64(), [0.0, 1.0, 0.0]);
+    assert_eq!(reader.metadata["format"], "pt");
+    assert_eq!(reader.header_len().unwrap() % 8, 0);
+}
+
+#[test]
+fn written_file_matches_bytes() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sample.safetensors");
+    sample().write(&path).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), sample().to_bytes());
+
+    let lazy = LazyReader::open(&path).unwrap();
+    assert_eq!(lazy.names(), ["a", "b", "c"]);
+    let eager = Reader::from_file(&path).unwrap();
+    assert_eq!(
+        lazy.load("b").unwrap().to_f64(),
+        eager.tensors["b"].to_f64()
+    );
+}
+
+#[test]
+fn random_fill_is_deterministic() {
+    let fill = |seed| {
+        let bytes = FixtureBuilder::new()
+            .tensor("x", Dtype::F32, &[16], Fill::Random(seed))
+            .to_bytes();
+        Reader::from_bytes(&bytes).unwrap().tensors["x"].to_f64()
+    };
+    assert_eq!(fill(1), fill(1));
+    assert_ne!(fill(1), fill(2));
+    assert!(fill(1).iter().all(|v| (-1.0..1.0).contains(v)));
+}
+
+#[test]
+fn header_padding_is_ignored() {
+    let bytes = sample().header_padding(13).to_bytes();
+    let reader = Reader::from_bytes(&bytes).unwrap();
+    assert!(reader.raw_header().unwrap().ends_with(&" ".repeat(13)));
+    assert_eq!(reader.tensors.len(), 3);
+}
+
+#[test]
+fn truncated_file_is_rejected() {
+    let len = sample().to_bytes().len();
+    assert!(Reader::from_bytes(&sample().truncate_at(len - 1).to_bytes()).is_err());
+    assert!(Reader::from_bytes(&sample().truncate_at(4).to_bytes()).is_err());
+}
+
+#[test]
+fn bogus_dtype_is_rejected() {
+    assert!(Reader::from_bytes(&sample().bogus_dtype("b").to_bytes()).is_err());
+}
+
+#[test]
+fn duplicate_key_is_rejected() {
+    let Err(err) = Reader::from_bytes(&sample().duplicate_key("b").to_bytes()) else {
+        panic!("duplicate key accepted");
+    };
+    assert!(err.to_string().contains("duplicate tensor `b`"), "{err}");
+}
+
+#[test]
+fn overlapping_offsets_are_rejected_when_aliases_are_checked() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("overlap.safetensors");
+    sample().overlap_offsets("c").write(&path).unwrap();
+    let options = ReaderOptions::default().allow_aliases(true);
+    assert!(Reader::from_file_with(&path, &options).is_err());
+}
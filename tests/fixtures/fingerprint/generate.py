@@ -0,0 +1,41 @@
+"""Writes pinned.safetensors and prints its fingerprint, computed from the
+canonical form documented on `header_fingerprint`.
+
+The header deliberately lists keys out of order, carries an unknown field
+and ends in padding, none of which the fingerprint depends on.
+"""
+
+import hashlib
+import json
+import struct
+
+header = (
+    '{"lm_head.weight":{"shape":[2,2],"dtype":"BF16","data_offsets":[16,24],'
+    '"quant":"none"},'
+    '"__metadata__":{"format":"pt","arch":"tiny \\"test\\""},'
+    '"embed.weight":{"dtype":"F32","data_offsets":[0,16],"shape":[2,2]},'
+    '"step":{"data_offsets":[24,32],"dtype":"I64","shape":[]}}'
+)
+header += " " * ((8 - len(header) % 8) % 8 + 8)
+data = struct.pack("<4f", 0.0, 1.0, 2.0, 3.0)
+data += bytes([0x80, 0x3F, 0x00, 0x40, 0x40, 0x40, 0x80, 0x40])
+data += struct.pack("<q", 42)
+
+with open("pinned.safetensors", "wb") as f:
+    f.write(struct.pack("<Q", len(header)))
+    f.write(header.encode())
+    f.write(data)
+
+parsed = json.loads(header)
+metadata = parsed.pop("__metadata__")
+tensors = [
+    [name, entry["dtype"], entry["shape"], entry["data_offsets"]]
+    for name, entry in sorted(parsed.items(), key=lambda item: item[0].encode())
+]
+canonical = "safetensors-header-fingerprint-v1\n" + json.dumps(
+    {"data_len": len(data), "metadata": metadata, "tensors": tensors},
+    sort_keys=True,
+    separators=(",", ":"),
+    ensure_ascii=False,
+)
+print(hashlib.sha256(canonical.encode()).hexdigest())
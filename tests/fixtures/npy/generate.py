@@ -0,0 +1,66 @@
+"""Writes the .npy/.npz fixtures for tests/npy.rs.
+
+This is an encoder written from the .npy format description, independent of the
+Rust writer; it does not use numpy, and its output has not been compared with
+np.save. Run from this directory.
+"""
+
+import struct
+import zipfile
+
+
+def header(descr, shape, fortran=False, version=1):
+    if len(shape) == 1:
+        shape_text = f"({shape[0]},)"
+    else:
+        shape_text = "(" + ", ".join(map(str, shape)) + ")"
+    order = "True" if fortran else "False"
+    text = f"{{'descr': '{descr}', 'fortran_order': {order}, 'shape': {shape_text}, }}"
+    len_bytes = 2 if version == 1 else 4
+    preamble = 6 + 2 + len_bytes
+    padded = -(-(preamble + len(text) + 1) // 64) * 64 - preamble
+    text = text.ljust(padded - 1) + "\n"
+    data = text.encode("latin1" if version < 3 else "utf8")
+    size = struct.pack("<H" if version == 1 else "<I", len(data))
+    return b"\x93NUMPY" + bytes([version, 0]) + size + data
+
+
+def array(descr, shape, values, fmt, **kwargs):
+    body = b"".join(struct.pack(fmt, v) for v in values)
+    return header(descr, shape, **kwargs) + body
+
+
+SEQ = [0, 1, 2, 3, 4, 5]
+SIGNED = [-3, -2, -1, 0, 1, 2]
+
+FIXTURES = {
+    "f2_2x3.npy": array("<f2", (2, 3), [v / 2 for v in SEQ], "<e"),
+    "f4_2x3.npy": array("<f4", (2, 3), SEQ, "<f"),
+    "f8_3.npy": array("<f8", (3,), [0.5, -1.25, 1e300], "<d"),
+    "i1_2x3.npy": array("|i1", (2, 3), SIGNED, "<b"),
+    "i2_2x3.npy": array("<i2", (2, 3), SIGNED, "<h"),
+    "i4_2x3.npy": array("<i4", (2, 3), SIGNED, "<i"),
+    "i8_2x3.npy": array("<i8", (2, 3), SIGNED, "<q"),
+    "u1_2x3.npy": array("|u1", (2, 3), SEQ, "<B"),
+    "u2_2x3.npy": array("<u2", (2, 3), SEQ, "<H"),
+    "u4_2x3.npy": array("<u4", (2, 3), SEQ, "<I"),
+    "u8_2x3.npy": array("<u8", (2, 3), SEQ, "<Q"),
+    "b1_4.npy": array("|b1", (4,), [True, False, False, True], "<?"),
+    "f4_scalar.npy": array("<f4", (), [7.5], "<f"),
+    "f4_empty.npy": array("<f4", (0, 3), [], "<f"),
+    "f4_v2.npy": array("<f4", (2, 3), SEQ, "<f", version=2),
+    "f4_v3.npy": array("<f4", (2, 3), SEQ, "<f", version=3),
+    "f4_fortran.npy": array("<f4", (2, 3), [0, 3, 1, 4, 2, 5], "<f", fortran=True),
+    "f4_big_endian.npy": array(">f4", (2, 3), SEQ, ">f"),
+}
+
+for name, data in FIXTURES.items():
+    with open(name, "wb") as f:
+        f.write(data)
+
+# One archive with stored members and one with deflated members.
+for name, method in [("stored.npz", zipfile.ZIP_STORED), ("deflated.npz", zipfile.ZIP_DEFLATED)]:
+    with zipfile.ZipFile(name, "w", method) as z:
+        z.writestr("weights.npy", FIXTURES["f4_2x3.npy"])
+        z.writestr("labels.npy", FIXTURES["i8_2x3.npy"])
+        z.writestr("mask.npy", FIXTURES["b1_4.npy"])
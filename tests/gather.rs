@@ -0,0 +1,111 @@
+mod common;
+
+use common::{sequence, write, DTYPES};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Error, LazyReader};
+
+/// The rows `indices` of a `[rows, cols]` sequence, computed by hand.
+fn expected_rows(cols: usize, indices: &[usize]) -> Vec<f64> {
+    indices
+        .iter()
+        .flat_map(|&i| (i * cols..(i + 1) * cols).map(|x| x as f64))
+        .collect()
+}
+
+#[test]
+fn both_paths_gather_the_same_rows() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut fixture = FixtureBuilder::new();
+    for dtype in DTYPES.into_iter().filter(|&d| d != Dtype::Bool) {
+        fixture = fixture.tensor(&format!("{dtype:?}"), dtype, &[10, 3], Fill::Sequence);
+    }
+    let path = write(dir.path(), "embed.safetensors", fixture);
+    let lazy = LazyReader::open(&path).unwrap();
+
+    // Out of order, repeated, adjacent and isolated rows.
+    let indices = [7, 2, 3, 4, 2, 9, 0, 7, 7];
+    for dtype in DTYPES.into_iter().filter(|&d| d != Dtype::Bool) {
+        let name = format!("{dtype:?}");
+        let eager = sequence(dtype, &[10, 3]).gather_rows(&indices).unwrap();
+        let from_disk = lazy.gather_rows(&name, &indices).unwrap();
+        assert_eq!(eager.dtype(), dtype);
+        assert_eq!(eager.shape(), [indices.len(), 3]);
+        assert_eq!(eager.to_f64(), expected_rows(3, &indices), "{name}");
+        assert_eq!(from_disk.shape(), eager.shape());
+        assert_eq!(from_disk.as_bytes(), eager.as_bytes(), "{name}");
+    }
+}
+
+#[test]
+fn gathers_bool_and_empty_selections() {
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = FixtureBuilder::new().tensor("mask", Dtype::Bool, &[4, 2], Fill::Sequence);
+    let path = write(dir.path(), "mask.safetensors", fixture);
+    let lazy = LazyReader::open(&path).unwrap();
+
+    let eager = sequence(Dtype::Bool, &[4, 2]).gather_rows(&[3, 3]).unwrap();
+    assert_eq!(
+        eager.as_slice::<bool>().unwrap(),
+        [false, true, false, true]
+    );
+    assert_eq!(
+        lazy.gather_rows("mask", &[3, 3]).unwrap().as_bytes(),
+        eager.as_bytes()
+    );
+
+    let none = lazy.gather_rows("mask", &[]).unwrap();
+    assert_eq!(none.shape(), [0, 2]);
+    assert_eq!(
+        sequence(Dtype::Bool, &[4, 2])
+            .gather_rows(&[])
+            .unwrap()
+            .shape(),
+        [0, 2]
+    );
+}
+
+#[test]
+fn rejects_an_out_of_range_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = FixtureBuilder::new().tensor("embed", Dtype::F32, &[5, 4], Fill::Sequence);
+    let path = write(dir.path(), "embed.safetensors", fixture);
+    let lazy = LazyReader::open(&path).unwrap();
+    let eager = sequence(Dtype::F32, &[5, 4]);
+
+    let indices = [0, 4, 5, 9];
+    for result in [
+        eager.gather_rows(&indices),
+        lazy.gather_rows("embed", &indices),
+    ] {
+        match result {
+            Err(Error::GatherIndex {
+                position,
+                index,
+                shape,
+            }) => assert_eq!((position, index, shape), (2, 5, vec![5, 4])),
+            other => panic!("expected GatherIndex, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn needs_a_matrix() {
+    let dir = tempfile::tempdir().unwrap();
+    let fixture = FixtureBuilder::new().tensor("bias", Dtype::F32, &[5], Fill::Sequence);
+    let path = write(dir.path(), "bias.safetensors", fixture);
+    let lazy = LazyReader::open(&path).unwrap();
+
+    let flat = sequence(Dtype::F32, &[5]);
+    assert!(matches!(
+        flat.gather_rows(&[0]),
+        Err(Error::RankMismatch { expected: 2, .. })
+    ));
+    assert!(matches!(
+        lazy.gather_rows("bias", &[0]),
+        Err(Error::RankMismatch { expected: 2, .. })
+    ));
+    assert!(matches!(
+        lazy.gather_rows("missing", &[0]),
+        Err(Error::TensorNotFound(name)) if name == "missing"
+    ));
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::{filled, write};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Histogram, LazyReader, Reader, Tensor};
+
+#[test]
+fn counts_values_into_equal_bins() {
+    // Range [0, 4] in four bins of width 1; the upper edge goes in the last bin.
+    let tensor =
+        Tensor::from_vec(vec![0.0f32, 0.5, 1.0, 1.5, 2.9, 3.0, 4.0, 4.0], vec![8]).unwrap();
+    let histogram = tensor.histogram(4, Some((0.0, 4.0)));
+    assert_eq!(histogram.counts, [2, 2, 1, 3]);
+    assert_eq!(histogram.binned(), 8);
+    assert_eq!(histogram.bin_edges(0), (0.0, 1.0));
+    assert_eq!(histogram.bin_edges(3), (3.0, 4.0));
+}
+
+#[test]
+fn special_and_out_of_range_values_are_counted_apart() {
+    let values = vec![
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+        -0.5,
+        1.5,
+        1.5,
+        0.25,
+    ];
+    let histogram = Tensor::from_vec(values, vec![8])
+        .unwrap()
+        .histogram(2, Some((0.0, 1.0)));
+    assert_eq!(histogram.counts, [1, 0]);
+    assert_eq!(
+        (histogram.nan, histogram.pos_inf, histogram.neg_inf),
+        (1, 1, 2)
+    );
+    assert_eq!((histogram.below, histogram.above), (1, 2));
+}
+
+#[test]
+fn auto_range_spans_the_finite_values() {
+    let tensor =
+        Tensor::from_vec(vec![-2.0f32, f32::INFINITY, 6.0, 2.0, f32::NAN], vec![5]).unwrap();
+    let histogram = tensor.histogram(4, None);
+    assert_eq!(histogram.range, (-2.0, 6.0));
+    assert_eq!(histogram.counts, [1, 0, 1, 1]);
+
+    // A constant tensor has an empty range and everything lands in bin 0.
+    let constant = filled(Dtype::F16, &[10], Fill::Constant(3.0)).histogram(5, None);
+    assert_eq!(constant.range, (3.0, 3.0));
+    assert_eq!(constant.counts, [10, 0, 0, 0, 0]);
+}
+
+#[test]
+fn percentiles_at_and_between_the_boundaries() {
+    // Ten values, one per bin of width 1 over [0, 10].
+    let tensor = Tensor::from_vec((0..10).map(|i| i as f64 + 0.5).collect(), vec![10]).unwrap();
+    let histogram = tensor.histogram(10, Some((0.0, 10.0)));
+    assert_eq!(histogram.percentile(0.0), Some(0.0));
+    assert_eq!(histogram.percentile(100.0), Some(10.0));
+    assert_eq!(histogram.percentile(50.0), Some(5.0));
+    assert_eq!(histogram.percentile(25.0), Some(2.5));
+    // Out-of-range requests are clamped to 0 and 100.
+    assert_eq!(histogram.percentile(-10.0), Some(0.0));
+    assert_eq!(histogram.percentile(250.0), Some(10.0));
+
+    // With empty outer bins the boundaries are the edges of the occupied ones.
+    let middle = Tensor::from_vec(vec![4.2f64, 4.8, 6.5], vec![3])
+        .unwrap()
+        .histogram(10, Some((0.0, 10.0)));
+    assert_eq!(middle.percentile(0.0), Some(4.0));
+    assert_eq!(middle.percentile(100.0), Some(7.0));
+
+    let nothing_binned = Tensor::from_vec(vec![f32::NAN], vec![1])
+        .unwrap()
+        .histogram(3, Some((0.0, 1.0)));
+    assert_eq!(nothing_binned.percentile(50.0), None);
+}
+
+#[test]
+fn parallel_counts_match_serial_ones() {
+    let tensor = filled(Dtype::F32, &[500, 300], Fill::Random(8));
+    let histogram = tensor.histogram(16, Some((-1.0, 1.0)));
+    let mut serial = Histogram::new(16, (-1.0, 1.0));
+    for value in tensor.to_f64() {
+        serial.add(value);
+    }
+    assert_eq!(histogram, serial);
+    assert_eq!(histogram.binned(), 150_000);
+}
+
+#[test]
+fn readers_give_the_same_histograms() {
+    let dir = tempfile::tempdir().unwrap();
+    let builder = || {
+        FixtureBuilder::new()
+            .tensor("a", Dtype::F32, &[1200, 1000], Fill::Random(1))
+            .tensor("b", Dtype::Bf16, &[64], Fill::Sequence)
+    };
+    let path = write(dir.path(), "model.safetensors", builder());
+    let reader = Reader::from_bytes(&builder().to_bytes()).unwrap();
+    let histograms = reader.histograms(8);
+    assert_eq!(histograms.len(), 2);
+
+    // The lazy reader streams `a` over more than one chunk.
+    let lazy = LazyReader::open(&path).unwrap();
+    for name in ["a", "b"] {
+        let expected = reader.tensors[name].histogram(8, None);
+        assert_eq!(histograms[name], expected, "{name}");
+        assert_eq!(lazy.histogram(name, 8, None).unwrap(), expected, "{name}");
+    }
+}
+
+#[test]
+#[should_panic(expected = "at least one bin")]
+fn zero_bins_panics() {
+    Histogram::new(0, (0.0, 1.0));
+}
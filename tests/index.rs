@@ -0,0 +1,95 @@
+mod common;
+
+use common::{sequence, DTYPES};
+use half::{bf16, f16};
+use safetensors_reader::{Dtype, Error, Scalar, Tensor};
+
+#[test]
+fn reads_corners_in_row_major_order() {
+    // Element (i, j, k) of a [2, 3, 4] sequence is 12i + 4j + k.
+    let tensor = sequence(Dtype::I32, &[2, 3, 4]);
+    assert_eq!(tensor.strides(), [12, 4, 1]);
+    for (index, expected) in [
+        ([0, 0, 0], 0),
+        ([0, 0, 3], 3),
+        ([0, 2, 0], 8),
+        ([1, 0, 0], 12),
+        ([1, 2, 3], 23),
+        ([1, 1, 2], 18),
+    ] {
+        assert_eq!(tensor.get(&index), Some(Scalar::I32(expected)), "{index:?}");
+        assert_eq!(tensor.try_get(&index).unwrap(), Scalar::I32(expected));
+    }
+}
+
+#[test]
+fn returns_the_scalar_of_each_dtype() {
+    for dtype in DTYPES {
+        let value = sequence(dtype, &[2, 2]).get(&[1, 1]).unwrap();
+        let expected = match dtype {
+            Dtype::Bool => Scalar::Bool(true),
+            Dtype::U8 => Scalar::U8(3),
+            Dtype::I8 => Scalar::I8(3),
+            Dtype::U16 => Scalar::U16(3),
+            Dtype::I16 => Scalar::I16(3),
+            Dtype::F16 => Scalar::F16(f16::from_f32(3.0)),
+            Dtype::Bf16 => Scalar::Bf16(bf16::from_f32(3.0)),
+            Dtype::U32 => Scalar::U32(3),
+            Dtype::I32 => Scalar::I32(3),
+            Dtype::F32 => Scalar::F32(3.0),
+            Dtype::U64 => Scalar::U64(3),
+            Dtype::I64 => Scalar::I64(3),
+            Dtype::F64 => Scalar::F64(3.0),
+        };
+        assert_eq!(value, expected, "{dtype:?}");
+    }
+}
+
+#[test]
+fn rank_zero_is_indexed_with_no_coordinates() {
+    let scalar = Tensor::from_vec(vec![2.5f32], vec![]).unwrap();
+    assert!(scalar.strides().is_empty());
+    assert_eq!(scalar.get(&[]), Some(Scalar::F32(2.5)));
+    assert!(matches!(
+        scalar.try_get(&[0]),
+        Err(Error::IndexRank { index, shape }) if index == [0] && shape.is_empty()
+    ));
+}
+
+#[test]
+fn rejects_out_of_bounds_coordinates() {
+    let tensor = sequence(Dtype::F32, &[2, 3]);
+    for (index, axis) in [([2, 0], 0), ([0, 3], 1), ([5, 5], 0)] {
+        assert_eq!(tensor.get(&index), None, "{index:?}");
+        match tensor.try_get(&index) {
+            Err(Error::IndexOutOfBounds {
+                axis: got,
+                index: coordinate,
+                shape,
+            }) => {
+                assert_eq!(got, axis, "{index:?}");
+                assert_eq!(coordinate, index[axis], "{index:?}");
+                assert_eq!(shape, [2, 3]);
+            }
+            other => panic!("{index:?}: expected IndexOutOfBounds, got {other:?}"),
+        }
+    }
+
+    let empty = sequence(Dtype::F32, &[0, 3]);
+    assert_eq!(empty.get(&[0, 0]), None);
+}
+
+#[test]
+fn rejects_the_wrong_number_of_coordinates() {
+    let tensor = sequence(Dtype::U8, &[2, 3]);
+    for index in [&[1][..], &[1, 2, 0], &[]] {
+        assert_eq!(tensor.get(index), None, "{index:?}");
+        assert!(
+            matches!(
+                tensor.try_get(index),
+                Err(Error::IndexRank { index: ref got, ref shape }) if got == index && shape == &[2, 3]
+            ),
+            "{index:?}"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::{filled, sequence, DTYPES};
+use rayon::prelude::*;
+use safetensors_reader::testing::Fill;
+use safetensors_reader::{Dtype, Tensor};
+
+#[test]
+fn iterators_match_to_vec_for_every_dtype() {
+    for dtype in DTYPES {
+        for fill in [Fill::Sequence, Fill::Random(3)] {
+            let tensor = filled(dtype, &[3, 5], fill);
+            let f32s: Vec<f32> = tensor.iter_f32().collect();
+            let f64s: Vec<f64> = tensor.iter_f64().collect();
+            assert_eq!(f32s, tensor.to_f32(), "{dtype:?}");
+            assert_eq!(f64s, tensor.to_f64(), "{dtype:?}");
+
+            let par32: Vec<f32> = tensor.par_iter_f32().collect();
+            let par64: Vec<f64> = tensor.par_iter_f64().collect();
+            assert_eq!(par32, f32s, "{dtype:?}");
+            assert_eq!(par64, f64s, "{dtype:?}");
+        }
+    }
+}
+
+#[test]
+fn next_and_fold_agree() {
+    let tensor = sequence(Dtype::I16, &[10]);
+    let mut iter = tensor.iter_f64();
+    let mut stepped = Vec::new();
+    while let Some(x) = iter.next() {
+        assert_eq!(iter.len(), 9 - stepped.len());
+        stepped.push(x);
+    }
+    assert_eq!(
+        stepped,
+        tensor.iter_f64().fold(Vec::new(), |mut v, x| {
+            v.push(x);
+            v
+        })
+    );
+}
+
+#[test]
+fn reverse_and_nth() {
+    let tensor = sequence(Dtype::Bf16, &[6]);
+    let reversed: Vec<f32> = tensor.iter_f32().rev().collect();
+    assert_eq!(reversed, [5.0, 4.0, 3.0, 2.0, 1.0, 0.0]);
+    let mut iter = tensor.iter_f32();
+    assert_eq!(iter.nth(2), Some(2.0));
+    assert_eq!(iter.next_back(), Some(5.0));
+    assert_eq!(iter.len(), 2);
+}
+
+#[test]
+fn lengths_of_empty_and_scalar_tensors() {
+    let empty = Tensor::from_vec(Vec::<f32>::new(), vec![0, 4]).unwrap();
+    assert_eq!(empty.iter_f32().len(), 0);
+    assert_eq!(empty.iter_f64().next(), None);
+    assert_eq!(empty.par_iter_f32().len(), 0);
+
+    let scalar = Tensor::from_vec(vec![2u8], vec![]).unwrap();
+    assert_eq!(scalar.iter_f32().len(), 1);
+    assert_eq!(scalar.par_iter_f64().len(), 1);
+    assert_eq!(scalar.iter_f64().collect::<Vec<_>>(), [2.0]);
+}
+
+#[test]
+fn parallel_iterator_is_indexed() {
+    let tensor = sequence(Dtype::F32, &[100_000]);
+    let sum: f64 = tensor.par_iter_f64().sum();
+    assert_eq!(sum, (0..100_000).map(|i| i as f64).sum::<f64>());
+
+    let zipped: Vec<(f32, f64)> = tensor
+        .par_iter_f32()
+        .zip(tensor.par_iter_f64())
+        .skip(99_998)
+        .collect();
+    assert_eq!(zipped, [(99_998.0, 99_998.0), (99_999.0, 99_999.0)]);
+    assert_eq!(
+        tensor.par_iter_f32().rev().take(1).collect::<Vec<_>>(),
+        [99_999.0]
+    );
+}
@@ -0,0 +1,117 @@
+use safetensors_reader::{Dtype, Error, Tensor};
+use serde_json::json;
+
+fn json_error(dtype: Dtype, value: serde_json::Value) -> (String, String) {
+    match Tensor::from_json(dtype, &value) {
+        Err(Error::JsonTensor { path, reason }) => (path, reason),
+        other => panic!("expected a JSON tensor error, got {other:?}"),
+    }
+}
+
+#[test]
+fn infers_shape_from_nesting() {
+    let scalar = Tensor::from_json(Dtype::F32, &json!(2.5)).unwrap();
+    assert_eq!(scalar.shape(), [] as [usize; 0]);
+    assert_eq!(scalar.as_slice::<f32>().unwrap(), [2.5]);
+
+    let vector = Tensor::from_json(Dtype::I64, &json!([3, -1, 4])).unwrap();
+    assert_eq!(vector.shape(), [3]);
+    assert_eq!(vector.as_slice::<i64>().unwrap(), [3, -1, 4]);
+
+    let matrix = Tensor::from_json(Dtype::F64, &json!([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]])).unwrap();
+    assert_eq!(matrix.shape(), [2, 3]);
+    assert_eq!(
+        matrix.as_slice::<f64>().unwrap(),
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+    );
+
+    let cube = Tensor::from_json(Dtype::U8, &json!([[[0, 1], [2, 3]], [[4, 5], [6, 7]]])).unwrap();
+    assert_eq!(cube.shape(), [2, 2, 2]);
+    assert_eq!(cube.as_slice::<u8>().unwrap(), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    let mask = Tensor::from_json(Dtype::Bool, &json!([true, false])).unwrap();
+    assert_eq!(mask.as_slice::<bool>().unwrap(), [true, false]);
+}
+
+#[test]
+fn empty_arrays_have_zero_dims() {
+    let empty = Tensor::from_json(Dtype::F32, &json!([])).unwrap();
+    assert_eq!(empty.shape(), [0]);
+    let nested = Tensor::from_json(Dtype::F32, &json!([[], []])).unwrap();
+    assert_eq!(nested.shape(), [2, 0]);
+}
+
+#[test]
+fn converts_floats_to_the_target_dtype() {
+    let values = json!([0.1, -2.5, 65504.0]);
+    for dtype in [Dtype::F16, Dtype::Bf16, Dtype::F32, Dtype::F64] {
+        let tensor = Tensor::from_json(dtype, &values).unwrap();
+        assert_eq!(tensor.dtype(), dtype);
+        let expected = match dtype {
+            Dtype::F16 => [0.1, -2.5, 65504.0].map(|x| half::f16::from_f64(x).to_f64()),
+            Dtype::Bf16 => [0.1, -2.5, 65504.0].map(|x| half::bf16::from_f64(x).to_f64()),
+            Dtype::F32 => [0.1f32 as f64, -2.5, 65504.0],
+            _ => [0.1, -2.5, 65504.0],
+        };
+        assert_eq!(tensor.to_f64(), expected, "{dtype:?}");
+    }
+}
+
+#[test]
+fn rejects_ragged_arrays() {
+    let (path, reason) = json_error(Dtype::F32, json!([[1, 2], [3]]));
+    assert_eq!(path, "$[1]");
+    assert!(reason.contains("expected 2 elements, found 1"), "{reason}");
+
+    let (path, reason) = json_error(Dtype::F32, json!([[1, 2], 3]));
+    assert_eq!(path, "$[1]");
+    assert!(
+        reason.contains("expected an array of 2 elements"),
+        "{reason}"
+    );
+
+    let (path, reason) = json_error(Dtype::F32, json!([1, [2]]));
+    assert_eq!(path, "$[1]");
+    assert!(reason.contains("expected a scalar"), "{reason}");
+
+    let (path, _) = json_error(Dtype::F32, json!([[[1], [2]], [[3], [4, 5]]]));
+    assert_eq!(path, "$[1][1]");
+}
+
+#[test]
+fn rejects_values_out_of_range_for_the_dtype() {
+    let cases = [
+        (Dtype::U8, json!([255, 256]), "$[1]"),
+        (Dtype::I8, json!([[-128], [-129]]), "$[1][0]"),
+        (Dtype::U16, json!(65536), "$"),
+        (Dtype::I32, json!([2147483648i64]), "$[0]"),
+        (Dtype::U64, json!([0, -1]), "$[1]"),
+        (Dtype::I64, json!([u64::MAX]), "$[0]"),
+    ];
+    for (dtype, value, expected) in cases {
+        let (path, reason) = json_error(dtype, value);
+        assert_eq!(path, expected, "{dtype:?}");
+        assert!(reason.contains("out of range"), "{dtype:?}: {reason}");
+    }
+
+    // The extremes themselves are accepted.
+    let extremes = Tensor::from_json(Dtype::I64, &json!([i64::MIN, i64::MAX])).unwrap();
+    assert_eq!(extremes.as_slice::<i64>().unwrap(), [i64::MIN, i64::MAX]);
+    let max = Tensor::from_json(Dtype::U64, &json!([u64::MAX])).unwrap();
+    assert_eq!(max.as_slice::<u64>().unwrap(), [u64::MAX]);
+}
+
+#[test]
+fn rejects_values_of_the_wrong_kind() {
+    let (path, reason) = json_error(Dtype::I32, json!([1, 2.5]));
+    assert_eq!(path, "$[1]");
+    assert!(reason.contains("expected an integer"), "{reason}");
+
+    let (path, reason) = json_error(Dtype::F32, json!(["1.0"]));
+    assert_eq!(path, "$[0]");
+    assert!(reason.contains("expected a number"), "{reason}");
+
+    let (path, reason) = json_error(Dtype::Bool, json!([true, 1]));
+    assert_eq!(path, "$[1]");
+    assert!(reason.contains("expected a bool"), "{reason}");
+}
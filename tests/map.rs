@@ -0,0 +1,88 @@
+mod common;
+
+use common::{filled, sequence};
+use safetensors_reader::testing::Fill;
+use safetensors_reader::{Dtype, Error, Tensor};
+
+const LIMIT: f32 = 3.0;
+
+fn clamp(x: f32) -> f32 {
+    x.clamp(-LIMIT, LIMIT)
+}
+
+/// Values in `[-1, 1)` with large outliers planted at known positions.
+fn with_outliers(shape: &[usize]) -> (Tensor, Vec<(usize, f32)>) {
+    let tensor = filled(Dtype::F32, shape, Fill::Random(21));
+    let mut data = tensor.as_slice::<f32>().unwrap().to_vec();
+    let n = data.len();
+    let outliers = vec![
+        (0, 1.0e6),
+        (n / 3, -250.0),
+        (n / 2, f32::INFINITY),
+        (n - 1, f32::NEG_INFINITY),
+        (n - 2, 3.5),
+    ];
+    for &(i, value) in &outliers {
+        data[i] = value;
+    }
+    (Tensor::from_vec(data, shape.to_vec()).unwrap(), outliers)
+}
+
+#[test]
+fn clamp_gives_the_same_result_both_ways() {
+    // Large enough for rayon to split the work.
+    let shape = [64, 4096];
+    let (tensor, outliers) = with_outliers(&shape);
+    let original = tensor.as_slice::<f32>().unwrap().to_vec();
+
+    let mapped = tensor.map_f32(clamp);
+    let mut in_place = tensor.clone();
+    let ptr = in_place.as_bytes().as_ptr();
+    in_place.map_in_place(clamp).unwrap();
+
+    assert_eq!(mapped.dtype(), Dtype::F32);
+    assert_eq!(mapped.shape(), shape);
+    assert_eq!(in_place.shape(), shape);
+    assert_eq!(in_place.as_bytes().as_ptr(), ptr, "in place reallocated");
+    assert_eq!(mapped.as_bytes(), in_place.as_bytes());
+
+    let values = mapped.as_slice::<f32>().unwrap();
+    for &(i, value) in &outliers {
+        assert_eq!(values[i], value.signum() * LIMIT, "outlier at {i}");
+    }
+    let untouched = (0..values.len()).filter(|i| outliers.iter().all(|&(j, _)| j != *i));
+    for i in untouched {
+        assert_eq!(values[i], original[i], "element {i}");
+    }
+}
+
+#[test]
+fn integer_tensors_go_through_f32() {
+    let tensor = Tensor::from_vec(vec![-100i32, -2, 0, 2, 100, 16_777_217], vec![2, 3]).unwrap();
+    let mapped = tensor.map_f32(clamp);
+    assert_eq!(mapped.dtype(), Dtype::F32);
+    assert_eq!(mapped.shape(), [2, 3]);
+    assert_eq!(
+        mapped.as_slice::<f32>().unwrap(),
+        [-3.0, -2.0, 0.0, 2.0, 3.0, 3.0]
+    );
+    // 2^24 + 1 has no f32 representation and is rounded before `f` sees it.
+    let identity = tensor.map_f32(|x| x);
+    assert_eq!(identity.as_slice::<f32>().unwrap()[5], 16_777_216.0);
+}
+
+#[test]
+fn map_in_place_needs_f32() {
+    for dtype in [Dtype::F16, Dtype::Bf16, Dtype::I32, Dtype::F64] {
+        let mut tensor = sequence(dtype, &[4]);
+        let before = tensor.as_bytes().to_vec();
+        assert!(matches!(
+            tensor.map_in_place(clamp),
+            Err(Error::DtypeMismatch {
+                expected: Dtype::F32,
+                actual,
+            }) if actual == dtype
+        ));
+        assert_eq!(tensor.as_bytes(), before);
+    }
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::{write, DTYPES};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{read_meta, Dtype, LazyReader, MetaTensor, Reader};
+
+fn fixture() -> FixtureBuilder {
+    DTYPES
+        .iter()
+        .enumerate()
+        .fold(FixtureBuilder::new(), |builder, (i, &dtype)| {
+            builder.tensor(&format!("t{i}"), dtype, &[i + 1, 2], Fill::Random(i as u64))
+        })
+        .tensor("scalar", Dtype::F32, &[], Fill::Constant(1.0))
+        .tensor("empty", Dtype::I64, &[0, 3], Fill::Sequence)
+        .metadata("format", "pt")
+}
+
+#[test]
+fn header_alone_describes_every_tensor() {
+    let dir = tempfile::tempdir().unwrap();
+    let full = write(dir.path(), "full.safetensors", fixture());
+    let header_len = LazyReader::open(&full).unwrap().header_len() as usize;
+    // Nothing after the header: any read of the data section would fail.
+    let truncated = write(
+        dir.path(),
+        "truncated.safetensors",
+        fixture().truncate_at(8 + header_len),
+    );
+    assert!(LazyReader::open(&truncated).is_err());
+    assert!(Reader::from_file(&truncated).is_err());
+
+    let real = Reader::from_file(&full).unwrap();
+    assert!(!real.is_placeholder());
+    let meta = read_meta(&truncated).unwrap();
+    assert_eq!(meta, LazyReader::open(&full).unwrap().to_meta());
+    assert_eq!(meta.len(), real.tensors.len());
+    for (name, tensor) in &real.tensors {
+        let expected = MetaTensor::new(tensor.dtype(), tensor.shape().to_vec());
+        assert_eq!(meta[name], expected, "{name}");
+        assert_eq!(
+            meta[name].byte_len().unwrap(),
+            tensor.as_bytes().len() as u64
+        );
+        assert_eq!(meta[name].len(), tensor.shape().iter().product::<usize>());
+    }
+    assert!(meta["empty"].is_empty());
+    assert_eq!(meta["scalar"].len(), 1);
+}
+
+#[test]
+fn placeholder_reader_is_zero_filled_and_marked() {
+    let dir = tempfile::tempdir().unwrap();
+    let full = write(dir.path(), "full.safetensors", fixture());
+    let header_len = LazyReader::open(&full).unwrap().header_len() as usize;
+    let truncated = write(
+        dir.path(),
+        "truncated.safetensors",
+        fixture().truncate_at(8 + header_len),
+    );
+
+    let real = Reader::from_file(&full).unwrap();
+    let placeholder = Reader::from_file_meta(&truncated).unwrap();
+    assert!(placeholder.is_placeholder());
+    assert_eq!(placeholder.metadata, real.metadata);
+    assert_eq!(placeholder.raw_header(), real.raw_header());
+    assert_eq!(placeholder.tensors.len(), real.tensors.len());
+    for (name, tensor) in &real.tensors {
+        let zeros = &placeholder.tensors[name];
+        assert_eq!(zeros.dtype(), tensor.dtype(), "{name}");
+        assert_eq!(zeros.shape(), tensor.shape(), "{name}");
+        assert_eq!(zeros.as_bytes().len(), tensor.as_bytes().len(), "{name}");
+        assert!(zeros.as_bytes().iter().all(|&b| b == 0), "{name}");
+    }
+    assert!(MetaTensor::new(Dtype::F16, vec![2, 2])
+        .zeros()
+        .unwrap()
+        .to_f64()
+        .iter()
+        .all(|&x| x == 0.0));
+}
+
+#[test]
+fn a_cut_header_is_still_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let cut = write(dir.path(), "cut.safetensors", fixture().truncate_at(40));
+    assert!(read_meta(&cut).is_err());
+    assert!(Reader::from_file_meta(&cut).is_err());
+}
@@ -0,0 +1,133 @@
+use safetensors_reader::{Error, LazyReader, Reader, StreamWriter, Tensor};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+struct Quantization {
+    bits: u32,
+    group_size: usize,
+    symmetric: bool,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct RunInfo {
+    run_id: String,
+    dataset_hash: String,
+    quantization: Quantization,
+    layers: Vec<String>,
+    // Not JSON, so it stays a string even when nested values are parsed.
+    step: String,
+}
+
+fn write_checkpoint(path: &Path, metadata: &[(&str, String)]) {
+    let mut writer = StreamWriter::create(path).unwrap();
+    let tensor = Tensor::from_vec(vec![1.0f32, 2.0], vec![2]).unwrap();
+    writer.add_tensor("w", &tensor).unwrap();
+    for (key, value) in metadata {
+        writer.insert_metadata(*key, value.clone());
+    }
+    writer.finish().unwrap();
+}
+
+fn run_metadata() -> Vec<(&'static str, String)> {
+    let quantization = Quantization {
+        bits: 4,
+        group_size: 128,
+        symmetric: false,
+    };
+    vec![
+        ("run_id", "run-7f3a".to_string()),
+        ("dataset_hash", "sha256:abc123".to_string()),
+        (
+            "quantization",
+            serde_json::to_string(&quantization).unwrap(),
+        ),
+        ("layers", r#"["embed", "lm_head"]"#.to_string()),
+        ("step", "12000".to_string()),
+    ]
+}
+
+#[test]
+fn round_trips_a_struct_with_json_in_a_string() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model.safetensors");
+    write_checkpoint(&path, &run_metadata());
+
+    let expected = RunInfo {
+        run_id: "run-7f3a".to_string(),
+        dataset_hash: "sha256:abc123".to_string(),
+        quantization: Quantization {
+            bits: 4,
+            group_size: 128,
+            symmetric: false,
+        },
+        layers: vec!["embed".to_string(), "lm_head".to_string()],
+        step: "12000".to_string(),
+    };
+    let reader = Reader::from_file(&path).unwrap();
+    assert_eq!(reader.metadata_as_nested::<RunInfo>().unwrap(), expected);
+    let lazy = LazyReader::open(&path).unwrap();
+    assert_eq!(lazy.metadata_as_nested::<RunInfo>().unwrap(), expected);
+
+    // Without parsing, the embedded JSON is only a string.
+    let flat: BTreeMap<String, String> = reader.metadata_as().unwrap();
+    assert_eq!(flat["quantization"], run_metadata()[2].1);
+    assert!(matches!(
+        reader.metadata_as::<RunInfo>(),
+        Err(Error::Metadata { ref path, .. }) if path == "layers"
+    ));
+}
+
+#[test]
+fn errors_name_the_key_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model.safetensors");
+    let mut metadata = run_metadata();
+    metadata[2].1 = r#"{"bits": 4, "symmetric": true}"#.to_string();
+    write_checkpoint(&path, &metadata);
+
+    let err = Reader::from_file(&path)
+        .unwrap()
+        .metadata_as_nested::<RunInfo>()
+        .unwrap_err();
+    let Error::Metadata { path, error } = &err else {
+        panic!("expected a metadata error, got {err:?}");
+    };
+    assert_eq!(path, "quantization");
+    assert!(
+        error.to_string().contains("missing field `group_size`"),
+        "{err}"
+    );
+
+    let mut metadata = run_metadata();
+    metadata.retain(|(key, _)| *key != "run_id");
+    write_checkpoint(&dir.path().join("other.safetensors"), &metadata);
+    let err = Reader::from_file(dir.path().join("other.safetensors"))
+        .unwrap()
+        .metadata_as_nested::<RunInfo>()
+        .unwrap_err();
+    assert!(err.to_string().contains("missing field `run_id`"), "{err}");
+}
+
+#[test]
+fn no_metadata_is_an_empty_map() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model.safetensors");
+    write_checkpoint(&path, &[]);
+
+    #[derive(Debug, Default, PartialEq, Deserialize)]
+    struct Optional {
+        #[serde(default)]
+        note: Option<String>,
+    }
+    let reader = Reader::from_file(&path).unwrap();
+    assert_eq!(
+        reader.metadata_as::<Optional>().unwrap(),
+        Optional::default()
+    );
+    assert!(reader
+        .metadata_as::<BTreeMap<String, String>>()
+        .unwrap()
+        .is_empty());
+}
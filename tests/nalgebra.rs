@@ -0,0 +1,63 @@
+#![cfg(feature = "nalgebra")]
+
+use nalgebra::{DMatrix, DVector};
+use safetensors_reader::{Error, Tensor};
+
+#[test]
+fn non_square_matrix_keeps_row_major_layout() {
+    let tensor = Tensor::from_vec(vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0], vec![2, 3]).unwrap();
+    let matrix = tensor.to_dmatrix::<f32>().unwrap();
+    assert_eq!(matrix.shape(), (2, 3));
+    for row in 0..2 {
+        for col in 0..3 {
+            assert_eq!(matrix[(row, col)], (row * 3 + col + 1) as f32);
+        }
+    }
+
+    let back = Tensor::from_dmatrix(&matrix);
+    assert_eq!(back.shape(), [2, 3]);
+    assert_eq!(
+        back.as_slice::<f32>().unwrap(),
+        tensor.as_slice::<f32>().unwrap()
+    );
+}
+
+#[test]
+fn matrix_from_nalgebra_is_row_major() {
+    // Column-major input: columns [1, 4], [2, 5], [3, 6].
+    let matrix = DMatrix::from_column_slice(2, 3, &[1i32, 4, 2, 5, 3, 6]);
+    let tensor = Tensor::from_dmatrix(&matrix);
+    assert_eq!(tensor.shape(), [2, 3]);
+    assert_eq!(tensor.as_slice::<i32>().unwrap(), [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn vector_round_trip() {
+    let tensor = Tensor::from_vec(vec![3.0f64, -1.0, 0.5], vec![3]).unwrap();
+    let vector = tensor.to_dvector::<f64>().unwrap();
+    assert_eq!(vector, DVector::from_vec(vec![3.0, -1.0, 0.5]));
+    let back = Tensor::from_dvector(&vector);
+    assert_eq!(back.shape(), [3]);
+    assert_eq!(back.as_slice::<f64>().unwrap(), [3.0, -1.0, 0.5]);
+}
+
+#[test]
+fn wrong_rank_reports_the_shape() {
+    let tensor = Tensor::from_vec(vec![0.0f32; 8], vec![2, 2, 2]).unwrap();
+    assert!(matches!(
+        tensor.to_dmatrix::<f32>(),
+        Err(Error::RankMismatch { expected: 2, shape }) if shape == [2, 2, 2]
+    ));
+    assert!(matches!(
+        tensor.to_dvector::<f32>(),
+        Err(Error::RankMismatch { expected: 1, shape }) if shape == [2, 2, 2]
+    ));
+    let err = tensor.to_dmatrix::<f32>().unwrap_err();
+    assert!(err.to_string().contains("[2, 2, 2]"), "{err}");
+}
+
+#[test]
+fn wrong_element_type_is_an_error() {
+    let tensor = Tensor::from_vec(vec![1.0f32, 2.0], vec![1, 2]).unwrap();
+    assert!(tensor.to_dmatrix::<f64>().is_err());
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::{sequence, DTYPES};
+use safetensors_reader::{Dtype, Error};
+
+const SHAPE: [usize; 3] = [3, 4, 5];
+
+/// The values of `narrow(dim, start, len)` on a sequence of [`SHAPE`],
+/// computed from each output index.
+fn expected(dim: usize, start: usize, len: usize) -> Vec<f64> {
+    let mut shape = SHAPE;
+    shape[dim] = len;
+    let mut out = Vec::new();
+    for i in 0..shape[0] {
+        for j in 0..shape[1] {
+            for k in 0..shape[2] {
+                let mut index = [i, j, k];
+                index[dim] += start;
+                out.push((index[0] * 20 + index[1] * 5 + index[2]) as f64);
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn narrows_leading_middle_and_trailing_dims() {
+    let tensor = sequence(Dtype::F64, &SHAPE);
+    for (dim, start, len) in [
+        (0, 1, 2),
+        (0, 0, 1),
+        (1, 1, 2),
+        (1, 3, 1),
+        (2, 2, 3),
+        (2, 0, 1),
+        (1, 0, 4),
+    ] {
+        let narrowed = tensor.narrow(dim, start, len).unwrap();
+        let mut shape = SHAPE;
+        shape[dim] = len;
+        assert_eq!(narrowed.shape(), shape, "{dim} {start} {len}");
+        assert_eq!(
+            narrowed.to_f64(),
+            expected(dim, start, len),
+            "{dim} {start} {len}"
+        );
+    }
+}
+
+#[test]
+fn narrows_every_dtype() {
+    for dtype in DTYPES {
+        let narrowed = sequence(dtype, &SHAPE).narrow(1, 1, 2).unwrap();
+        assert_eq!(narrowed.dtype(), dtype);
+        let expected = match dtype {
+            // Sequences of bools alternate false, true by position.
+            Dtype::Bool => expected(1, 1, 2).iter().map(|&x| x % 2.0).collect(),
+            _ => expected(1, 1, 2),
+        };
+        assert_eq!(narrowed.to_f64(), expected, "{dtype:?}");
+    }
+}
+
+#[test]
+fn zero_length_and_rank_one() {
+    let empty = sequence(Dtype::F32, &SHAPE).narrow(2, 5, 0).unwrap();
+    assert_eq!(empty.shape(), [3, 4, 0]);
+    assert_eq!(empty.numel(), 0);
+
+    let vector = sequence(Dtype::I64, &[6]).narrow(0, 2, 3).unwrap();
+    assert_eq!(vector.as_slice::<i64>().unwrap(), [2, 3, 4]);
+}
+
+#[test]
+fn rejects_ranges_past_the_end() {
+    let tensor = sequence(Dtype::F32, &SHAPE);
+    for (dim, start, len) in [(0, 2, 2), (1, 4, 1), (2, 0, 6), (2, usize::MAX, 2)] {
+        match tensor.narrow(dim, start, len) {
+            Err(Error::RangeOutOfBounds {
+                dim: got_dim,
+                start: got_start,
+                len: got_len,
+                shape,
+            }) => {
+                assert_eq!((got_dim, got_start, got_len), (dim, start, len));
+                assert_eq!(shape, SHAPE);
+            }
+            other => panic!("{dim} {start} {len}: expected RangeOutOfBounds, got {other:?}"),
+        }
+    }
+
+    assert!(matches!(
+        tensor.narrow(3, 0, 1),
+        Err(Error::DimOutOfRange { dim: 3, .. })
+    ));
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use common::filled;
+use half::{bf16, f16};
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Error, NormKind, Reader, Tensor};
+
+/// Norms computed directly from the f64 values.
+fn reference(values: &[f64], kind: NormKind) -> f64 {
+    match kind {
+        NormKind::L1 => values.iter().map(|x| x.abs()).sum(),
+        NormKind::L2 => values.iter().map(|x| x * x).sum::<f64>().sqrt(),
+        NormKind::MaxAbs => values.iter().fold(0.0, |acc, x| acc.max(x.abs())),
+    }
+}
+
+const KINDS: [NormKind; 3] = [NormKind::L1, NormKind::L2, NormKind::MaxAbs];
+
+#[test]
+fn norms_by_hand() {
+    let tensor = Tensor::from_vec(vec![3.0f32, -4.0, 0.0, 12.0], vec![2, 2]).unwrap();
+    assert_eq!(tensor.norm(NormKind::L1), 19.0);
+    assert_eq!(tensor.norm(NormKind::L2), 13.0);
+    assert_eq!(tensor.norm(NormKind::MaxAbs), 12.0);
+
+    assert_eq!(tensor.row_norms(NormKind::L2).unwrap(), [5.0, 12.0]);
+    assert_eq!(tensor.row_norms(NormKind::L1).unwrap(), [7.0, 12.0]);
+    assert_eq!(tensor.row_norms(NormKind::MaxAbs).unwrap(), [4.0, 12.0]);
+}
+
+#[test]
+fn matches_the_reference_on_random_data() {
+    // Large enough to be reduced in parallel.
+    for dtype in [Dtype::F32, Dtype::F64, Dtype::Bf16, Dtype::I8] {
+        let tensor = filled(dtype, &[300, 200], Fill::Random(4));
+        let values = tensor.to_f64();
+        for kind in KINDS {
+            let expected = reference(&values, kind);
+            let got = tensor.norm(kind);
+            assert!(
+                (got - expected).abs() <= 1e-9 * expected,
+                "{dtype:?} {kind:?}: {got} vs {expected}"
+            );
+            let rows = tensor.row_norms(kind).unwrap();
+            assert_eq!(rows.len(), 300);
+            for (row, chunk) in rows.iter().zip(values.chunks(200)) {
+                let expected = reference(chunk, kind);
+                assert!(
+                    (row - expected).abs() <= 1e-9 * expected,
+                    "{dtype:?} {kind:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn half_precision_accumulates_in_f64() {
+    // Summed in f16 these would stop growing at 2048 (and in bf16 at 256),
+    // where adding 1 no longer changes the total.
+    let n = 100_000;
+    let ones = Tensor::from_vec(vec![f16::ONE; n], vec![n]).unwrap();
+    assert_eq!(ones.norm(NormKind::L1), n as f64);
+    assert_eq!(ones.norm(NormKind::L2), (n as f64).sqrt());
+    let ones = Tensor::from_vec(vec![bf16::ONE; n], vec![n]).unwrap();
+    assert_eq!(ones.norm(NormKind::L1), n as f64);
+
+    // Every partial sum of this f16 value is exact in f64, whatever the order.
+    let tenth = f16::from_f32(0.1);
+    let tenths = Tensor::from_vec(vec![tenth; n], vec![1, n]).unwrap();
+    let expected = tenth.to_f64() * n as f64;
+    assert_eq!(tenths.norm(NormKind::L1), expected);
+    assert_eq!(tenths.row_norms(NormKind::L1).unwrap(), [expected]);
+}
+
+#[test]
+fn nan_propagates_and_empty_is_zero() {
+    let with_nan = Tensor::from_vec(vec![1.0f64, f64::NAN, 100.0], vec![1, 3]).unwrap();
+    for kind in KINDS {
+        assert!(with_nan.norm(kind).is_nan(), "{kind:?}");
+        assert!(with_nan.row_norms(kind).unwrap()[0].is_nan(), "{kind:?}");
+    }
+
+    let empty = filled(Dtype::F32, &[3, 0], Fill::Sequence);
+    for kind in KINDS {
+        assert_eq!(empty.norm(kind), 0.0);
+        assert_eq!(empty.row_norms(kind).unwrap(), [0.0; 3]);
+    }
+    assert!(matches!(
+        filled(Dtype::F32, &[4], Fill::Sequence).row_norms(NormKind::L2),
+        Err(Error::RankMismatch { expected: 2, .. })
+    ));
+}
+
+#[test]
+fn reader_norms_cover_every_tensor() {
+    let bytes = FixtureBuilder::new()
+        .tensor("a", Dtype::F32, &[4, 4], Fill::Random(1))
+        .tensor("b", Dtype::F16, &[8], Fill::Constant(-0.5))
+        .tensor("c", Dtype::I64, &[], Fill::Constant(-3.0))
+        .to_bytes();
+    let reader = Reader::from_bytes(&bytes).unwrap();
+    let norms = reader.norms(NormKind::L2);
+    assert_eq!(norms.len(), 3);
+    assert_eq!(norms["a"], reader.tensors["a"].norm(NormKind::L2));
+    assert_eq!(norms["b"], 2.0f64.sqrt());
+    assert_eq!(norms["c"], 3.0);
+}
@@ -0,0 +1,252 @@
+use half::bf16;
+use safetensors_reader::{Bf16Policy, Dtype, Error, Tensor};
+use std::path::PathBuf;
+
+/// Fixtures in the `.npy` layout, written by `generate.py`.
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/npy")
+        .join(name)
+}
+
+fn load(name: &str) -> Tensor {
+    Tensor::from_npy(fixture(name)).unwrap()
+}
+
+#[test]
+fn reads_every_supported_descr() {
+    let cases = [
+        ("f2_2x3.npy", Dtype::F16),
+        ("f4_2x3.npy", Dtype::F32),
+        ("i1_2x3.npy", Dtype::I8),
+        ("i2_2x3.npy", Dtype::I16),
+        ("i4_2x3.npy", Dtype::I32),
+        ("i8_2x3.npy", Dtype::I64),
+        ("u1_2x3.npy", Dtype::U8),
+        ("u2_2x3.npy", Dtype::U16),
+        ("u4_2x3.npy", Dtype::U32),
+        ("u8_2x3.npy", Dtype::U64),
+    ];
+    for (name, dtype) in cases {
+        let tensor = load(name);
+        assert_eq!(tensor.dtype(), dtype, "{name}");
+        assert_eq!(tensor.shape(), [2, 3], "{name}");
+        let expected: Vec<f64> = match name {
+            "f2_2x3.npy" => vec![0.0, 0.5, 1.0, 1.5, 2.0, 2.5],
+            _ if name.starts_with('i') => vec![-3.0, -2.0, -1.0, 0.0, 1.0, 2.0],
+            _ => vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+        };
+        assert_eq!(tensor.to_f64(), expected, "{name}");
+    }
+
+    let f8 = load("f8_3.npy");
+    assert_eq!(f8.dtype(), Dtype::F64);
+    assert_eq!(f8.as_slice::<f64>().unwrap(), [0.5, -1.25, 1e300]);
+
+    let mask = load("b1_4.npy");
+    assert_eq!(mask.dtype(), Dtype::Bool);
+    assert_eq!(mask.as_slice::<bool>().unwrap(), [true, false, false, true]);
+}
+
+#[test]
+fn reads_scalar_and_empty_arrays() {
+    let scalar = load("f4_scalar.npy");
+    assert_eq!(scalar.shape(), [] as [usize; 0]);
+    assert_eq!(scalar.as_slice::<f32>().unwrap(), [7.5]);
+
+    let empty = load("f4_empty.npy");
+    assert_eq!(empty.shape(), [0, 3]);
+    assert!(empty.as_slice::<f32>().unwrap().is_empty());
+}
+
+#[test]
+fn reads_format_versions_2_and_3() {
+    for name in ["f4_v2.npy", "f4_v3.npy"] {
+        let tensor = load(name);
+        assert_eq!(tensor.shape(), [2, 3], "{name}");
+        assert_eq!(tensor.to_f64(), load("f4_2x3.npy").to_f64(), "{name}");
+    }
+}
+
+#[test]
+fn rejects_fortran_order() {
+    let err = Tensor::from_npy(fixture("f4_fortran.npy")).unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidNpy(reason) if reason.contains("fortran_order")),
+        "{err}"
+    );
+}
+
+#[test]
+fn rejects_big_endian() {
+    let err = Tensor::from_npy(fixture("f4_big_endian.npy")).unwrap_err();
+    assert!(
+        matches!(&err, Error::InvalidNpy(reason) if reason.contains("big-endian")),
+        "{err}"
+    );
+}
+
+#[test]
+fn rejects_bad_magic() {
+    let mut bytes = std::fs::read(fixture("f4_2x3.npy")).unwrap();
+    bytes[1] = b'X';
+    assert!(matches!(
+        Tensor::from_npy_bytes(&bytes),
+        Err(Error::InvalidNpy(_))
+    ));
+}
+
+// The fixtures come from the separate encoder in `generate.py`, so this
+// checks the writer against a second implementation of the format.
+#[test]
+fn writes_the_same_bytes_as_the_fixtures() {
+    for name in [
+        "f4_2x3.npy",
+        "i8_2x3.npy",
+        "b1_4.npy",
+        "f4_scalar.npy",
+        "f4_empty.npy",
+    ] {
+        let expected = std::fs::read(fixture(name)).unwrap();
+        let mut written = Vec::new();
+        load(name)
+            .write_npy_to(&mut written, Bf16Policy::Error)
+            .unwrap();
+        assert_eq!(written, expected, "{name}");
+    }
+}
+
+#[test]
+fn bf16_is_rejected_or_upcast_by_policy() {
+    let values = [1.0f32, -0.5, 3.0e38, 1.0e-3];
+    let tensor = Tensor::from_vec(values.map(bf16::from_f32).to_vec(), vec![2, 2]).unwrap();
+
+    let err = tensor
+        .write_npy_to(Vec::new(), Bf16Policy::Error)
+        .unwrap_err();
+    assert!(matches!(err, Error::UnsupportedDtype(_)), "{err}");
+
+    let mut written = Vec::new();
+    tensor
+        .write_npy_to(&mut written, Bf16Policy::UpcastF32)
+        .unwrap();
+    let upcast = Tensor::from_npy_bytes(&written).unwrap();
+    assert_eq!(upcast.dtype(), Dtype::F32);
+    assert_eq!(upcast.shape(), [2, 2]);
+    let expected: Vec<f32> = values.iter().map(|&v| bf16::from_f32(v).to_f32()).collect();
+    assert_eq!(upcast.as_slice::<f32>().unwrap(), expected);
+}
+
+#[test]
+fn long_headers_switch_to_version_2() {
+    // Each unit dim adds three bytes ("1, ") to the header dict.
+    for (dims, version) in [(21_000, 1), (22_000, 2)] {
+        let tensor = Tensor::from_vec(vec![2.5f32], vec![1; dims]).unwrap();
+        let mut written = Vec::new();
+        tensor
+            .write_npy_to(&mut written, Bf16Policy::Error)
+            .unwrap();
+
+        assert_eq!(written[6..8], [version, 0], "{dims} dims");
+        let (header_len, preamble) = match version {
+            1 => (u16::from_le_bytes([written[8], written[9]]) as usize, 10),
+            _ => (
+                u32::from_le_bytes(written[8..12].try_into().unwrap()) as usize,
+                12,
+            ),
+        };
+        assert_eq!((preamble + header_len) % 64, 0, "{dims} dims");
+        assert_eq!(written[preamble + header_len - 1], b'\n', "{dims} dims");
+        assert_eq!(written.len(), preamble + header_len + 4, "{dims} dims");
+
+        let back = Tensor::from_npy_bytes(&written).unwrap();
+        assert_eq!(back.shape(), vec![1; dims].as_slice());
+        assert_eq!(back.as_slice::<f32>().unwrap(), [2.5]);
+    }
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npz_round_trips_with_flattened_names() {
+    use safetensors_reader::testing::{Fill, FixtureBuilder};
+    use safetensors_reader::Reader;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("model.npz");
+    let bytes = FixtureBuilder::new()
+        .tensor("model/embed.weight", Dtype::F32, &[2, 3], Fill::Random(3))
+        .tensor("step", Dtype::I64, &[], Fill::Constant(42.0))
+        .tensor("norm", Dtype::Bf16, &[4], Fill::Sequence)
+        .to_bytes();
+    let reader = Reader::from_bytes(&bytes).unwrap();
+
+    let err = reader.write_npz(&path, Bf16Policy::Error).unwrap_err();
+    assert!(matches!(err, Error::UnsupportedDtype(_)), "{err}");
+
+    reader.write_npz(&path, Bf16Policy::UpcastF32).unwrap();
+    let back = Reader::from_npz(&path).unwrap();
+    let mut names: Vec<_> = back.tensors.keys().map(String::as_str).collect();
+    names.sort();
+    assert_eq!(names, ["model_embed.weight", "norm", "step"]);
+    assert_eq!(
+        back.tensors["model_embed.weight"].as_bytes(),
+        reader.tensors["model/embed.weight"].as_bytes()
+    );
+    assert_eq!(back.tensors["step"].dtype(), Dtype::I64);
+    assert_eq!(back.tensors["step"].as_slice::<i64>().unwrap(), [42]);
+    assert_eq!(back.tensors["norm"].dtype(), Dtype::F32);
+    assert_eq!(
+        back.tensors["norm"].as_slice::<f32>().unwrap(),
+        [0.0, 1.0, 2.0, 3.0]
+    );
+
+    // Every member is stored uncompressed.
+    let mut zip = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    for i in 0..zip.len() {
+        let member = zip.by_index(i).unwrap();
+        assert_eq!(member.compression(), zip::CompressionMethod::Stored);
+    }
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn npz_rejects_names_that_collide_once_flattened() {
+    use safetensors_reader::testing::{Fill, FixtureBuilder};
+    use safetensors_reader::Reader;
+
+    let dir = tempfile::tempdir().unwrap();
+    let bytes = FixtureBuilder::new()
+        .tensor("a/b", Dtype::F32, &[1], Fill::Sequence)
+        .tensor("a_b", Dtype::F32, &[1], Fill::Sequence)
+        .to_bytes();
+    let reader = Reader::from_bytes(&bytes).unwrap();
+    let err = reader
+        .write_npz(dir.path().join("model.npz"), Bf16Policy::Error)
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::DuplicateTensor(name) if name == "a_b.npy"),
+        "{err}"
+    );
+}
+
+#[cfg(feature = "npz")]
+#[test]
+fn reads_stored_and_deflated_npz() {
+    use safetensors_reader::Reader;
+
+    for name in ["stored.npz", "deflated.npz"] {
+        let reader = Reader::from_npz(fixture(name)).unwrap();
+        let mut names: Vec<_> = reader.tensors.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, ["labels", "mask", "weights"], "{name}");
+        assert_eq!(
+            reader.tensors["weights"].to_f64(),
+            load("f4_2x3.npy").to_f64()
+        );
+        assert_eq!(reader.tensors["labels"].dtype(), Dtype::I64);
+        assert_eq!(
+            reader.tensors["mask"].as_slice::<bool>().unwrap(),
+            [true, false, false, true]
+        );
+    }
+}
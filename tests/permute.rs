@@ -0,0 +1,105 @@
+mod common;
+
+use common::{sequence, DTYPES};
+use safetensors_reader::{Dtype, Error, Tensor};
+
+/// Every ordering of `0..n`.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    let mut all = Vec::new();
+    for rest in permutations(n - 1) {
+        for i in 0..=rest.len() {
+            let mut order = rest.clone();
+            order.insert(i, n - 1);
+            all.push(order);
+        }
+    }
+    all
+}
+
+/// Permutes by computing each output element's source index directly.
+fn reference(tensor: &Tensor, order: &[usize]) -> Vec<f64> {
+    let shape = tensor.shape();
+    let values = tensor.to_f64();
+    let new_shape: Vec<usize> = order.iter().map(|&axis| shape[axis]).collect();
+    let mut out = Vec::with_capacity(values.len());
+    for flat in 0..values.len() {
+        let mut rest = flat;
+        let mut index = vec![0; shape.len()];
+        for k in (0..new_shape.len()).rev() {
+            index[order[k]] = rest % new_shape[k];
+            rest /= new_shape[k];
+        }
+        let source = index
+            .iter()
+            .zip(shape)
+            .fold(0, |acc, (&i, &dim)| acc * dim + i);
+        out.push(values[source]);
+    }
+    out
+}
+
+#[test]
+fn permute_matches_reference_for_every_4d_order() {
+    let tensor = sequence(Dtype::F32, &[2, 3, 4, 5]);
+    for order in permutations(4) {
+        let permuted = tensor.permute(&order).unwrap();
+        let shape: Vec<usize> = order.iter().map(|&axis| tensor.shape()[axis]).collect();
+        assert_eq!(permuted.shape(), shape, "{order:?}");
+        assert_eq!(permuted.to_f64(), reference(&tensor, &order), "{order:?}");
+    }
+}
+
+#[test]
+fn permute_works_for_every_dtype() {
+    for dtype in DTYPES {
+        let tensor = sequence(dtype, &[2, 3, 4]);
+        let permuted = tensor.permute(&[2, 0, 1]).unwrap();
+        assert_eq!(permuted.dtype(), dtype);
+        assert_eq!(
+            permuted.to_f64(),
+            reference(&tensor, &[2, 0, 1]),
+            "{dtype:?}"
+        );
+    }
+}
+
+#[test]
+fn double_transpose_is_identity() {
+    for shape in [&[7, 5][..], &[70, 33], &[3, 1, 4, 2], &[0, 3]] {
+        let tensor = sequence(Dtype::F64, shape);
+        for d0 in 0..shape.len() {
+            for d1 in 0..shape.len() {
+                let twice = tensor.transpose(d0, d1).unwrap().transpose(d0, d1).unwrap();
+                assert_eq!(twice.shape(), tensor.shape());
+                assert_eq!(twice.to_f64(), tensor.to_f64(), "{shape:?} {d0} {d1}");
+            }
+        }
+    }
+}
+
+#[test]
+fn blocked_2d_transpose_matches_reference() {
+    // Larger than one tile in both directions, with ragged edges.
+    let tensor = sequence(Dtype::F32, &[70, 45]);
+    let transposed = tensor.transpose(0, 1).unwrap();
+    assert_eq!(transposed.shape(), [45, 70]);
+    assert_eq!(transposed.to_f64(), reference(&tensor, &[1, 0]));
+}
+
+#[test]
+fn invalid_axes_are_errors() {
+    let tensor = sequence(Dtype::F32, &[2, 3]);
+    assert!(matches!(
+        tensor.transpose(0, 2),
+        Err(Error::DimOutOfRange { dim: 2, .. })
+    ));
+    for order in [&[0, 0][..], &[0], &[0, 1, 2], &[1, 2]] {
+        assert!(
+            matches!(tensor.permute(order), Err(Error::InvalidPermutation { .. })),
+            "{order:?}"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use safetensors_reader::{Error, Normalize, Tensor, NAN_PIXEL};
+
+fn pgm(tensor: &Tensor, normalize: Normalize) -> Vec<u8> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("out.pgm");
+    tensor.write_pgm(&path, normalize).unwrap();
+    std::fs::read(path).unwrap()
+}
+
+#[test]
+fn min_max_gradient_exact_bytes() {
+    let tensor = Tensor::from_vec((0..8).map(|i| i as f32).collect(), vec![2, 4]).unwrap();
+    let mut expected = b"P5\n4 2\n255\n".to_vec();
+    expected.extend_from_slice(&[1, 37, 74, 110, 146, 182, 219, 255]);
+    assert_eq!(pgm(&tensor, Normalize::MinMax), expected);
+}
+
+#[test]
+fn symmetric_centres_zero_and_marks_nan() {
+    let tensor =
+        Tensor::from_vec(vec![-2.0f64, -1.0, 0.0, 1.0, 2.0, f64::NAN], vec![3, 2]).unwrap();
+    let mut expected = b"P5\n2 3\n255\n".to_vec();
+    expected.extend_from_slice(&[1, 65, 128, 192, 255, NAN_PIXEL]);
+    assert_eq!(pgm(&tensor, Normalize::Symmetric), expected);
+}
+
+#[test]
+fn infinities_are_clamped() {
+    let tensor =
+        Tensor::from_vec(vec![f32::NEG_INFINITY, 0.0, 1.0, f32::INFINITY], vec![1, 4]).unwrap();
+    assert_eq!(pgm(&tensor, Normalize::MinMax)[11..], [1, 1, 255, 255]);
+}
+
+#[test]
+fn constant_tensor_is_mid_gray() {
+    let mut expected = b"P5\n3 2\n255\n".to_vec();
+    expected.extend_from_slice(&[128; 6]);
+    let threes = Tensor::from_vec(vec![3u8; 6], vec![2, 3]).unwrap();
+    assert_eq!(pgm(&threes, Normalize::MinMax), expected);
+    let zeros = Tensor::from_vec(vec![0.0f32; 6], vec![2, 3]).unwrap();
+    assert_eq!(pgm(&zeros, Normalize::Symmetric), expected);
+}
+
+#[test]
+fn non_2d_tensor_reports_its_shape() {
+    let tensor = Tensor::from_vec(vec![0.0f32; 8], vec![2, 2, 2]).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let err = tensor
+        .write_pgm(dir.path().join("out.pgm"), Normalize::MinMax)
+        .unwrap_err();
+    assert!(matches!(&err, Error::RankMismatch { expected: 2, .. }));
+    assert!(err.to_string().contains("[2, 2, 2]"), "{err}");
+    assert!(!dir.path().join("out.pgm").exists());
+}
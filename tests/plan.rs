@@ -0,0 +1,99 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::capture::capture;
+use common::write;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Reader, ReaderOptions};
+
+/// Bytes and calls this process has passed through `read`-like system
+/// calls, and the bytes read to find out, which the next reading includes.
+#[cfg(target_os = "linux")]
+fn io_counters() -> (u64, u64, u64) {
+    let io = std::fs::read_to_string("/proc/self/io").unwrap();
+    let counter = |key: &str| -> u64 {
+        let line = io.lines().find(|line| line.starts_with(key)).unwrap();
+        line[key.len()..].trim().parse().unwrap()
+    };
+    (counter("rchar:"), counter("syscr:"), io.len() as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn io_counters() -> (u64, u64, u64) {
+    (0, 0, 0)
+}
+
+// The only test in this binary, so no other thread reads while it measures.
+#[test]
+fn plan_matches_the_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let builder = FixtureBuilder::new()
+        .tensor("embed.weight", Dtype::F32, &[8, 4], Fill::Random(1))
+        // Rows of 400 bytes, transposed in blocks of 163 rows.
+        .tensor("proj.weight", Dtype::F32, &[300, 100], Fill::Random(2))
+        .tensor("proj.bias", Dtype::F16, &[100], Fill::Sequence)
+        .tensor("lm_head.weight", Dtype::F32, &[8, 4], Fill::Random(1))
+        .overlap_offsets("lm_head.weight")
+        .tensor("step", Dtype::I64, &[], Fill::Constant(3.0))
+        .metadata("format", "pt");
+    let path = write(dir.path(), "model.safetensors", builder);
+    // Starting the pool reads cgroup limits once; keep that out of the counts.
+    rayon::current_num_threads();
+
+    let cases = [
+        ReaderOptions::default(),
+        ReaderOptions::default().transpose_2d(|name, _| name == "proj.weight"),
+        ReaderOptions::default().allow_aliases(true),
+        ReaderOptions::default()
+            .allow_aliases(true)
+            .transpose_2d(|name, _| name.ends_with(".weight")),
+    ];
+    for options in &cases {
+        let plan = options.plan(&path).unwrap();
+        let baseline = io_counters();
+        let before = io_counters();
+        let (reader, records) = capture(|| Reader::from_file_with(&path, options).unwrap());
+        let after = io_counters();
+
+        let reads: Vec<_> = records.spans("read_tensor").collect();
+        let planned_reads = plan.tensors.iter().filter(|t| t.alias_of.is_none());
+        assert_eq!(reads.len(), planned_reads.count(), "{plan}");
+        let tensor_bytes: u64 = reads
+            .iter()
+            .map(|span| span.field("bytes").parse::<u64>().unwrap())
+            .sum();
+        let header_len = reader.header_len().unwrap();
+        assert_eq!(8 + header_len + tensor_bytes, plan.bytes_read, "{plan}");
+
+        if cfg!(target_os = "linux") {
+            // Reading the counters costs system calls of its own, counted
+            // between `baseline` and `before`.
+            let calls = before.1 - baseline.1;
+            let read = (after.0 - before.0 - before.2, after.1 - before.1 - calls);
+            assert_eq!(read, (plan.bytes_read, plan.read_ops as u64), "{plan}");
+        }
+
+        assert_eq!(
+            reader.tensors.len() + reader.aliases.len(),
+            plan.tensors.len()
+        );
+        for tensor in &plan.tensors {
+            assert_eq!(
+                reader.aliases.get(&tensor.name),
+                tensor.alias_of.as_ref(),
+                "{}",
+                tensor.name
+            );
+            let loaded = reader.tensor(&tensor.name).unwrap();
+            assert_eq!(loaded.dtype(), tensor.dtype, "{}", tensor.name);
+            assert_eq!(loaded.shape(), tensor.shape, "{}", tensor.name);
+        }
+        let resident: u64 = reader
+            .tensors
+            .values()
+            .map(|tensor| tensor.as_bytes().len() as u64)
+            .sum();
+        assert_eq!(resident + header_len, plan.resident_bytes);
+    }
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::write;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Framework, LazyReader, Reader};
+use std::collections::HashSet;
+
+/// The key set of a two-layer transformers causal LM.
+fn transformer() -> FixtureBuilder {
+    let mut builder = FixtureBuilder::new()
+        .tensor(
+            "model.embed_tokens.weight",
+            Dtype::Bf16,
+            &[8, 4],
+            Fill::Random(1),
+        )
+        .tensor("model.norm.weight", Dtype::Bf16, &[4], Fill::Constant(1.0))
+        .tensor("lm_head.weight", Dtype::Bf16, &[8, 4], Fill::Random(2));
+    for layer in 0..2 {
+        for proj in ["q_proj", "k_proj", "v_proj", "o_proj"] {
+            builder = builder.tensor(
+                &format!("model.layers.{layer}.self_attn.{proj}.weight"),
+                Dtype::Bf16,
+                &[4, 4],
+                Fill::Random(layer as u64),
+            );
+        }
+    }
+    builder.tensor(
+        "model.layers.1.rotary_emb.inv_freq",
+        Dtype::F32,
+        &[2],
+        Fill::Sequence,
+    )
+}
+
+fn provenance(builder: FixtureBuilder) -> safetensors_reader::Provenance {
+    Reader::from_bytes(&builder.to_bytes())
+        .unwrap()
+        .provenance()
+}
+
+#[test]
+fn pt_format_metadata() {
+    let found = provenance(
+        FixtureBuilder::new()
+            .tensor("weight", Dtype::F32, &[2, 2], Fill::Sequence)
+            .tensor("bias", Dtype::F16, &[2], Fill::Sequence)
+            .metadata("format", "pt"),
+    );
+    assert_eq!(found.format.as_deref(), Some("pt"));
+    assert_eq!(found.framework, Some(Framework::PyTorch));
+    assert_eq!(found.dtypes, HashSet::from([Dtype::F32, Dtype::F16]));
+    assert!(!found.is_transformers_layout());
+    assert_eq!(found.shard, None);
+}
+
+#[test]
+fn no_metadata_gives_no_format() {
+    let found = provenance(FixtureBuilder::new().tensor("x", Dtype::I64, &[3], Fill::Sequence));
+    assert_eq!(found.format, None);
+    assert_eq!(found.framework, None);
+    assert_eq!(found.dtypes, HashSet::from([Dtype::I64]));
+    assert_eq!(found.layer_prefix, None);
+    assert_eq!(found.layer_count, 0);
+}
+
+#[test]
+fn transformer_key_set() {
+    let found = provenance(transformer().metadata("format", "pt"));
+    assert!(found.is_transformers_layout());
+    assert_eq!(found.layer_prefix.as_deref(), Some("model.layers"));
+    assert_eq!(found.layer_count, 2);
+    assert_eq!(found.dtypes, HashSet::from([Dtype::Bf16, Dtype::F32]));
+
+    // GPT-2 style names under `transformer.h`.
+    let gpt2 = provenance(
+        FixtureBuilder::new()
+            .tensor(
+                "transformer.h.0.attn.c_attn.weight",
+                Dtype::F32,
+                &[2],
+                Fill::Sequence,
+            )
+            .tensor(
+                "transformer.h.11.mlp.c_fc.weight",
+                Dtype::F32,
+                &[2],
+                Fill::Sequence,
+            )
+            .tensor("transformer.wte.weight", Dtype::F32, &[2], Fill::Sequence),
+    );
+    assert_eq!(gpt2.layer_prefix.as_deref(), Some("transformer.h"));
+    assert_eq!(gpt2.layer_count, 12);
+
+    // A bare number at the end of a name is not a layer.
+    let flat =
+        provenance(FixtureBuilder::new().tensor("layers.0", Dtype::F32, &[1], Fill::Sequence));
+    assert!(!flat.is_transformers_layout());
+}
+
+#[test]
+fn recognises_other_formats() {
+    for (format, framework) in [
+        ("flax", Framework::Flax),
+        ("tf", Framework::TensorFlow),
+        ("np", Framework::NumPy),
+        ("mlx", Framework::Mlx),
+        ("custom", Framework::Other("custom".to_string())),
+    ] {
+        let found = provenance(
+            FixtureBuilder::new()
+                .tensor("x", Dtype::F32, &[1], Fill::Sequence)
+                .metadata("format", format),
+        );
+        assert_eq!(found.framework, Some(framework), "{format}");
+    }
+}
+
+#[test]
+fn lazy_reader_reads_shard_names() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(
+        dir.path(),
+        "model-00002-of-00004.safetensors",
+        transformer(),
+    );
+    let found = LazyReader::open(&path).unwrap().provenance();
+    assert_eq!(found.shard, Some((2, 4)));
+    assert_eq!(found.layer_prefix.as_deref(), Some("model.layers"));
+
+    // The in-memory reader agrees apart from the file name.
+    let in_memory = Reader::from_file(&path).unwrap().provenance();
+    assert_eq!(in_memory.shard, None);
+    assert_eq!(in_memory.dtypes, found.dtypes);
+
+    let path = write(dir.path(), "model-final-of-run.safetensors", transformer());
+    assert_eq!(LazyReader::open(&path).unwrap().provenance().shard, None);
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "python")]
+
+use pyo3::exceptions::{PyIOError, PyKeyError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use safetensors_reader::python_module;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::Dtype;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+/// Registers the module as `safetensors_reader`, then runs `f` in the
+/// embedded interpreter.
+fn with_module<R>(f: impl for<'py> FnOnce(Python<'py>, Bound<'py, PyModule>) -> R) -> R {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        pyo3::append_to_inittab!(python_module);
+        Python::initialize();
+    });
+    Python::attach(|py| f(py, py.import("safetensors_reader").unwrap()))
+}
+
+fn checkpoint(dir: &Path) -> PathBuf {
+    let path = dir.join("model.safetensors");
+    FixtureBuilder::new()
+        .tensor("embed", Dtype::F32, &[2, 3], Fill::Sequence)
+        .tensor("norm", Dtype::Bf16, &[2], Fill::Constant(1.5))
+        .tensor("step", Dtype::I64, &[], Fill::Constant(7.0))
+        .metadata("format", "pt")
+        .write(&path)
+        .unwrap();
+    path
+}
+
+#[test]
+fn reader_exposes_the_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = checkpoint(dir.path());
+    with_module(|py, module| {
+        let reader = module.getattr("Reader").unwrap().call1((&path,)).unwrap();
+        let names: Vec<String> = reader.call_method0("names").unwrap().extract().unwrap();
+        assert_eq!(names, ["embed", "norm", "step"]);
+        assert_eq!(reader.len().unwrap(), 3);
+        assert!(reader.contains("norm").unwrap());
+        assert!(!reader.contains("missing").unwrap());
+
+        let metadata = reader.call_method0("metadata").unwrap();
+        let metadata = metadata.cast::<PyDict>().unwrap();
+        let format: String = metadata
+            .get_item("format")
+            .unwrap()
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(format, "pt");
+
+        let err = reader.call_method1("get", ("missing",)).unwrap_err();
+        assert!(err.is_instance_of::<PyKeyError>(py), "{err}");
+
+        let err = module
+            .getattr("Reader")
+            .unwrap()
+            .call1((dir.path().join("absent.safetensors"),))
+            .unwrap_err();
+        assert!(err.is_instance_of::<PyIOError>(py), "{err}");
+    });
+}
+
+// Needs NumPy installed in the interpreter PyO3 links against.
+#[test]
+fn tensors_load_as_numpy_arrays() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = checkpoint(dir.path());
+    with_module(|_py, module| {
+        let reader = module.getattr("Reader").unwrap().call1((&path,)).unwrap();
+        let dtype = |array: &Bound<'_, PyAny>| -> String {
+            array
+                .getattr("dtype")
+                .unwrap()
+                .str()
+                .unwrap()
+                .extract()
+                .unwrap()
+        };
+        let shape = |array: &Bound<'_, PyAny>| -> Vec<usize> {
+            array.getattr("shape").unwrap().extract().unwrap()
+        };
+
+        let embed = reader.call_method1("get", ("embed",)).unwrap();
+        assert_eq!(dtype(&embed), "float32");
+        assert_eq!(shape(&embed), [2, 3]);
+        let rows: Vec<Vec<f32>> = embed.call_method0("tolist").unwrap().extract().unwrap();
+        assert_eq!(rows, [[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]);
+
+        let step = reader.call_method1("get", ("step",)).unwrap();
+        assert_eq!(dtype(&step), "int64");
+        assert_eq!(shape(&step), [] as [usize; 0]);
+        assert_eq!(
+            step.call_method0("item").unwrap().extract::<i64>().unwrap(),
+            7
+        );
+
+        // BF16 is upcast by default, or handed over as its raw bits.
+        let norm = reader.call_method1("get", ("norm",)).unwrap();
+        assert_eq!(dtype(&norm), "float32");
+        let values: Vec<f32> = norm.call_method0("tolist").unwrap().extract().unwrap();
+        assert_eq!(values, [1.5, 1.5]);
+        let kwargs = PyDict::new(module.py());
+        kwargs.set_item("bf16_as_uint16", true).unwrap();
+        let bits = reader.call_method("get", ("norm",), Some(&kwargs)).unwrap();
+        assert_eq!(dtype(&bits), "uint16");
+        let bits: Vec<u16> = bits.call_method0("tolist").unwrap().extract().unwrap();
+        assert_eq!(bits, [half::bf16::from_f32(1.5).to_bits(); 2]);
+
+        let all = reader.call_method0("load_all").unwrap();
+        let all = all.cast::<PyDict>().unwrap();
+        let keys: Vec<String> = all.keys().extract().unwrap();
+        assert_eq!(keys, ["embed", "norm", "step"]);
+        let embed_again = all.get_item("embed").unwrap().unwrap();
+        let rows_again: Vec<Vec<f32>> = embed_again
+            .call_method0("tolist")
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(rows_again, rows);
+        assert_eq!(dtype(&all.get_item("norm").unwrap().unwrap()), "float32");
+    });
+}
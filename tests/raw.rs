@@ -0,0 +1,98 @@
+//! Also run under Miri: `cargo +nightly miri test --test raw`.
+
+use safetensors_reader::{Dtype, Error, Tensor};
+
+const DTYPES: [Dtype; 13] = [
+    Dtype::Bool,
+    Dtype::U8,
+    Dtype::I8,
+    Dtype::U16,
+    Dtype::I16,
+    Dtype::F16,
+    Dtype::Bf16,
+    Dtype::U32,
+    Dtype::I32,
+    Dtype::F32,
+    Dtype::U64,
+    Dtype::I64,
+    Dtype::F64,
+];
+
+/// A tensor whose bytes are all distinct where the dtype allows, built
+/// without the file readers so Miri stays fast.
+fn tensor(dtype: Dtype, shape: &[usize]) -> Tensor {
+    let len = dtype.size() * shape.iter().product::<usize>();
+    let bytes: Vec<u8> = match dtype {
+        Dtype::Bool => (0..len).map(|i| (i % 2) as u8).collect(),
+        _ => (0..len).map(|i| (i * 7 + 3) as u8).collect(),
+    };
+    Tensor::from_bytes(dtype, shape.to_vec(), &bytes).unwrap()
+}
+
+#[test]
+fn round_trips_every_dtype() {
+    for dtype in DTYPES {
+        for shape in [&[2, 3][..], &[5], &[], &[0, 4]] {
+            let original = tensor(dtype, shape);
+            let (raw_dtype, raw_shape, raw) = original.clone().into_raw_parts();
+            assert_eq!(raw_dtype, dtype);
+            assert_eq!(raw_shape, shape);
+            assert_eq!(raw.len, shape.iter().product::<usize>());
+            assert!(raw.capacity >= raw.len);
+
+            // SAFETY: the parts come straight from `into_raw_parts`.
+            let rebuilt = unsafe { Tensor::from_raw_parts(raw_dtype, raw_shape, raw) }.unwrap();
+            assert_eq!(rebuilt.dtype(), dtype);
+            assert_eq!(rebuilt.shape(), shape);
+            assert_eq!(
+                rebuilt.as_bytes(),
+                original.as_bytes(),
+                "{dtype:?} {shape:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn buffer_is_not_copied() {
+    let original = tensor(Dtype::F32, &[4, 4]);
+    let address = original.as_bytes().as_ptr();
+    let (dtype, shape, raw) = original.into_raw_parts();
+    assert_eq!(raw.ptr.cast_const(), address);
+    // SAFETY: the parts come straight from `into_raw_parts`.
+    let rebuilt = unsafe { Tensor::from_raw_parts(dtype, shape, raw) }.unwrap();
+    assert_eq!(rebuilt.as_bytes().as_ptr(), address);
+}
+
+#[test]
+fn spare_capacity_survives_the_round_trip() {
+    let mut data = Vec::with_capacity(10);
+    data.extend_from_slice(&[1u16, 2, 3]);
+    let (dtype, shape, raw) = Tensor::from_vec(data, vec![3]).unwrap().into_raw_parts();
+    assert_eq!((raw.len, raw.capacity), (3, 10));
+    // SAFETY: the parts come straight from `into_raw_parts`.
+    let rebuilt = unsafe { Tensor::from_raw_parts(dtype, shape, raw) }.unwrap();
+    assert_eq!(rebuilt.to_f64(), [1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn mismatched_shape_hands_the_buffer_back() {
+    let original = tensor(Dtype::I32, &[2, 3]);
+    let (dtype, _, raw) = original.clone().into_raw_parts();
+
+    // SAFETY: the parts come straight from `into_raw_parts`.
+    let (err, raw) = unsafe { Tensor::from_raw_parts(dtype, vec![4, 2], raw) }.unwrap_err();
+    assert!(matches!(err, Error::ElementCount { ref shape, len: 6 } if shape == &[4, 2]));
+
+    // The product of these wraps around to 6 if not checked.
+    let wrapping = vec![usize::MAX / 2 + 1, 2, 3];
+    // SAFETY: as above; the buffer was handed back untouched.
+    let (err, raw) = unsafe { Tensor::from_raw_parts(dtype, wrapping, raw) }.unwrap_err();
+    assert!(matches!(err, Error::ElementCount { len: 6, .. }));
+
+    // Still owned here, so it can be rebuilt and freed normally, which Miri
+    // checks: a leak or double free would fail the run.
+    // SAFETY: as above.
+    let rebuilt = unsafe { Tensor::from_raw_parts(dtype, vec![3, 2], raw) }.unwrap();
+    assert_eq!(rebuilt.as_bytes(), original.as_bytes());
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use common::write;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{read_raw_header, Dtype, LazyReader, Reader};
+use sha2::{Digest, Sha256};
+
+fn fixture(dir: &std::path::Path) -> std::path::PathBuf {
+    write(
+        dir,
+        "model.safetensors",
+        FixtureBuilder::new()
+            .tensor("embed", Dtype::F32, &[3, 4], Fill::Random(2))
+            .tensor("norm", Dtype::F16, &[5], Fill::Sequence)
+            .metadata("format", "pt")
+            .header_padding(13),
+    )
+}
+
+#[test]
+fn tensor_data_starts_right_after_the_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = fixture(dir.path());
+    let file = std::fs::read(&path).unwrap();
+    let reader = Reader::from_file(&path).unwrap();
+
+    let n = reader.header_len().unwrap();
+    assert_eq!(u64::from_le_bytes(file[..8].try_into().unwrap()), n);
+
+    // Each tensor's bytes sit at `8 + N` plus its data offsets.
+    let lazy = LazyReader::open(&path).unwrap();
+    assert_eq!(lazy.header_len(), n);
+    let data_start = 8 + n as usize;
+    let mut end = data_start;
+    for name in lazy.names() {
+        let (start, stop) = lazy.info(name).unwrap().data_offsets();
+        let bytes = &file[data_start + start as usize..data_start + stop as usize];
+        assert_eq!(bytes, reader.tensors[name].as_bytes(), "{name}");
+        end = end.max(data_start + stop as usize);
+    }
+    assert_eq!(end, file.len());
+}
+
+#[test]
+fn raw_header_hashes_like_the_file_slice() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = fixture(dir.path());
+    let file = std::fs::read(&path).unwrap();
+    let reader = Reader::from_file(&path).unwrap();
+
+    let n = reader.header_len().unwrap() as usize;
+    let slice = &file[8..8 + n];
+    let raw = reader.raw_header_bytes().unwrap();
+    assert_eq!(Sha256::digest(raw), Sha256::digest(slice));
+    assert_eq!(reader.raw_header().unwrap().as_bytes(), raw);
+    // The padding is kept as written.
+    assert!(reader.raw_header().unwrap().ends_with(&" ".repeat(13)));
+
+    let standalone = read_raw_header(&path).unwrap();
+    assert_eq!(Sha256::digest(standalone.as_bytes()), Sha256::digest(slice));
+    assert_eq!(LazyReader::open(&path).unwrap().raw_header(), standalone);
+}
+
+#[test]
+fn bytes_and_discarded_headers() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = fixture(dir.path());
+    let bytes = std::fs::read(&path).unwrap();
+
+    let mut reader = Reader::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        reader.raw_header(),
+        Some(read_raw_header(&path).unwrap().as_str())
+    );
+    reader.discard_raw_header();
+    assert_eq!(reader.raw_header(), None);
+    assert_eq!(reader.raw_header_bytes(), None);
+    assert_eq!(reader.header_len(), None);
+    assert_eq!(reader.tensors.len(), 2);
+}
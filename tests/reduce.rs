@@ -0,0 +1,195 @@
+mod common;
+
+use common::{sequence, DTYPES};
+use safetensors_reader::{Dtype, Error, Tensor};
+
+/// [[1, -2, 3], [4, 0, -6]]
+fn matrix() -> Tensor {
+    Tensor::from_vec(vec![1.0f32, -2.0, 3.0, 4.0, 0.0, -6.0], vec![2, 3]).unwrap()
+}
+
+#[test]
+fn sums_and_means_by_hand() {
+    let m = matrix();
+    let all = m.sum(None, false).unwrap();
+    assert_eq!(all.dtype(), Dtype::F64);
+    assert_eq!(all.shape(), [] as [usize; 0]);
+    assert_eq!(all.as_slice::<f64>().unwrap(), [0.0]);
+
+    let rows = m.sum(Some(1), false).unwrap();
+    assert_eq!(rows.shape(), [2]);
+    assert_eq!(rows.as_slice::<f64>().unwrap(), [2.0, -2.0]);
+    let cols = m.sum(Some(0), true).unwrap();
+    assert_eq!(cols.shape(), [1, 3]);
+    assert_eq!(cols.as_slice::<f64>().unwrap(), [5.0, -2.0, -3.0]);
+
+    let means = m.mean(Some(0), false).unwrap();
+    assert_eq!(means.as_slice::<f64>().unwrap(), [2.5, -1.0, -1.5]);
+    let mean = m.mean(None, true).unwrap();
+    assert_eq!(mean.shape(), [1, 1]);
+    assert_eq!(mean.as_slice::<f64>().unwrap(), [0.0]);
+}
+
+#[test]
+fn reduces_the_middle_dim_of_a_cube() {
+    // Element (i, j, k) of a [2, 3, 2] sequence is 6i + 2j + k, so summing
+    // over j gives 18i + 3k + 6.
+    let cube = sequence(Dtype::I32, &[2, 3, 2]);
+    let sums = cube.sum(Some(1), false).unwrap();
+    assert_eq!(sums.shape(), [2, 2]);
+    assert_eq!(sums.as_slice::<f64>().unwrap(), [6.0, 9.0, 24.0, 27.0]);
+    let maxes = cube.max(Some(1), true).unwrap();
+    assert_eq!(maxes.shape(), [2, 1, 2]);
+    assert_eq!(maxes.as_slice::<i32>().unwrap(), [4, 5, 10, 11]);
+    let argmax = cube.argmax(Some(1), false).unwrap();
+    assert_eq!(argmax.as_slice::<i64>().unwrap(), [2, 2, 2, 2]);
+}
+
+#[test]
+fn extremes_keep_the_dtype() {
+    let m = matrix();
+    let max = m.max(Some(1), false).unwrap();
+    assert_eq!(max.dtype(), Dtype::F32);
+    assert_eq!(max.as_slice::<f32>().unwrap(), [3.0, 4.0]);
+    assert_eq!(
+        m.min(Some(0), false).unwrap().as_slice::<f32>().unwrap(),
+        [1.0, -2.0, -6.0]
+    );
+    assert_eq!(
+        m.min(None, false).unwrap().as_slice::<f32>().unwrap(),
+        [-6.0]
+    );
+    assert_eq!(
+        m.argmax(None, false).unwrap().as_slice::<i64>().unwrap(),
+        [3]
+    );
+    assert_eq!(
+        m.argmax(Some(1), true).unwrap().as_slice::<i64>().unwrap(),
+        [2, 0]
+    );
+
+    for dtype in DTYPES {
+        let tensor = sequence(dtype, &[2, 3]);
+        assert_eq!(tensor.max(None, false).unwrap().dtype(), dtype);
+        let expected = if dtype == Dtype::Bool { 1.0 } else { 5.0 };
+        assert_eq!(
+            tensor.max(None, false).unwrap().to_f64(),
+            [expected],
+            "{dtype:?}"
+        );
+        assert_eq!(
+            tensor.min(None, false).unwrap().to_f64(),
+            [0.0],
+            "{dtype:?}"
+        );
+    }
+}
+
+#[test]
+fn ties_go_to_the_first_index() {
+    let tied = Tensor::from_vec(vec![2i64, 7, 7, 1, 7], vec![5]).unwrap();
+    assert_eq!(
+        tied.argmax(None, false).unwrap().as_slice::<i64>().unwrap(),
+        [1]
+    );
+}
+
+#[test]
+fn large_64_bit_integers_are_compared_exactly() {
+    // These round to the same f64, so an f64 comparison would see a tie.
+    let base = 1u64 << 53;
+    assert_eq!(base as f64, (base + 1) as f64);
+    let unsigned = Tensor::from_vec(vec![base, base + 1, base], vec![3]).unwrap();
+    assert_eq!(
+        unsigned
+            .argmax(None, false)
+            .unwrap()
+            .as_slice::<i64>()
+            .unwrap(),
+        [1]
+    );
+    assert_eq!(
+        unsigned
+            .max(None, false)
+            .unwrap()
+            .as_slice::<u64>()
+            .unwrap(),
+        [base + 1]
+    );
+    assert_eq!(
+        Tensor::from_vec(vec![u64::MAX, u64::MAX - 1], vec![2])
+            .unwrap()
+            .min(None, false)
+            .unwrap()
+            .as_slice::<u64>()
+            .unwrap(),
+        [u64::MAX - 1]
+    );
+
+    let signed = Tensor::from_vec(vec![i64::MIN + 1, i64::MIN, i64::MIN + 1], vec![3]).unwrap();
+    assert_eq!(
+        signed.min(None, false).unwrap().as_slice::<i64>().unwrap(),
+        [i64::MIN]
+    );
+    assert_eq!(
+        signed
+            .argmax(None, false)
+            .unwrap()
+            .as_slice::<i64>()
+            .unwrap(),
+        [0]
+    );
+}
+
+#[test]
+fn nan_propagates_through_extremes() {
+    let values = Tensor::from_vec(vec![1.0f64, f64::NAN, 5.0, f64::NAN], vec![2, 2]).unwrap();
+    assert!(values.max(None, false).unwrap().as_slice::<f64>().unwrap()[0].is_nan());
+    assert!(values.min(None, false).unwrap().as_slice::<f64>().unwrap()[0].is_nan());
+    // The first NaN wins.
+    assert_eq!(
+        values
+            .argmax(None, false)
+            .unwrap()
+            .as_slice::<i64>()
+            .unwrap(),
+        [1]
+    );
+    let cols = values.max(Some(0), false).unwrap();
+    let cols = cols.as_slice::<f64>().unwrap();
+    assert_eq!(cols[0], 5.0);
+    assert!(cols[1].is_nan());
+
+    let half = Tensor::from_vec(vec![half::f16::from_f32(2.0), half::f16::NAN], vec![2]).unwrap();
+    assert_eq!(
+        half.argmax(None, false).unwrap().as_slice::<i64>().unwrap(),
+        [1]
+    );
+}
+
+#[test]
+fn empty_reductions() {
+    let empty = sequence(Dtype::F32, &[2, 0]);
+    assert_eq!(
+        empty
+            .sum(Some(1), false)
+            .unwrap()
+            .as_slice::<f64>()
+            .unwrap(),
+        [0.0, 0.0]
+    );
+    assert!(empty.mean(None, false).unwrap().as_slice::<f64>().unwrap()[0].is_nan());
+    for result in [
+        empty.max(Some(1), false),
+        empty.min(None, false),
+        empty.argmax(None, false),
+    ] {
+        assert!(matches!(result, Err(Error::EmptyReduction { .. })));
+    }
+    // Reducing the non-empty dim of an empty tensor gives an empty result.
+    assert_eq!(empty.max(Some(0), false).unwrap().shape(), [0]);
+    assert!(matches!(
+        matrix().sum(Some(2), false),
+        Err(Error::DimOutOfRange { dim: 2, .. })
+    ));
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::{sequence, DTYPES};
+use safetensors_reader::{Dtype, Error, Tensor};
+
+fn reshape_error(tensor: &Tensor, new_shape: &[isize]) -> Error {
+    let err = tensor.reshape(new_shape).unwrap_err();
+    match &err {
+        Error::Reshape {
+            shape,
+            new_shape: got,
+        } => {
+            assert_eq!(shape, tensor.shape());
+            assert_eq!(got, new_shape);
+        }
+        other => panic!("{new_shape:?}: expected a reshape error, got {other:?}"),
+    }
+    err
+}
+
+#[test]
+fn reshapes_to_an_exact_shape() {
+    for dtype in DTYPES {
+        let tensor = sequence(dtype, &[2, 3, 4]);
+        let reshaped = tensor.reshape(&[4, 6]).unwrap();
+        assert_eq!(reshaped.dtype(), dtype);
+        assert_eq!(reshaped.shape(), [4, 6]);
+        assert_eq!(reshaped.as_bytes(), tensor.as_bytes(), "{dtype:?}");
+    }
+}
+
+#[test]
+fn infers_one_dimension() {
+    let tensor = sequence(Dtype::F32, &[2, 3, 4]);
+    for (new_shape, expected) in [
+        (&[-1][..], &[24][..]),
+        (&[-1, 4], &[6, 4]),
+        (&[2, -1, 3], &[2, 4, 3]),
+        (&[3, 8, -1], &[3, 8, 1]),
+    ] {
+        let reshaped = tensor.reshape(new_shape).unwrap();
+        assert_eq!(reshaped.shape(), expected, "{new_shape:?}");
+        assert_eq!(reshaped.to_f64(), tensor.to_f64());
+    }
+
+    // An empty tensor still has an inferable dimension when the rest are non-zero.
+    let empty = sequence(Dtype::F32, &[0, 3]);
+    assert_eq!(empty.reshape(&[3, -1]).unwrap().shape(), [3, 0]);
+}
+
+#[test]
+fn reshapes_to_and_from_rank_zero() {
+    let scalar = Tensor::from_vec(vec![4.5f64], vec![]).unwrap();
+    assert_eq!(scalar.reshape(&[1, 1]).unwrap().shape(), [1, 1]);
+    assert_eq!(scalar.reshape(&[-1]).unwrap().shape(), [1]);
+    assert_eq!(scalar.reshape(&[]).unwrap().shape(), [] as [usize; 0]);
+
+    let single = Tensor::from_vec(vec![4.5f64], vec![1, 1]).unwrap();
+    let back = single.reshape(&[]).unwrap();
+    assert_eq!(back.shape(), [] as [usize; 0]);
+    assert_eq!(back.as_slice::<f64>().unwrap(), [4.5]);
+
+    reshape_error(&sequence(Dtype::F32, &[2]), &[]);
+}
+
+#[test]
+fn rejects_a_different_element_count() {
+    let tensor = sequence(Dtype::F32, &[2, 3]);
+    let err = reshape_error(&tensor, &[4, 2]);
+    assert!(err.to_string().contains("(8 elements)"), "{err}");
+    reshape_error(&tensor, &[4, -1]);
+    reshape_error(&tensor, &[0, -1]);
+    reshape_error(&tensor, &[-2, 3]);
+}
+
+#[test]
+fn rejects_more_than_one_inferred_dimension() {
+    let tensor = sequence(Dtype::F32, &[2, 3]);
+    let err = reshape_error(&tensor, &[-1, -1]);
+    assert!(
+        err.to_string()
+            .contains("at most one dimension may be inferred"),
+        "{err}"
+    );
+    reshape_error(&tensor, &[-1, 3, -1]);
+}
+
+#[test]
+fn into_reshaped_keeps_the_buffer() {
+    let tensor = sequence(Dtype::I32, &[2, 3]);
+    let ptr = tensor.as_bytes().as_ptr();
+    let reshaped = tensor.into_reshaped(&[3, -1]).unwrap();
+    assert_eq!(reshaped.shape(), [3, 2]);
+    assert_eq!(reshaped.as_bytes().as_ptr(), ptr);
+    assert_eq!(reshaped.as_slice::<i32>().unwrap(), [0, 1, 2, 3, 4, 5]);
+}
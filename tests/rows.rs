@@ -0,0 +1,94 @@
+mod common;
+
+use common::sequence;
+use safetensors_reader::{Dtype, Error, Tensor};
+
+#[test]
+fn rows_hold_consecutive_slices() {
+    let tensor = sequence(Dtype::F32, &[3, 4]);
+    let rows: Vec<_> = tensor.rows().unwrap().collect();
+    assert_eq!(rows.len(), 3);
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(row.index(), i);
+        assert_eq!(row.len(), 4);
+        assert_eq!(row.dtype(), Dtype::F32);
+        let expected: Vec<f32> = (0..4).map(|j| (i * 4 + j) as f32).collect();
+        assert_eq!(row.as_f32().unwrap(), expected);
+        assert_eq!(row.as_slice::<f32>().unwrap(), expected);
+    }
+    assert_eq!(
+        tensor.row(2).unwrap().as_f32().unwrap(),
+        [8.0, 9.0, 10.0, 11.0]
+    );
+
+    // Rows borrow the tensor's storage rather than copying it.
+    let data = tensor.as_slice::<f32>().unwrap();
+    assert_eq!(rows[1].as_f32().unwrap().as_ptr(), data[4..].as_ptr());
+}
+
+#[test]
+fn len_tracks_partial_consumption_from_both_ends() {
+    let tensor = sequence(Dtype::I64, &[5, 2]);
+    let mut rows = tensor.rows().unwrap();
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows.next().unwrap().index(), 0);
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows.next_back().unwrap().index(), 4);
+    assert_eq!(rows.len(), 3);
+    let rest: Vec<_> = rows.map(|row| row.as_i64().unwrap()[0]).collect();
+    assert_eq!(rest, [2, 4, 6]);
+
+    let mut rows = tensor.rows().unwrap();
+    assert_eq!(rows.nth(3).unwrap().index(), 3);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows.size_hint(), (1, Some(1)));
+}
+
+#[test]
+fn rows_of_last_dim_flatten_leading_dims() {
+    let tensor = sequence(Dtype::U16, &[2, 3, 2]);
+    let rows: Vec<_> = tensor.rows_of_last_dim().unwrap().collect();
+    assert_eq!(rows.len(), 6);
+    assert_eq!(rows[4].as_u16().unwrap(), [8, 9]);
+
+    let vector = sequence(Dtype::U16, &[3]);
+    let rows: Vec<_> = vector.rows_of_last_dim().unwrap().collect();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].as_u16().unwrap(), [0, 1, 2]);
+
+    // No rows at all when a leading dim is zero; empty rows when the last is.
+    assert_eq!(sequence(Dtype::U16, &[0, 3]).rows().unwrap().len(), 0);
+    let narrow = sequence(Dtype::U16, &[2, 0]);
+    let empty_rows: Vec<_> = narrow.rows().unwrap().collect();
+    assert_eq!(empty_rows.len(), 2);
+    assert!(empty_rows.iter().all(|row| row.is_empty()));
+}
+
+#[test]
+fn rejects_the_wrong_rank_dtype_and_index() {
+    let cube = sequence(Dtype::F32, &[2, 2, 2]);
+    assert!(matches!(
+        cube.rows().map(|_| ()),
+        Err(Error::RankMismatch { expected: 2, .. })
+    ));
+    assert!(matches!(
+        cube.row(0),
+        Err(Error::RankMismatch { expected: 2, .. })
+    ));
+    let scalar = Tensor::from_vec(vec![1.0f32], vec![]).unwrap();
+    assert!(matches!(
+        scalar.rows_of_last_dim().map(|_| ()),
+        Err(Error::RankMismatch { expected: 1, .. })
+    ));
+
+    let matrix = sequence(Dtype::F32, &[2, 3]);
+    assert!(matches!(
+        matrix.row(2),
+        Err(Error::IndexOutOfBounds {
+            axis: 0,
+            index: 2,
+            ..
+        })
+    ));
+    assert!(matrix.row(0).unwrap().as_f64().is_err());
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::capture::capture;
+use common::write;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Reader, ReaderOptions, Schedule};
+use std::path::Path;
+
+/// Small tensors with the largest last in the file and a medium one in the
+/// middle.
+fn fixture(dir: &Path) -> std::path::PathBuf {
+    let builder = (0..24).fold(FixtureBuilder::new(), |builder, i| {
+        let builder = builder.tensor(&format!("small.{i}"), Dtype::U8, &[16], Fill::Sequence);
+        match i {
+            12 => builder.tensor("medium", Dtype::F32, &[256], Fill::Sequence),
+            _ => builder,
+        }
+    });
+    let builder = builder.tensor("largest", Dtype::F32, &[64, 64], Fill::Random(1));
+    write(dir, "model.safetensors", builder)
+}
+
+/// Tensor names in the order their reads started, loading on a pool of
+/// `threads` threads.
+fn start_order(path: &Path, threads: usize, schedule: Schedule) -> Vec<String> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap();
+    let options = ReaderOptions::default().schedule(schedule);
+    let (_, records) = pool.install(|| capture(|| Reader::from_file_with(path, &options).unwrap()));
+    records
+        .spans("read_tensor")
+        .map(|span| span.field("name").to_string())
+        .collect()
+}
+
+#[test]
+fn largest_tensor_starts_within_the_first_reads() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = fixture(dir.path());
+    for threads in [1, 2, 4, 8] {
+        let order = start_order(&path, threads, Schedule::LargestFirst);
+        assert_eq!(order.len(), 26);
+        let largest = order.iter().position(|name| name == "largest").unwrap();
+        assert!(largest < threads, "{threads} threads: {order:?}");
+        let medium = order.iter().position(|name| name == "medium").unwrap();
+        assert!(medium < 2 * threads, "{threads} threads: {order:?}");
+    }
+}
+
+#[test]
+fn offset_order_keeps_file_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = fixture(dir.path());
+    let order = start_order(&path, 1, Schedule::OffsetOrder);
+    let mut expected: Vec<String> = (0..24).map(|i| format!("small.{i}")).collect();
+    expected.insert(13, "medium".to_string());
+    expected.push("largest".to_string());
+    assert_eq!(order, expected);
+
+    for threads in [2, 4, 8] {
+        let order = start_order(&path, threads, Schedule::OffsetOrder);
+        let largest = order.iter().position(|name| name == "largest").unwrap();
+        assert!(
+            largest >= order.len() - threads,
+            "{threads} threads: {order:?}"
+        );
+    }
+}
+
+#[test]
+fn plan_lists_reads_in_schedule_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = fixture(dir.path());
+    for schedule in [Schedule::LargestFirst, Schedule::OffsetOrder] {
+        let plan = ReaderOptions::default()
+            .schedule(schedule)
+            .plan(&path)
+            .unwrap();
+        let planned: Vec<&str> = plan.tensors.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(planned, start_order(&path, 1, schedule));
+    }
+}
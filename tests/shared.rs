@@ -0,0 +1,77 @@
+mod common;
+
+use std::thread;
+
+use common::{model, sequence};
+use safetensors_reader::{Dtype, Reader, SharedTensor};
+
+#[test]
+fn clones_share_the_same_data() {
+    let tensor = sequence(Dtype::F32, &[64, 64]);
+    let data = tensor.as_bytes().as_ptr();
+    let shared = tensor.into_shared();
+    // Moving into shared storage does not copy.
+    assert_eq!(shared.as_bytes().as_ptr(), data);
+
+    let clone = shared.clone();
+    assert!(SharedTensor::ptr_eq(&shared, &clone));
+    assert_eq!(clone.as_bytes().as_ptr(), data);
+    assert_eq!(clone.shape(), [64, 64]);
+    assert_eq!(clone.as_slice::<f32>().unwrap()[4095], 4095.0);
+
+    let other = sequence(Dtype::F32, &[64, 64]).into_shared();
+    assert!(!SharedTensor::ptr_eq(&shared, &other));
+}
+
+#[test]
+fn the_data_lives_until_the_last_clone_is_dropped() {
+    let shared = sequence(Dtype::F16, &[1000]).into_shared();
+    let data = shared.as_bytes().as_ptr();
+    let clones: Vec<SharedTensor> = (0..4).map(|_| shared.clone()).collect();
+    assert_eq!(SharedTensor::strong_count(&shared), 5);
+
+    let workers: Vec<_> = clones
+        .into_iter()
+        .map(|clone| thread::spawn(move || clone.to_f64().iter().sum::<f64>()))
+        .collect();
+    for worker in workers {
+        assert_eq!(worker.join().unwrap(), 499_500.0);
+    }
+    assert_eq!(SharedTensor::strong_count(&shared), 1);
+
+    // As the only handle left, unwrapping hands back the same allocation,
+    // which is freed when the owned tensor is dropped.
+    let owned = shared.into_owned();
+    assert_eq!(owned.as_bytes().as_ptr(), data);
+}
+
+#[test]
+fn converting_back_copies_only_while_shared() {
+    let shared = sequence(Dtype::I32, &[8]).into_shared();
+    let clone = shared.clone();
+
+    let copy = shared.to_owned();
+    assert_ne!(copy.as_bytes().as_ptr(), shared.as_bytes().as_ptr());
+    let unwrapped = shared.into_owned();
+    assert_ne!(unwrapped.as_bytes().as_ptr(), clone.as_bytes().as_ptr());
+    assert_eq!(SharedTensor::strong_count(&clone), 1);
+    assert_eq!(unwrapped.as_bytes(), clone.as_bytes());
+    assert_eq!(copy.as_bytes(), clone.as_bytes());
+}
+
+#[test]
+fn reader_tensors_move_into_shared_storage() {
+    let reader = Reader::from_bytes(&model().to_bytes()).unwrap();
+    let pointers: Vec<_> = reader
+        .tensors
+        .iter()
+        .map(|(name, tensor)| (name.clone(), tensor.as_bytes().as_ptr()))
+        .collect();
+
+    let shared = reader.into_shared_tensors();
+    assert_eq!(shared.len(), pointers.len());
+    for (name, data) in pointers {
+        assert_eq!(shared[&name].as_bytes().as_ptr(), data, "{name}");
+        assert_eq!(SharedTensor::strong_count(&shared[&name]), 1);
+    }
+}
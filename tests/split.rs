@@ -0,0 +1,74 @@
+mod common;
+
+use common::{filled, sequence, DTYPES};
+use safetensors_reader::testing::Fill;
+use safetensors_reader::{Dtype, Error};
+
+#[test]
+fn fused_qkv_splits_like_three_narrows() {
+    // A fused [q + 2 * kv, hidden] projection; K and V are narrower than Q,
+    // as with grouped-query attention.
+    let (q, kv, hidden) = (8, 4, 8);
+    for dtype in [Dtype::F32, Dtype::F16, Dtype::Bf16] {
+        let qkv = filled(dtype, &[q + 2 * kv, hidden], Fill::Random(11));
+        let parts = qkv.split(0, &[q, kv, kv]).unwrap();
+        assert_eq!(parts.len(), 3);
+        for (part, (start, len)) in parts.iter().zip([(0, q), (q, kv), (q + kv, kv)]) {
+            let narrowed = qkv.narrow(0, start, len).unwrap();
+            assert_eq!(part.shape(), [len, hidden], "{dtype:?}");
+            assert_eq!(part.as_bytes(), narrowed.as_bytes(), "{dtype:?}");
+        }
+    }
+
+    // Fused along the output columns instead, as some checkpoints store it.
+    let qkv = filled(Dtype::F32, &[hidden, q + 2 * kv], Fill::Random(12));
+    let parts = qkv.split(1, &[q, kv, kv]).unwrap();
+    for (part, (start, len)) in parts.iter().zip([(0, q), (q, kv), (q + kv, kv)]) {
+        assert_eq!(part.shape(), [hidden, len]);
+        assert_eq!(part.to_f64(), qkv.narrow(1, start, len).unwrap().to_f64());
+    }
+}
+
+#[test]
+fn chunks_are_equal_splits() {
+    for dtype in DTYPES {
+        let tensor = sequence(dtype, &[2, 6]);
+        let chunks = tensor.chunks(1, 3).unwrap();
+        let splits = tensor.split(1, &[2, 2, 2]).unwrap();
+        assert_eq!(chunks.len(), 3);
+        for (chunk, split) in chunks.iter().zip(&splits) {
+            assert_eq!(chunk.shape(), [2, 2], "{dtype:?}");
+            assert_eq!(chunk.as_bytes(), split.as_bytes(), "{dtype:?}");
+        }
+    }
+    assert_eq!(
+        sequence(Dtype::I64, &[4]).chunks(0, 2).unwrap()[1]
+            .as_slice::<i64>()
+            .unwrap(),
+        [2, 3]
+    );
+}
+
+#[test]
+fn rejects_sizes_that_do_not_cover_the_dim() {
+    let tensor = sequence(Dtype::F32, &[6, 2]);
+    for sizes in [&[2, 2][..], &[4, 4], &[], &[usize::MAX, 7]] {
+        assert!(
+            matches!(
+                tensor.split(0, sizes),
+                Err(Error::SplitSizes { dim: 0, sizes: ref got, .. }) if got == sizes
+            ),
+            "{sizes:?}"
+        );
+    }
+    assert!(matches!(
+        tensor.split(2, &[6]),
+        Err(Error::DimOutOfRange { dim: 2, .. })
+    ));
+    for n in [0, 4] {
+        assert!(matches!(
+            tensor.chunks(0, n),
+            Err(Error::UnevenChunks { dim: 0, chunks, .. }) if chunks == n
+        ));
+    }
+}
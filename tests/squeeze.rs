@@ -0,0 +1,101 @@
+mod common;
+
+use common::sequence;
+use safetensors_reader::{Dtype, Error, Tensor};
+
+#[test]
+fn squeezes_one_size_one_dim() {
+    let tensor = sequence(Dtype::F32, &[1, 3, 1, 2]);
+    for (dim, expected) in [(0, [3, 1, 2]), (2, [1, 3, 2])] {
+        let squeezed = tensor.squeeze(Some(dim)).unwrap();
+        assert_eq!(squeezed.shape(), expected, "{dim}");
+        assert_eq!(squeezed.to_f64(), tensor.to_f64());
+    }
+}
+
+#[test]
+fn rejects_squeezing_a_dim_that_is_not_one() {
+    let tensor = sequence(Dtype::F32, &[1, 3, 2]);
+    for dim in [1, 2] {
+        assert!(
+            matches!(
+                tensor.squeeze(Some(dim)),
+                Err(Error::NotSqueezable { dim: got, ref shape }) if got == dim && shape == &[1, 3, 2]
+            ),
+            "{dim}"
+        );
+    }
+    assert!(matches!(
+        tensor.squeeze(Some(3)),
+        Err(Error::DimOutOfRange { dim: 3, .. })
+    ));
+    let empty = sequence(Dtype::F32, &[0, 1]);
+    assert!(matches!(
+        empty.squeeze(Some(0)),
+        Err(Error::NotSqueezable { dim: 0, .. })
+    ));
+}
+
+#[test]
+fn squeezes_every_size_one_dim() {
+    let tensor = sequence(Dtype::I64, &[1, 2, 1, 3, 1]);
+    let squeezed = tensor.squeeze(None).unwrap();
+    assert_eq!(squeezed.shape(), [2, 3]);
+    assert_eq!(squeezed.as_slice::<i64>().unwrap(), [0, 1, 2, 3, 4, 5]);
+
+    // Nothing to remove leaves the shape alone.
+    assert_eq!(
+        sequence(Dtype::I64, &[2, 3]).squeeze(None).unwrap().shape(),
+        [2, 3]
+    );
+
+    // All dims of size 1 give a rank-0 tensor.
+    let ones = Tensor::from_vec(vec![9u8], vec![1, 1, 1]).unwrap();
+    let scalar = ones.squeeze(None).unwrap();
+    assert_eq!(scalar.shape(), [] as [usize; 0]);
+    assert_eq!(scalar.as_slice::<u8>().unwrap(), [9]);
+}
+
+#[test]
+fn unsqueezes_at_the_front_and_at_rank() {
+    let tensor = sequence(Dtype::F32, &[2, 3]);
+    for (dim, expected) in [(0, [1, 2, 3]), (1, [2, 1, 3]), (2, [2, 3, 1])] {
+        let unsqueezed = tensor.unsqueeze(dim).unwrap();
+        assert_eq!(unsqueezed.shape(), expected, "{dim}");
+        assert_eq!(unsqueezed.to_f64(), tensor.to_f64());
+    }
+    assert!(matches!(
+        tensor.unsqueeze(3),
+        Err(Error::DimOutOfRange { dim: 3, .. })
+    ));
+}
+
+#[test]
+fn rank_zero_input() {
+    let scalar = Tensor::from_vec(vec![2.5f32], vec![]).unwrap();
+    assert_eq!(scalar.squeeze(None).unwrap().shape(), [] as [usize; 0]);
+    assert!(matches!(
+        scalar.squeeze(Some(0)),
+        Err(Error::DimOutOfRange { dim: 0, .. })
+    ));
+
+    let vector = scalar.unsqueeze(0).unwrap();
+    assert_eq!(vector.shape(), [1]);
+    assert_eq!(vector.as_slice::<f32>().unwrap(), [2.5]);
+    assert!(matches!(
+        scalar.unsqueeze(1),
+        Err(Error::DimOutOfRange { dim: 1, .. })
+    ));
+}
+
+#[test]
+fn consuming_variants_keep_the_buffer() {
+    let tensor = sequence(Dtype::F64, &[1, 4]);
+    let ptr = tensor.as_bytes().as_ptr();
+    let squeezed = tensor.squeeze_into(Some(0)).unwrap();
+    assert_eq!(squeezed.shape(), [4]);
+    assert_eq!(squeezed.as_bytes().as_ptr(), ptr);
+    let unsqueezed = squeezed.unsqueeze_into(1).unwrap();
+    assert_eq!(unsqueezed.shape(), [4, 1]);
+    assert_eq!(unsqueezed.as_bytes().as_ptr(), ptr);
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "tch")]
+
+mod common;
+
+use common::{filled, sequence};
+use safetensors_reader::testing::Fill;
+use safetensors_reader::{Dtype, Error, NonContiguous, Tensor};
+use tch::Kind;
+
+#[test]
+fn round_trips_every_supported_dtype() {
+    let kinds = [
+        (Dtype::Bool, Kind::Bool),
+        (Dtype::U8, Kind::Uint8),
+        (Dtype::I8, Kind::Int8),
+        (Dtype::I16, Kind::Int16),
+        (Dtype::F16, Kind::Half),
+        (Dtype::Bf16, Kind::BFloat16),
+        (Dtype::I32, Kind::Int),
+        (Dtype::F32, Kind::Float),
+        (Dtype::I64, Kind::Int64),
+        (Dtype::F64, Kind::Double),
+    ];
+    for (dtype, kind) in kinds {
+        let tensor = filled(dtype, &[2, 3], Fill::Random(9));
+        let converted = tensor.to_tch().unwrap();
+        assert_eq!(converted.kind(), kind, "{dtype:?}");
+        assert_eq!(converted.size(), [2, 3], "{dtype:?}");
+
+        let back = Tensor::from_tch(&converted, NonContiguous::Reject).unwrap();
+        assert_eq!(back.dtype(), dtype, "{dtype:?}");
+        assert_eq!(back.shape(), [2, 3], "{dtype:?}");
+        assert_eq!(back.as_bytes(), tensor.as_bytes(), "{dtype:?}");
+    }
+}
+
+#[test]
+fn keeps_row_major_order() {
+    let converted = sequence(Dtype::F32, &[2, 3]).to_tch().unwrap();
+    assert_eq!(converted.double_value(&[0, 2]), 2.0);
+    assert_eq!(converted.double_value(&[1, 0]), 3.0);
+}
+
+#[test]
+fn non_contiguous_tensors_are_rejected_or_copied() {
+    let transposed = sequence(Dtype::F32, &[2, 3])
+        .to_tch()
+        .unwrap()
+        .transpose(0, 1);
+    assert!(!transposed.is_contiguous());
+
+    let err = Tensor::from_tch(&transposed, NonContiguous::Reject).unwrap_err();
+    assert!(matches!(err, Error::Tch(_)), "{err}");
+    assert!(err.to_string().contains("not contiguous"), "{err}");
+
+    let copied = Tensor::from_tch(&transposed, NonContiguous::Copy).unwrap();
+    assert_eq!(copied.shape(), [3, 2]);
+    assert_eq!(
+        copied.as_slice::<f32>().unwrap(),
+        [0.0, 3.0, 1.0, 4.0, 2.0, 5.0]
+    );
+}
+
+#[test]
+fn unsigned_dtypes_without_a_kind_are_rejected() {
+    for dtype in [Dtype::U16, Dtype::U32, Dtype::U64] {
+        let err = sequence(dtype, &[2]).to_tch().unwrap_err();
+        assert!(
+            matches!(err, Error::UnsupportedDtype(_)),
+            "{dtype:?}: {err}"
+        );
+    }
+}
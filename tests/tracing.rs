@@ -0,0 +1,144 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use common::capture::capture;
+use common::{model, write};
+use safetensors_reader::{LazyReader, Reader, ReaderOptions, UnknownFields};
+use std::io::Write;
+use tracing::Level;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+#[test]
+fn from_file_spans_every_stage_and_tensor() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "model.safetensors", model());
+
+    let (reader, records) = capture(|| Reader::from_file(&path).unwrap());
+    let names = records.span_names();
+    assert_eq!(
+        names[..3],
+        ["Reader::from_file", "parse_header", "validate_header"]
+    );
+    assert_eq!(names[3..], ["read_tensor"; 4]);
+
+    let reads: Vec<_> = records.spans("read_tensor").collect();
+    assert_eq!(reads.len(), reader.tensors.len());
+    for read in &reads {
+        // Worker threads still parent their spans to the load.
+        assert_eq!(read.parent.as_deref(), Some("Reader::from_file"));
+        let bytes: u64 = read.field("bytes").parse().unwrap();
+        assert_eq!(
+            bytes,
+            reader.tensors[read.field("name")].as_bytes().len() as u64
+        );
+        assert!(read.field("elapsed_us").parse::<u64>().is_ok());
+    }
+    assert_eq!(
+        records
+            .spans("validate_header")
+            .next()
+            .unwrap()
+            .field("tensors"),
+        "4"
+    );
+}
+
+#[test]
+fn from_bytes_and_lazy_loads_span_each_tensor() {
+    let bytes = model().to_bytes();
+    let (reader, records) = capture(|| Reader::from_bytes(&bytes).unwrap());
+    assert_eq!(records.span_names()[0], "Reader::from_bytes");
+    assert_eq!(records.spans("read_tensor").count(), reader.tensors.len());
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "model.safetensors", model());
+    let (_, records) = capture(|| {
+        let lazy = LazyReader::open(&path).unwrap();
+        lazy.load("step").unwrap();
+        lazy.load("model.norm.bias").unwrap();
+    });
+    assert_eq!(
+        records.span_names(),
+        [
+            "LazyReader::open",
+            "parse_header",
+            "validate_header",
+            "read_tensor",
+            "read_tensor",
+        ]
+    );
+    let loaded: Vec<_> = records
+        .spans("read_tensor")
+        .map(|span| span.field("name"))
+        .collect();
+    assert_eq!(loaded, ["step", "model.norm.bias"]);
+}
+
+#[test]
+fn fallbacks_emit_debug_events() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "model.safetensors", model());
+
+    let (_, records) = capture(|| {
+        let lazy = LazyReader::open(&path).unwrap();
+        lazy.gather_rows("lm_head.weight", &[2, 0, 1, 0]).unwrap();
+        lazy.gather_rows("lm_head.weight", &[2, 0]).unwrap();
+    });
+    let gathers: Vec<_> = records
+        .events("coalesced row reads")
+        .map(|event| (event.field("rows"), event.field("reads")))
+        .collect();
+    assert_eq!(gathers, [("3", "1"), ("2", "2")]);
+    assert!(records
+        .events("coalesced row reads")
+        .all(|event| event.level == Level::DEBUG));
+
+    let options = ReaderOptions::default().transpose_2d(|name, _| name.ends_with(".weight"));
+    let (_, records) = capture(|| Reader::from_file_with(&path, &options).unwrap());
+    let mut transposed: Vec<_> = records
+        .events("transposing while reading")
+        .map(|event| {
+            assert_eq!(event.parent.as_deref(), Some("read_tensor"));
+            event.field("tensor")
+        })
+        .collect();
+    transposed.sort();
+    assert_eq!(transposed, ["lm_head.weight", "model.embed.weight"]);
+
+    let archive = dir.path().join("model.zip");
+    let mut zip = ZipWriter::new(std::fs::File::create(&archive).unwrap());
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file("model.safetensors", deflated).unwrap();
+    zip.write_all(&model().to_bytes()).unwrap();
+    zip.finish().unwrap();
+    let (_, records) = capture(|| Reader::from_zip(&archive, "model.safetensors").unwrap());
+    let sequential: Vec<_> = records
+        .events("compressed member, reading sequentially")
+        .map(|event| event.field("member"))
+        .collect();
+    assert_eq!(sequential, ["model.safetensors"]);
+}
+
+#[test]
+fn unknown_fields_warn_through_tracing() {
+    let header = r#"{"w":{"dtype":"U8","shape":[1],"data_offsets":[0,1],"scale":2}}"#;
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.push(7);
+
+    let options = ReaderOptions {
+        unknown_fields: UnknownFields::Warn,
+        ..ReaderOptions::default()
+    };
+    let (_, records) = capture(|| Reader::from_bytes_with(&bytes, &options).unwrap());
+    let warnings: Vec<_> = records.events("unknown tensor header field").collect();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].level, Level::WARN);
+    assert_eq!(warnings[0].field("tensor"), "w");
+    assert_eq!(warnings[0].field("field"), "scale");
+
+    let (_, records) = capture(|| Reader::from_bytes(&bytes).unwrap());
+    assert_eq!(records.events("unknown tensor header field").count(), 0);
+}
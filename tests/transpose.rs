@@ -0,0 +1,83 @@
+mod common;
+
+use common::write;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Error, NonMatrix, Reader, ReaderOptions};
+
+/// Matrices selected by name, with the shapes that exercise the block reads:
+/// a square-ish one, a tall-skinny one spanning many blocks and a wide one
+/// whose rows are each longer than a block.
+const MATRICES: [(&str, Dtype, [usize; 2]); 6] = [
+    ("f32.square", Dtype::F32, [37, 53]),
+    ("f32.tall", Dtype::F32, [100_000, 3]),
+    ("f32.wide", Dtype::F32, [2, 40_000]),
+    ("f16.square", Dtype::F16, [37, 53]),
+    ("f16.tall", Dtype::F16, [100_000, 3]),
+    ("f16.wide", Dtype::F16, [3, 40_000]),
+];
+
+fn fixture() -> FixtureBuilder {
+    MATRICES
+        .iter()
+        .fold(FixtureBuilder::new(), |builder, (name, dtype, shape)| {
+            builder.tensor(name, *dtype, shape, Fill::Random(shape[0] as u64))
+        })
+        .tensor("kept", Dtype::F32, &[4, 5], Fill::Sequence)
+        .tensor("bias", Dtype::F32, &[5], Fill::Sequence)
+}
+
+fn transpose_matrices() -> ReaderOptions {
+    ReaderOptions::default().transpose_2d(|name, _| name != "kept" && name != "bias")
+}
+
+#[test]
+fn matches_load_then_transpose() {
+    let dir = tempfile::tempdir().unwrap();
+    let bytes = fixture().to_bytes();
+    let path = write(dir.path(), "weights.safetensors", fixture());
+
+    let plain = Reader::from_file(&path).unwrap();
+    let from_file = Reader::from_file_with(&path, &transpose_matrices()).unwrap();
+    let from_bytes = Reader::from_bytes_with(&bytes, &transpose_matrices()).unwrap();
+    for (name, dtype, [rows, cols]) in MATRICES {
+        let expected = plain.tensors[name].transpose(0, 1).unwrap();
+        assert_eq!(expected.shape(), [cols, rows]);
+        for reader in [&from_file, &from_bytes] {
+            let loaded = &reader.tensors[name];
+            assert_eq!(loaded.dtype(), dtype);
+            assert_eq!(loaded.shape(), [cols, rows], "{name}");
+            assert_eq!(loaded.as_bytes(), expected.as_bytes(), "{name}");
+        }
+    }
+    for name in ["kept", "bias"] {
+        assert_eq!(from_file.tensors[name].shape(), plain.tensors[name].shape());
+        assert_eq!(
+            from_file.tensors[name].as_bytes(),
+            plain.tensors[name].as_bytes()
+        );
+    }
+}
+
+#[test]
+fn selected_tensors_that_are_not_matrices_fail_or_are_skipped() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(dir.path(), "weights.safetensors", fixture());
+    let everything = ReaderOptions::default().transpose_2d(|_, _| true);
+
+    let Err(err) = Reader::from_file_with(&path, &everything) else {
+        panic!("a 1-D tensor was selected for transposition without error");
+    };
+    assert!(matches!(err, Error::RankMismatch { expected: 2, shape } if shape == [5]));
+
+    let skip = ReaderOptions {
+        transpose_non_2d: NonMatrix::Skip,
+        ..everything
+    };
+    let reader = Reader::from_file_with(&path, &skip).unwrap();
+    assert_eq!(reader.tensors["bias"].shape(), [5]);
+    assert_eq!(reader.tensors["kept"].shape(), [5, 4]);
+    assert_eq!(
+        reader.tensors["kept"].as_slice::<f32>().unwrap()[..4],
+        [0.0, 5.0, 10.0, 15.0]
+    );
+}
@@ -0,0 +1,93 @@
+use safetensors_reader::{
+    extract, Error, LazyReader, Reader, ReaderOptions, Selector, UnknownFields,
+};
+use serde_json::json;
+
+/// A file whose tensors each carry a made-up `quant_scheme` field.
+fn write_fixture(path: &std::path::Path) {
+    let header = json!({
+        "__metadata__": {"format": "pt"},
+        "w": {
+            "dtype": "F32",
+            "shape": [2],
+            "data_offsets": [0, 8],
+            "quant_scheme": {"kind": "int4", "group": 32},
+        },
+        "b": {
+            "dtype": "U8",
+            "shape": [2],
+            "data_offsets": [8, 10],
+            "quant_scheme": "none",
+        },
+    })
+    .to_string();
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(bytemuck::cast_slice(&[1.5f32, -2.0]));
+    bytes.extend_from_slice(&[7, 9]);
+    std::fs::write(path, bytes).unwrap();
+}
+
+fn scheme(reader: &LazyReader, name: &str) -> serde_json::Value {
+    reader.info(name).unwrap().extra()["quant_scheme"].clone()
+}
+
+#[test]
+fn unknown_fields_survive_read_write_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let src = dir.path().join("src.safetensors");
+    let dst = dir.path().join("dst.safetensors");
+    write_fixture(&src);
+
+    let first = LazyReader::open(&src).unwrap();
+    assert_eq!(scheme(&first, "w"), json!({"kind": "int4", "group": 32}));
+    assert_eq!(scheme(&first, "b"), json!("none"));
+    assert_eq!(first.info("w").unwrap().extra().len(), 1);
+
+    extract(&src, &dst, &Selector::pattern("*")).unwrap();
+    let second = LazyReader::open(&dst).unwrap();
+    assert_eq!(scheme(&second, "w"), scheme(&first, "w"));
+    assert_eq!(scheme(&second, "b"), scheme(&first, "b"));
+    assert_eq!(second.metadata()["format"], "pt");
+
+    let values = Reader::from_file(&dst).unwrap();
+    assert_eq!(values.tensors["w"].to_f64(), [1.5, -2.0]);
+    assert_eq!(values.tensors["b"].to_f64(), [7.0, 9.0]);
+}
+
+#[test]
+fn policy_controls_unknown_fields() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("src.safetensors");
+    write_fixture(&path);
+
+    for policy in [UnknownFields::Carry, UnknownFields::Warn] {
+        let options = ReaderOptions {
+            unknown_fields: policy,
+            ..Default::default()
+        };
+        assert_eq!(
+            Reader::from_file_with(&path, &options)
+                .unwrap()
+                .tensors
+                .len(),
+            2
+        );
+        let lazy = LazyReader::open_with(&path, &options).unwrap();
+        assert_eq!(scheme(&lazy, "b"), json!("none"));
+    }
+
+    let options = ReaderOptions {
+        unknown_fields: UnknownFields::Error,
+        ..Default::default()
+    };
+    let Err(err) = Reader::from_file_with(&path, &options) else {
+        panic!("unknown field accepted");
+    };
+    // Entries are checked in offset order, so `w` is reported first.
+    assert!(
+        matches!(&err, Error::UnknownField { tensor, field } if tensor == "w" && field == "quant_scheme"),
+        "{err}"
+    );
+    assert!(LazyReader::open_with(&path, &options).is_err());
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "safetensors")]
+
+mod common;
+
+use common::{sequence, DTYPES};
+use safetensors::SafeTensors;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{Dtype, Error, Reader, Tensor};
+use std::collections::HashMap;
+
+#[test]
+fn reads_what_upstream_serializes() {
+    let tensors: Vec<(String, Tensor)> = DTYPES
+        .iter()
+        .map(|&dtype| (format!("{dtype:?}"), sequence(dtype, &[2, 3])))
+        .chain([("scalar".to_string(), sequence(Dtype::F32, &[]))])
+        .collect();
+    let metadata = HashMap::from([("format".to_string(), "pt".to_string())]);
+    let bytes =
+        safetensors::serialize(tensors.iter().map(|(n, t)| (n, t)), Some(metadata)).unwrap();
+
+    let reader = Reader::from_bytes(&bytes).unwrap();
+    assert_eq!(reader.metadata["format"], "pt");
+    assert_eq!(reader.tensors.len(), tensors.len());
+    for (name, expected) in &tensors {
+        let tensor = &reader.tensors[name];
+        assert_eq!(tensor.dtype(), expected.dtype(), "{name}");
+        assert_eq!(tensor.shape(), expected.shape(), "{name}");
+        assert_eq!(tensor.as_bytes(), expected.as_bytes(), "{name}");
+    }
+}
+
+#[test]
+fn upstream_reads_what_we_write() {
+    let bytes = DTYPES
+        .iter()
+        .fold(FixtureBuilder::new(), |builder, &dtype| {
+            builder.tensor(&format!("{dtype:?}"), dtype, &[3, 2], Fill::Random(7))
+        })
+        .metadata("format", "pt")
+        .to_bytes();
+    let ours = Reader::from_bytes(&bytes).unwrap();
+
+    let theirs = SafeTensors::deserialize(&bytes).unwrap();
+    let (_, metadata) = SafeTensors::read_metadata(&bytes).unwrap();
+    assert_eq!(metadata.metadata().as_ref().unwrap()["format"], "pt");
+    assert_eq!(theirs.len(), DTYPES.len());
+    for dtype in DTYPES {
+        let name = format!("{dtype:?}");
+        let view = theirs.tensor(&name).unwrap();
+        assert_eq!(view.dtype(), dtype.into());
+        assert_eq!(view.data(), ours.tensors[&name].as_bytes());
+
+        let back = Tensor::try_from(&view).unwrap();
+        assert_eq!(back.dtype(), dtype);
+        assert_eq!(back.shape(), [3, 2]);
+        assert_eq!(back.as_bytes(), ours.tensors[&name].as_bytes());
+    }
+}
+
+#[test]
+fn tensors_round_trip_through_upstream() {
+    let original: HashMap<String, Tensor> = DTYPES
+        .iter()
+        .map(|&dtype| (format!("{dtype:?}"), sequence(dtype, &[4])))
+        .collect();
+    let bytes = safetensors::serialize(&original, None).unwrap();
+    let read = Reader::from_bytes(&bytes).unwrap().tensors;
+    let again = safetensors::serialize(&read, None).unwrap();
+    assert_eq!(again, bytes);
+}
+
+#[test]
+fn dtypes_map_both_ways() {
+    for dtype in DTYPES {
+        let theirs: safetensors::Dtype = dtype.into();
+        assert_eq!(Dtype::try_from(theirs).unwrap(), dtype);
+    }
+    assert!(matches!(
+        Dtype::try_from(safetensors::Dtype::F8_E4M3),
+        Err(Error::UnsupportedDtype(_))
+    ));
+}
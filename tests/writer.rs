@@ -0,0 +1,132 @@
+use safetensors_reader::{Dtype, Error, LazyReader, Reader, StreamWriter, Tensor};
+use std::io;
+
+const GIB: u64 = 1 << 30;
+const MIB: u64 = 1 << 20;
+
+/// Fills `chunk` with the generated bytes starting at `position` of a
+/// tensor of `len` bytes: a pattern in the first and last MiB and zeros in
+/// between, so the file can be sparse. Chunks never straddle those regions.
+fn generate(chunk: &mut [u8], position: u64, len: u64) {
+    if position < MIB || position >= len - MIB {
+        for (i, byte) in chunk.iter_mut().enumerate() {
+            *byte = ((position + i as u64) % 251) as u8 + 1;
+        }
+    } else {
+        chunk.fill(0);
+    }
+}
+
+#[test]
+fn sparse_multi_gigabyte_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("big.safetensors");
+    let shape = [3, GIB as usize];
+    let len = 3 * GIB;
+
+    let mut writer = StreamWriter::create(&path).unwrap();
+    let head = Tensor::from_vec(vec![1.5f32, -2.0, 3.25], vec![3]).unwrap();
+    writer.add_tensor("head", &head).unwrap();
+    let mut position = 0;
+    writer
+        .add("big", Dtype::U8, &shape, |chunk| {
+            generate(chunk, position, len);
+            position += chunk.len() as u64;
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(position, len);
+    let tail = Tensor::from_vec(vec![7i64, 8, 9], vec![3]).unwrap();
+    writer.add_tensor("tail", &tail).unwrap();
+    writer.insert_metadata("note", "sparse");
+    writer.finish().unwrap();
+
+    let reader = LazyReader::open(&path).unwrap();
+    let file_len = std::fs::metadata(&path).unwrap().len();
+    assert_eq!(file_len, 8 + reader.header_len() + 12 + len + 24);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let allocated = std::fs::metadata(&path).unwrap().blocks() * 512;
+        assert!(allocated < 64 * MIB, "{allocated} bytes allocated on disk");
+    }
+    assert_eq!(reader.metadata()["note"], "sparse");
+    assert_eq!(reader.names(), ["head", "big", "tail"]);
+    assert_eq!(reader.info("big").unwrap().shape(), shape);
+    assert_eq!(
+        reader.info("tail").unwrap().data_offsets(),
+        (12 + len, 36 + len)
+    );
+    assert_eq!(reader.load("head").unwrap().to_f64(), head.to_f64());
+    assert_eq!(reader.load("tail").unwrap().to_f64(), tail.to_f64());
+
+    let mut position = 0;
+    let mut expected = vec![0u8; MIB as usize];
+    for chunk in reader.chunks("big", MIB as usize).unwrap() {
+        let chunk = chunk.unwrap();
+        generate(&mut expected, position, len);
+        assert!(chunk.as_bytes() == expected, "mismatch at byte {position}");
+        position += MIB;
+    }
+    assert_eq!(position, len);
+}
+
+#[test]
+fn small_round_trip_through_reader() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("small.safetensors");
+    let a = Tensor::from_vec(vec![1u16, 2, 3, 4, 5, 6], vec![2, 3]).unwrap();
+    let mut writer = StreamWriter::create(&path).unwrap();
+    writer.add_tensor("a", &a).unwrap();
+    writer
+        .add("zeros", Dtype::F64, &[4], |chunk| {
+            chunk.fill(0);
+            Ok(())
+        })
+        .unwrap();
+    writer.finish().unwrap();
+
+    let reader = Reader::from_file(&path).unwrap();
+    assert_eq!(reader.tensors["a"].to_f64(), a.to_f64());
+    assert_eq!(reader.tensors["zeros"].to_f64(), [0.0; 4]);
+    assert!(!path.with_extension("safetensors.partial").exists());
+}
+
+#[test]
+fn add_rejects_bad_names_and_lengths() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut writer = StreamWriter::create(dir.path().join("bad.safetensors")).unwrap();
+    writer
+        .add_from_reader("x", Dtype::U8, &[2], &[1u8, 2][..])
+        .unwrap();
+
+    let duplicate = writer.add_from_reader("x", Dtype::U8, &[2], &[1u8, 2][..]);
+    assert!(matches!(duplicate, Err(Error::DuplicateTensor(name)) if name == "x"));
+
+    let reserved = writer.add_from_reader("__metadata__", Dtype::U8, &[1], &[0u8][..]);
+    assert!(matches!(reserved, Err(Error::InvalidHeader(_))));
+
+    let short = writer.add_from_reader("y", Dtype::F32, &[2], &[0u8; 7][..]);
+    assert!(matches!(
+        short,
+        Err(Error::LengthMismatch {
+            expected: 8,
+            actual: 7,
+            ..
+        })
+    ));
+    let long = writer.add_from_reader("y", Dtype::F32, &[2], &[0u8; 9][..]);
+    assert!(matches!(long, Err(Error::LengthMismatch { actual: 9, .. })));
+
+    let failing = writer.add("z", Dtype::U8, &[4], |_| Err(io::Error::other("boom")));
+    assert!(failing.is_err());
+
+    // Failed adds leave nothing behind.
+    writer
+        .add_from_reader("y", Dtype::U8, &[1], &[5u8][..])
+        .unwrap();
+    writer.finish().unwrap();
+    let reader = Reader::from_file(dir.path().join("bad.safetensors")).unwrap();
+    assert_eq!(reader.tensors.len(), 2);
+    assert_eq!(reader.tensors["y"].to_f64(), [5.0]);
+}
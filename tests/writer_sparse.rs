@@ -0,0 +1,41 @@
+use safetensors_reader::{Dtype, LazyReader, StreamWriter};
+
+/// Bytes this process has passed through `write`-like calls so far.
+#[cfg(target_os = "linux")]
+fn bytes_written() -> u64 {
+    let io = std::fs::read_to_string("/proc/self/io").unwrap();
+    let line = io.lines().find(|line| line.starts_with("wchar:")).unwrap();
+    line["wchar:".len()..].trim().parse().unwrap()
+}
+
+// The only test in this binary, so no other thread writes while it measures.
+#[cfg(target_os = "linux")]
+#[test]
+fn short_zero_tail_is_skipped_not_written() {
+    const MIB: usize = 1 << 20;
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("zeros.safetensors");
+    // One full chunk and a half chunk after it, all zero.
+    let shape = [MIB + MIB / 2];
+
+    let before = bytes_written();
+    let mut writer = StreamWriter::create(&path).unwrap();
+    writer
+        .add("zeros", Dtype::U8, &shape, |chunk| {
+            chunk.fill(0);
+            Ok(())
+        })
+        .unwrap();
+    writer.finish().unwrap();
+    let written = bytes_written() - before;
+
+    // Only the header is written; both zero chunks become holes.
+    assert!(written < 4096, "{written} bytes written");
+    let reader = LazyReader::open(&path).unwrap();
+    assert_eq!(
+        std::fs::metadata(&path).unwrap().len(),
+        8 + reader.header_len() + shape[0] as u64
+    );
+    let zeros = reader.load("zeros").unwrap();
+    assert!(zeros.as_bytes().iter().all(|&byte| byte == 0));
+}
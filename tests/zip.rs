@@ -0,0 +1,146 @@
+#![cfg(feature = "zip")]
+
+mod common;
+
+use common::model;
+use safetensors_reader::testing::{Fill, FixtureBuilder};
+use safetensors_reader::{list_safetensors_members, Dtype, Error, LazyReader, Reader};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// Writes an archive holding `model.safetensors` with `method`, a config file
+/// and a second checkpoint under a directory.
+fn archive(dir: &Path, method: CompressionMethod) -> PathBuf {
+    let path = dir.join(format!("{method:?}.zip"));
+    let mut zip = ZipWriter::new(std::fs::File::create(&path).unwrap());
+    let options = SimpleFileOptions::default().compression_method(method);
+    zip.start_file("config.json", options).unwrap();
+    zip.write_all(br#"{"hidden_size": 3}"#).unwrap();
+    zip.start_file("model.safetensors", options).unwrap();
+    zip.write_all(&model().to_bytes()).unwrap();
+    zip.start_file("extra/adapter.safetensors", options)
+        .unwrap();
+    let adapter = FixtureBuilder::new().tensor("lora.a", Dtype::F32, &[2], Fill::Sequence);
+    zip.write_all(&adapter.to_bytes()).unwrap();
+    zip.finish().unwrap();
+    path
+}
+
+fn assert_matches_model(reader: &Reader) {
+    let expected = Reader::from_bytes(&model().to_bytes()).unwrap();
+    assert_eq!(reader.tensors.len(), expected.tensors.len());
+    for (name, tensor) in &expected.tensors {
+        assert_eq!(reader.tensors[name].dtype(), tensor.dtype(), "{name}");
+        assert_eq!(reader.tensors[name].shape(), tensor.shape(), "{name}");
+        assert_eq!(reader.tensors[name].as_bytes(), tensor.as_bytes(), "{name}");
+    }
+    assert_eq!(reader.metadata["format"], "pt");
+}
+
+#[test]
+fn lists_safetensors_members_in_archive_order() {
+    let dir = tempfile::tempdir().unwrap();
+    for method in [CompressionMethod::Stored, CompressionMethod::Deflated] {
+        let path = archive(dir.path(), method);
+        assert_eq!(
+            list_safetensors_members(&path).unwrap(),
+            ["model.safetensors", "extra/adapter.safetensors"]
+        );
+    }
+}
+
+#[test]
+fn loads_stored_and_deflated_members() {
+    let dir = tempfile::tempdir().unwrap();
+    for method in [CompressionMethod::Stored, CompressionMethod::Deflated] {
+        let path = archive(dir.path(), method);
+        assert_matches_model(&Reader::from_zip(&path, "model.safetensors").unwrap());
+        let adapter = Reader::from_zip(&path, "extra/adapter.safetensors").unwrap();
+        assert_eq!(adapter.tensors["lora.a"].to_f64(), [0.0, 1.0]);
+    }
+
+    let path = archive(dir.path(), CompressionMethod::Stored);
+    assert!(Reader::from_zip(&path, "missing.safetensors").is_err());
+}
+
+#[test]
+fn lazy_loads_read_stored_members_in_place() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = archive(dir.path(), CompressionMethod::Stored);
+    let lazy = LazyReader::from_zip(&path, "model.safetensors").unwrap();
+    let expected = Reader::from_bytes(&model().to_bytes()).unwrap();
+    assert_eq!(
+        lazy.load("lm_head.weight").unwrap().as_bytes(),
+        expected.tensors["lm_head.weight"].as_bytes()
+    );
+
+    // Overwrite `step` inside the archive without fixing the member's CRC.
+    // Reading the member through the zip decoder would now fail its checksum,
+    // but ranged reads of the other tensors never look at it.
+    let (start, _) = lazy.info("step").unwrap().data_offsets();
+    let mut zip = ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+    let member_start = zip
+        .by_name("model.safetensors")
+        .unwrap()
+        .data_start()
+        .unwrap();
+    let data_start = member_start + 8 + lazy.header_len();
+    let mut bytes = std::fs::read(&path).unwrap();
+    let step = (data_start + start) as usize;
+    bytes[step..step + 8].copy_from_slice(&7i64.to_le_bytes());
+    std::fs::write(&path, bytes).unwrap();
+
+    let lazy = LazyReader::from_zip(&path, "model.safetensors").unwrap();
+    assert_eq!(lazy.load("step").unwrap().to_f64(), [7.0]);
+    assert_eq!(
+        lazy.load("model.embed.weight").unwrap().to_f64(),
+        expected.tensors["model.embed.weight"].to_f64()
+    );
+}
+
+#[test]
+fn lazy_loads_reject_compressed_members() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = archive(dir.path(), CompressionMethod::Deflated);
+    let Err(err) = LazyReader::from_zip(&path, "model.safetensors") else {
+        panic!("opened a deflated member lazily");
+    };
+    assert!(matches!(err, Error::CompressedMember(member) if member == "model.safetensors"));
+}
+
+#[test]
+fn empty_tensors_sharing_a_start_stream_in_order() {
+    // Each empty tensor starts where the next tensor does. Streaming a
+    // deflated member visits tensors in offset order, and must not take the
+    // empty one for an overlap whichever order the header map yields.
+    let builder = (0..16).fold(FixtureBuilder::new(), |builder, i| {
+        builder
+            .tensor(&format!("z{i}.empty"), Dtype::F32, &[0, 4], Fill::Sequence)
+            .tensor(
+                &format!("a{i}.weight"),
+                Dtype::F32,
+                &[2],
+                Fill::Constant(i as f64),
+            )
+    });
+    let bytes = builder.to_bytes();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("empty.zip");
+    let mut zip = ZipWriter::new(std::fs::File::create(&path).unwrap());
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    zip.start_file("model.safetensors", deflated).unwrap();
+    zip.write_all(&bytes).unwrap();
+    zip.finish().unwrap();
+
+    let reader = Reader::from_zip(&path, "model.safetensors").unwrap();
+    assert_eq!(reader.tensors.len(), 32);
+    for i in 0..16 {
+        assert_eq!(reader.tensors[&format!("z{i}.empty")].shape(), [0, 4]);
+        assert_eq!(
+            reader.tensors[&format!("a{i}.weight")].to_f64(),
+            [i as f64; 2]
+        );
+    }
+}